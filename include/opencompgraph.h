@@ -29,12 +29,17 @@
 #include <opencompgraph/internal/debug.h>
 #include <opencompgraph/internal/ldpk_utils.h>
 #include <opencompgraph/internal/imageio.h>
+#include <opencompgraph/internal/graph_observer.h>
 #include <opencompgraph/internal/pixelblock.h>
 #include <opencompgraph/colorlutimage.h>
 #include <opencompgraph/cache.h>
 #include <opencompgraph/config.h>
+#include <opencompgraph/frameset.h>
 #include <opencompgraph/graph.h>
+#include <opencompgraph/graph_observer.h>
+#include <opencompgraph/imagesequence.h>
 #include <opencompgraph/node.h>
+#include <opencompgraph/prefetch.h>
 #include <opencompgraph/stream.h>
 
 #endif // OPENCOMPGRAPH_H
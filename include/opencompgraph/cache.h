@@ -29,16 +29,16 @@
 
 namespace open_comp_graph {
 
+// All Cache methods are thread-safe, so a single Cache can be shared
+// between graphs executing on different threads. The cache locks
+// itself only for the duration of each individual cache operation.
 class Cache {
 public:
     OCG_API_EXPORT
     Cache() noexcept;
 
     OCG_API_EXPORT
-    rust::Box<internal::CacheImpl> get_box() noexcept;
-
-    OCG_API_EXPORT
-    void set_box(rust::Box<internal::CacheImpl> box) noexcept;
+    const rust::Box<internal::CacheImpl>& get_box() const noexcept;
 
     OCG_API_EXPORT
     std::uint64_t count() const noexcept;
@@ -52,6 +52,80 @@ public:
     OCG_API_EXPORT
     void set_capacity_bytes(std::size_t value) noexcept;
 
+    // Remove all cached images read from the file 'path', or from
+    // any file inside the directory 'path'. Returns the number of
+    // cache entries removed.
+    OCG_API_EXPORT
+    std::size_t invalidate_path(const char* path) noexcept;
+
+    OCG_API_EXPORT
+    CacheEvictionPolicy eviction_policy() const noexcept;
+
+    OCG_API_EXPORT
+    void set_eviction_policy(CacheEvictionPolicy value) noexcept;
+
+    // How new images are stored in RAM. Compressed images use less
+    // memory, but must be decompressed each time they are used.
+    OCG_API_EXPORT
+    CacheCompression compression() const noexcept;
+
+    OCG_API_EXPORT
+    void set_compression(CacheCompression value) noexcept;
+
+    // Pinned values are never evicted, until they are unpinned. The
+    // key is the hash of a node, see 'Graph::node_hash'.
+    OCG_API_EXPORT
+    void pin(uint64_t key) noexcept;
+
+    OCG_API_EXPORT
+    bool unpin(uint64_t key) noexcept;
+
+    OCG_API_EXPORT
+    void unpin_all() noexcept;
+
+    OCG_API_EXPORT
+    bool is_pinned(uint64_t key) const noexcept;
+
+    OCG_API_EXPORT
+    std::uint64_t pinned_count() const noexcept;
+
+    OCG_API_EXPORT
+    std::size_t pinned_bytes() const noexcept;
+
+    OCG_API_EXPORT
+    CacheStats stats() const noexcept;
+
+    // Details of each value in the cache, from the least to the most
+    // recently used.
+    OCG_API_EXPORT
+    rust::Vec<CacheEntryInfo> entries() const noexcept;
+
+    // Use a directory on disk as a second level of the cache. Values
+    // evicted from RAM are written to disk, and read back on a RAM
+    // cache miss.
+    OCG_API_EXPORT
+    bool enable_disk_cache(const char* directory,
+                           std::size_t capacity_bytes,
+                           DiskCacheImageType image_type) noexcept;
+
+    OCG_API_EXPORT
+    void disable_disk_cache() noexcept;
+
+    OCG_API_EXPORT
+    bool disk_cache_enabled() const noexcept;
+
+    OCG_API_EXPORT
+    std::uint64_t disk_cache_count() const noexcept;
+
+    OCG_API_EXPORT
+    std::size_t disk_cache_used_bytes() const noexcept;
+
+    OCG_API_EXPORT
+    std::size_t disk_cache_capacity_bytes() const noexcept;
+
+    OCG_API_EXPORT
+    void set_disk_cache_capacity_bytes(std::size_t value) noexcept;
+
     OCG_API_EXPORT
     std::string data_debug_string() const noexcept;
 
@@ -60,6 +134,12 @@ private:
 
 };
 
+// The cache shared by all graphs in the process, created on first
+// use. The RAM capacity (and disk cache) are read from the
+// configuration file.
+OCG_API_EXPORT
+std::shared_ptr<Cache> shared_cache() noexcept;
+
 }  // namespace open_comp_graph
 
 #endif //OPENCOMPGRAPH_CACHE_H
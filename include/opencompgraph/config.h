@@ -49,6 +49,21 @@ public:
     OCG_API_EXPORT
     std::size_t cache_ram_capacity_bytes() const noexcept;
 
+    OCG_API_EXPORT
+    std::string cache_disk_directory() const noexcept;
+
+    OCG_API_EXPORT
+    std::size_t cache_disk_capacity_bytes() const noexcept;
+
+    OCG_API_EXPORT
+    DiskCacheImageType cache_disk_image_type() const noexcept;
+
+    OCG_API_EXPORT
+    CacheCompression cache_ram_compression() const noexcept;
+
+    OCG_API_EXPORT
+    ImageIOBackend imageio_backend() const noexcept;
+
     OCG_API_EXPORT
     std::string data_debug_string() const noexcept;
 
@@ -57,6 +72,16 @@ private:
 
 };
 
+// The library used to read and write images, for all threads.
+//
+// Defaults to the "imageio: backend" value in the
+// "open_comp_graph.yaml" configuration file.
+OCG_API_EXPORT
+ImageIOBackend image_io_backend() noexcept;
+
+OCG_API_EXPORT
+void set_image_io_backend(ImageIOBackend backend) noexcept;
+
 }  // namespace open_comp_graph
 
 #endif //OPENCOMPGRAPH_CONFIG_H
@@ -0,0 +1,98 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+#ifndef OPENCOMPGRAPH_FRAMESET_H
+#define OPENCOMPGRAPH_FRAMESET_H
+
+#include <string>
+#include <vector>
+#include <rust/cxx.h>
+#include <opencompgraph/_cxxbridge.h>
+#include "symbol_export.h"
+
+namespace open_comp_graph {
+
+class FrameSet {
+public:
+    OCG_API_EXPORT
+    FrameSet() noexcept;
+
+    // Parse a frame specification, such as "1-10", "1,5,10-20x2".
+    OCG_API_EXPORT
+    FrameSet(const char* spec) noexcept;
+
+    OCG_API_EXPORT
+    FrameSet(rust::Box<internal::FrameSetImpl> box) noexcept;
+
+    OCG_API_EXPORT
+    rust::Box<internal::FrameSetImpl> get_box() noexcept;
+
+    OCG_API_EXPORT
+    void set_box(rust::Box<internal::FrameSetImpl> box) noexcept;
+
+    // Returns false if the spec could not be parsed, or has more than
+    // one million frames; the frame set is left unchanged.
+    OCG_API_EXPORT
+    bool parse(const char* spec) noexcept;
+
+    OCG_API_EXPORT
+    std::size_t len() const noexcept;
+
+    OCG_API_EXPORT
+    bool is_empty() const noexcept;
+
+    OCG_API_EXPORT
+    int32_t first_frame() const noexcept;
+
+    OCG_API_EXPORT
+    int32_t last_frame() const noexcept;
+
+    OCG_API_EXPORT
+    bool contains(int32_t frame) const noexcept;
+
+    OCG_API_EXPORT
+    std::vector<int32_t> frames() const noexcept;
+
+    OCG_API_EXPORT
+    std::vector<double> frames_f64() const noexcept;
+
+    OCG_API_EXPORT
+    void merge(const FrameSet &other) noexcept;
+
+    // Split the frame set into consecutive chunks of (at most)
+    // 'chunk_size' frames each.
+    OCG_API_EXPORT
+    std::vector<FrameSet> chunks(std::size_t chunk_size) const noexcept;
+
+    OCG_API_EXPORT
+    std::string to_spec_string() const noexcept;
+
+    OCG_API_EXPORT
+    std::string data_debug_string() const noexcept;
+
+private:
+    internal::FrameSetImplShared inner;
+
+};
+
+}  // namespace open_comp_graph
+
+#endif //OPENCOMPGRAPH_FRAMESET_H
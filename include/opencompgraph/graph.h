@@ -27,6 +27,8 @@
 #include <rust/cxx.h>
 #include <opencompgraph/_cxxbridge.h>
 #include <opencompgraph/cache.h>
+#include <opencompgraph/frameset.h>
+#include <opencompgraph/graph_observer.h>
 #include <opencompgraph/node.h>
 #include <opencompgraph/stream.h>
 #include "symbol_export.h"
@@ -43,6 +45,15 @@ public:
     OCG_API_EXPORT
     ExecuteStatus execute_status() const noexcept;
 
+    // The resolution the ReadImage nodes read images at, for fast
+    // review. Pixel-space attributes of downstream nodes (such as
+    // crop windows) are scaled to match.
+    OCG_API_EXPORT
+    ProxyScale proxy_scale() const noexcept;
+
+    OCG_API_EXPORT
+    void set_proxy_scale(ProxyScale proxy_scale) noexcept;
+
     OCG_API_EXPORT
     Node create_node(NodeType node_type) noexcept;
 
@@ -88,6 +99,28 @@ public:
     OCG_API_EXPORT
     void connect(const Node& src_node, const Node& dst_node, uint8_t input_num) noexcept;
 
+    // Returns an id, used to remove the observer.
+    OCG_API_EXPORT
+    uint64_t add_observer(std::shared_ptr<GraphObserver> observer) noexcept;
+
+    OCG_API_EXPORT
+    bool remove_observer(uint64_t observer_id) noexcept;
+
+    OCG_API_EXPORT
+    rust::Vec<ValidationIssue> validate(
+        const Node& node,
+        std::vector<int32_t> &frames) const noexcept;
+
+    OCG_API_EXPORT
+    rust::Vec<ValidationIssue> validate(
+        const Node& node,
+        std::vector<double> &frames) const noexcept;
+
+    OCG_API_EXPORT
+    rust::Vec<ValidationIssue> validate(
+        const Node& node,
+        const FrameSet &frames) const noexcept;
+
     OCG_API_EXPORT
     ExecuteStatus execute(
         const Node& node,
@@ -100,9 +133,55 @@ public:
         std::vector<double> &frames,
         std::shared_ptr<Cache> &cache) noexcept;
 
+    OCG_API_EXPORT
+    ExecuteStatus execute(
+        const Node& node,
+        const FrameSet &frames,
+        std::shared_ptr<Cache> &cache) noexcept;
+
+    // Which of the 'frames' of the node are stored in the cache,
+    // without executing the graph.
+    OCG_API_EXPORT
+    rust::Vec<CachedFrame> cached_frames(
+        const Node& node,
+        std::vector<int32_t> &frames,
+        std::shared_ptr<Cache> &cache) const noexcept;
+
+    OCG_API_EXPORT
+    rust::Vec<CachedFrame> cached_frames(
+        const Node& node,
+        std::vector<double> &frames,
+        std::shared_ptr<Cache> &cache) const noexcept;
+
+    OCG_API_EXPORT
+    rust::Vec<CachedFrame> cached_frames(
+        const Node& node,
+        const FrameSet &frames,
+        std::shared_ptr<Cache> &cache) const noexcept;
+
+    // The images that can be read ahead of time, before 'node' is
+    // computed at 'frames'. See 'Prefetcher'.
+    OCG_API_EXPORT
+    rust::Vec<PrefetchRequest> prefetch_requests(
+        const Node& node,
+        std::vector<double> &frames,
+        std::shared_ptr<Cache> &cache) const noexcept;
+
     OCG_API_EXPORT
     NodeStatus node_status(const Node &node) const noexcept;
 
+    OCG_API_EXPORT
+    NodeErrorKind node_error_kind(const Node &node) const noexcept;
+
+    OCG_API_EXPORT
+    std::string node_status_message(const Node &node) const noexcept;
+
+    // The hash value of the node (and all upstream nodes) at the
+    // given frame. Hash values are stable across processes and
+    // platforms.
+    OCG_API_EXPORT
+    uint64_t node_hash(const Node &node, double frame) const noexcept;
+
     OCG_API_EXPORT
     std::string data_debug_string() const noexcept;
 
@@ -0,0 +1,46 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+#ifndef OPENCOMPGRAPH_GRAPH_OBSERVER_H
+#define OPENCOMPGRAPH_GRAPH_OBSERVER_H
+
+#include <rust/cxx.h>
+#include <opencompgraph/_cxxbridge.h>
+#include "symbol_export.h"
+
+namespace open_comp_graph {
+
+// Sub-class to be notified when a graph is edited.
+//
+// 'notify' is called while the graph is being edited, so the
+// observer must not call back into the same graph. Record the event
+// and act on it later instead.
+class GraphObserver {
+public:
+    OCG_API_EXPORT
+    virtual ~GraphObserver();
+
+    virtual void notify(const GraphEvent &event) = 0;
+};
+
+}  // namespace open_comp_graph
+
+#endif //OPENCOMPGRAPH_GRAPH_OBSERVER_H
@@ -0,0 +1,41 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+#ifndef OPENCOMPGRAPH_IMAGE_SEQUENCE_H
+#define OPENCOMPGRAPH_IMAGE_SEQUENCE_H
+
+#include <rust/cxx.h>
+#include <opencompgraph/_cxxbridge.h>
+#include "symbol_export.h"
+
+namespace open_comp_graph {
+
+// Find the image sequences in 'directory', sorted by file path.
+//
+// Files are grouped into sequences by the file name with the frame
+// number removed. Files without a frame number are ignored, and
+// sub-directories are not searched.
+OCG_API_EXPORT
+rust::Vec<ImageSequence> find_image_sequences(rust::Str directory) noexcept;
+
+} // namespace open_comp_graph
+
+#endif // OPENCOMPGRAPH_IMAGE_SEQUENCE_H
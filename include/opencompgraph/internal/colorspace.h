@@ -34,6 +34,9 @@ namespace internal {
 OCG_API_EXPORT
 bool ocio_print_color_spaces();
 
+OCG_API_EXPORT
+bool oiio_color_space_exists(const rust::Str color_space);
+
 OCG_API_EXPORT
 bool oiio_color_convert_inplace(
         rust::Slice<float> pixel_block,
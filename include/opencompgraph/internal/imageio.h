@@ -42,12 +42,36 @@ bool oiio_set_thread_count(int32_t num_threads);
 OCG_API_EXPORT
 bool oiio_read_image(const rust::String &file_path, ImageShared &image);
 
+OCG_API_EXPORT
+bool oiio_read_image_num_channels(const rust::String &file_path, int32_t &num_channels);
+
+OCG_API_EXPORT
+bool oiio_read_image_header(const rust::String &file_path, ImageShared &image);
+
+OCG_API_EXPORT
+bool oiio_read_image_channel_names(
+    const rust::String &file_path,
+    rust::Vec<rust::String> &channel_names);
+
+OCG_API_EXPORT
+bool oiio_read_image_channels(
+    const rust::String &file_path,
+    const rust::Vec<rust::String> &channel_names,
+    ImageShared &image);
+
 OCG_API_EXPORT
 bool oiio_write_image(
     const rust::String &file_path,
     const ImageShared &image,
     const ImageCompression &compress);
 
+OCG_API_EXPORT
+bool oiio_write_image_channels(
+    const rust::String &file_path,
+    const ImageShared &image,
+    const rust::Vec<rust::String> &channel_names,
+    const ImageCompression &compress);
+
 } // namespace internal
 } // namespace open_comp_graph
 
@@ -65,6 +65,20 @@ bool oiio_allocate_image(
     const OIIO::ImageSpec &spec,
     ImageShared &image);
 
+// Not exported to the API.
+//
+// Copy the extra attributes of 'spec' into 'metadata'. Attributes
+// stored elsewhere in the ImageSpec (such as color space), and
+// attributes describing the file encoding, are skipped.
+void oiio_read_metadata(
+    const OIIO::ImageSpec &spec,
+    rust::Vec<ImageMetadata> &metadata);
+
+// Not exported to the API.
+void oiio_write_metadata(
+    const rust::Vec<ImageMetadata> &metadata,
+    OIIO::ImageSpec &spec);
+
 } // namespace internal
 } // namespace open_comp_graph
 
@@ -0,0 +1,102 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+#ifndef OPENCOMPGRAPH_PREFETCH_H
+#define OPENCOMPGRAPH_PREFETCH_H
+
+#include <condition_variable>
+#include <deque>
+#include <memory>
+#include <mutex>
+#include <thread>
+#include <vector>
+#include <rust/cxx.h>
+#include <opencompgraph/_cxxbridge.h>
+#include <opencompgraph/cache.h>
+#include <opencompgraph/graph.h>
+#include <opencompgraph/node.h>
+#include "symbol_export.h"
+
+namespace open_comp_graph {
+
+// Reads images into the cache on worker threads, ahead of the
+// playhead, so the frames are ready before they are played.
+//
+// Images are only inserted into unused cache capacity; once the
+// cache is full the remaining requests are dropped.
+class Prefetcher {
+public:
+    OCG_API_EXPORT
+    Prefetcher(std::shared_ptr<Cache> cache, size_t num_threads) noexcept;
+
+    OCG_API_EXPORT
+    ~Prefetcher();
+
+    // Queue the images needed to compute 'node' for the
+    // 'frame_count' frames after 'playhead', in 'direction' (1 for
+    // forwards, -1 for backwards). Replaces any queued requests.
+    OCG_API_EXPORT
+    void prefetch(const Graph &graph,
+                  const Node &node,
+                  double playhead,
+                  int32_t direction,
+                  size_t frame_count) noexcept;
+
+    // Move the playhead, cancelling queued requests for frames the
+    // playhead has passed.
+    OCG_API_EXPORT
+    void set_playhead(double playhead, int32_t direction) noexcept;
+
+    // Cancel all queued requests. Images being read are finished.
+    OCG_API_EXPORT
+    void cancel() noexcept;
+
+    // Block until all queued requests are finished.
+    OCG_API_EXPORT
+    void wait() noexcept;
+
+    // Number of requests queued or being read.
+    OCG_API_EXPORT
+    size_t pending_count() const noexcept;
+
+    // Has the cache become full since the last 'prefetch'?
+    OCG_API_EXPORT
+    bool budget_reached() const noexcept;
+
+private:
+    void run_worker() noexcept;
+
+    std::shared_ptr<Cache> cache;
+    std::vector<std::thread> threads;
+    std::deque<PrefetchRequest> queue;
+    mutable std::mutex mutex;
+    std::condition_variable queue_condition;
+    std::condition_variable idle_condition;
+    size_t active_count;
+    double playhead;
+    int32_t direction;
+    bool stop;
+    bool cache_full;
+};
+
+} // namespace open_comp_graph
+
+#endif // OPENCOMPGRAPH_PREFETCH_H
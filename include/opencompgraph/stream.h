@@ -64,6 +64,9 @@ public:
     OCG_API_EXPORT
     internal::ImageSpec clone_image_spec() const noexcept;
 
+    OCG_API_EXPORT
+    ProxyScale proxy_scale() const noexcept;
+
     OCG_API_EXPORT
     Matrix4 transform_matrix() const noexcept;
 
@@ -96,6 +99,23 @@ public:
     OCG_API_EXPORT
     DataType pixel_data_type() const noexcept;
 
+    // Extra named channels, such as "depth" or "motion", passed
+    // through with the RGBA pixels. Layer pixels are Float32.
+    OCG_API_EXPORT
+    size_t layers_len() const noexcept;
+
+    OCG_API_EXPORT
+    rust::Str layer_name(size_t index) const noexcept;
+
+    OCG_API_EXPORT
+    rust::Vec<rust::String> layer_channel_names(size_t index) const noexcept;
+
+    OCG_API_EXPORT
+    BBox2Di layer_data_window(size_t index) const noexcept;
+
+    OCG_API_EXPORT
+    rust::Slice<const uint8_t> layer_pixel_buffer(size_t index) const noexcept;
+
 private:
     internal::StreamDataImplShared inner;
 };
@@ -19,13 +19,12 @@
  *
  */
 
-use std::collections::hash_map::DefaultHasher;
-
 use crate::cxxbridge::ffi::AttrState;
 use crate::data::FrameValue;
+use crate::hashutils::StableHasher;
 
 pub trait AttrBlock: std::fmt::Debug {
-    fn attr_hash(&self, frame: FrameValue, state: &mut DefaultHasher);
+    fn attr_hash(&self, frame: FrameValue, state: &mut StableHasher);
 
     fn attr_exists(&self, name: &str) -> AttrState;
 
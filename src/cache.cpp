@@ -20,9 +20,9 @@
  */
 
 #include <iostream>
-#include <utility>
 #include <rust/cxx.h>
 #include <opencompgraph/cache.h>
+#include <opencompgraph/config.h>
 
 namespace open_comp_graph {
 
@@ -30,12 +30,8 @@ Cache::Cache() noexcept
         : inner{internal::create_cache_shared_with_capacity(0)} {
 }
 
-rust::Box<internal::CacheImpl> Cache::get_box() noexcept {
-    return std::move(this->inner.inner);
-}
-
-void Cache::set_box(rust::Box<internal::CacheImpl> box) noexcept {
-    this->inner.inner = std::move(box);
+const rust::Box<internal::CacheImpl>& Cache::get_box() const noexcept {
+    return this->inner.inner;
 }
 
 std::size_t Cache::count() const noexcept {
@@ -54,9 +50,110 @@ void Cache::set_capacity_bytes(std::size_t value) noexcept {
     return this->inner.inner->set_capacity_bytes(value);
 }
 
+std::size_t Cache::invalidate_path(const char* path) noexcept {
+    return this->inner.inner->invalidate_path(path);
+}
+
+CacheEvictionPolicy Cache::eviction_policy() const noexcept {
+    return this->inner.inner->eviction_policy();
+}
+
+void Cache::set_eviction_policy(CacheEvictionPolicy value) noexcept {
+    this->inner.inner->set_eviction_policy(value);
+}
+
+CacheCompression Cache::compression() const noexcept {
+    return this->inner.inner->compression();
+}
+
+void Cache::set_compression(CacheCompression value) noexcept {
+    this->inner.inner->set_compression(value);
+}
+
+void Cache::pin(uint64_t key) noexcept {
+    this->inner.inner->pin(key);
+}
+
+bool Cache::unpin(uint64_t key) noexcept {
+    return this->inner.inner->unpin(key);
+}
+
+void Cache::unpin_all() noexcept {
+    this->inner.inner->unpin_all();
+}
+
+bool Cache::is_pinned(uint64_t key) const noexcept {
+    return this->inner.inner->is_pinned(key);
+}
+
+std::uint64_t Cache::pinned_count() const noexcept {
+    return this->inner.inner->pinned_len();
+}
+
+std::size_t Cache::pinned_bytes() const noexcept {
+    return this->inner.inner->pinned_bytes();
+}
+
+CacheStats Cache::stats() const noexcept {
+    return this->inner.inner->stats();
+}
+
+rust::Vec<CacheEntryInfo> Cache::entries() const noexcept {
+    return this->inner.inner->entries();
+}
+
+bool Cache::enable_disk_cache(const char* directory,
+                              std::size_t capacity_bytes,
+                              DiskCacheImageType image_type) noexcept {
+    return this->inner.inner->enable_disk_cache(directory, capacity_bytes, image_type);
+}
+
+void Cache::disable_disk_cache() noexcept {
+    this->inner.inner->disable_disk_cache();
+}
+
+bool Cache::disk_cache_enabled() const noexcept {
+    return this->inner.inner->disk_cache_enabled();
+}
+
+std::uint64_t Cache::disk_cache_count() const noexcept {
+    return this->inner.inner->disk_cache_len();
+}
+
+std::size_t Cache::disk_cache_used_bytes() const noexcept {
+    return this->inner.inner->disk_cache_used_bytes();
+}
+
+std::size_t Cache::disk_cache_capacity_bytes() const noexcept {
+    return this->inner.inner->disk_cache_capacity_bytes();
+}
+
+void Cache::set_disk_cache_capacity_bytes(std::size_t value) noexcept {
+    this->inner.inner->set_disk_cache_capacity_bytes(value);
+}
+
 std::string Cache::data_debug_string() const noexcept {
     auto rust_string = this->inner.inner->data_debug_string();
     return std::string(rust_string);
 }
 
+std::shared_ptr<Cache> shared_cache() noexcept {
+    // Initialization of a static local variable is thread-safe.
+    static std::shared_ptr<Cache> cache = [] {
+        auto config = Config("open_comp_graph.yaml");
+        auto value = std::make_shared<Cache>();
+        value->set_capacity_bytes(config.cache_ram_capacity_bytes());
+        value->set_compression(config.cache_ram_compression());
+        auto disk_directory = config.cache_disk_directory();
+        if (!disk_directory.empty()) {
+            value->enable_disk_cache(
+                disk_directory.c_str(),
+                config.cache_disk_capacity_bytes(),
+                config.cache_disk_image_type());
+        }
+        return value;
+    }();
+    return cache;
+}
+
 } // namespace open_comp_graph
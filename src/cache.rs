@@ -20,33 +20,126 @@
  */
 
 use linked_hash_map::LinkedHashMap;
-use log::{debug, error};
-use std::rc::Rc;
+use log::{debug, error, warn};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
 
 use crate::cxxbridge::ffi::BBox2Di;
+use crate::cxxbridge::ffi::CacheCompression;
+use crate::cxxbridge::ffi::CacheEntryInfo;
+use crate::cxxbridge::ffi::CacheEvictionPolicy;
+use crate::cxxbridge::ffi::CacheStats;
+use crate::cxxbridge::ffi::DiskCacheImageType;
 use crate::cxxbridge::ffi::ImageSpec;
+use crate::cxxbridge::ffi::NodeType;
+use crate::data::OperationCacheType;
 use crate::data::BYTES_TO_GIGABYTES;
+use crate::diskcache::DiskCache;
+use crate::pixelblock::compressed::CompressedPixelBlock;
 use crate::pixelblock::pixelblock::PixelBlock;
+use crate::prefetch::PrefetchImage;
 
 #[derive(Debug, Clone)]
 pub struct CachedImage {
-    pub pixel_block: Rc<PixelBlock>,
+    pub pixel_block: Arc<PixelBlock>,
     pub spec: ImageSpec,
     pub display_window: BBox2Di,
     pub data_window: BBox2Di,
+    // The file the image was read from, if any, so that entries can
+    // be invalidated when the file changes.
+    pub source_path: Option<PathBuf>,
+    // The type of node that computed the image, and how long it
+    // took, used to decide which entries are cheap to re-compute.
+    pub node_type: Option<NodeType>,
+    pub compute_seconds: f32,
+    pub cache_type: OperationCacheType,
+    // Set by the cache when the pixels are stored compressed, in
+    // which case 'pixel_block' is empty.
+    pub compressed: Option<Arc<CompressedPixelBlock>>,
 }
 
 impl CachedImage {
     pub fn size_bytes(&self) -> usize {
-        self.pixel_block.size_bytes()
+        let compressed_bytes = match &self.compressed {
+            Some(compressed) => compressed.size_bytes(),
+            None => 0,
+        };
+        self.pixel_block.size_bytes() + compressed_bytes
+    }
+
+    /// A copy of the image with the pixels compressed, if the
+    /// compression reduces the size of the pixels.
+    pub fn compress(&self, compression: CacheCompression) -> CachedImage {
+        if self.compressed.is_some() {
+            return self.clone();
+        }
+        match CompressedPixelBlock::compress(&self.pixel_block, compression) {
+            Some(compressed) => CachedImage {
+                pixel_block: Arc::new(PixelBlock::empty(self.pixel_block.data_type())),
+                compressed: Some(Arc::new(compressed)),
+                ..self.clone()
+            },
+            None => self.clone(),
+        }
+    }
+
+    /// A copy of the image with uncompressed pixels.
+    pub fn decompress(&self) -> CachedImage {
+        match &self.compressed {
+            Some(compressed) => CachedImage {
+                pixel_block: Arc::new(compressed.decompress()),
+                compressed: None,
+                ..self.clone()
+            },
+            None => self.clone(),
+        }
     }
 }
 
 type CacheKey = u64;
 type CacheValue = CachedImage;
 
+pub fn compression_from_name(name: &str) -> CacheCompression {
+    match name.to_lowercase().as_str() {
+        "none" => CacheCompression::Uncompressed,
+        "lossless" => CacheCompression::Lossless,
+        "lossy" => CacheCompression::LossyHalf16,
+        _ => CacheCompression::Uninitialized,
+    }
+}
+
+impl CacheStats {
+    /// Percentage of queries found in the cache, or zero if the
+    /// cache has not been queried.
+    pub fn hit_ratio(&self) -> f64 {
+        let queries = self.hits + self.misses;
+        match queries {
+            0 => 0.0,
+            _ => (self.hits as f64 / queries as f64) * 100.0,
+        }
+    }
+
+    /// Percentage of inserted values that have been evicted, or zero
+    /// if nothing has been inserted.
+    pub fn eviction_ratio(&self) -> f64 {
+        match self.inserts {
+            0 => 0.0,
+            _ => (self.evictions as f64 / self.inserts as f64) * 100.0,
+        }
+    }
+}
+
+// How many of the least recently used entries are considered when
+// evicting with the 'ComputeCost' policy. Only stale entries are
+// considered, so recently used entries always stay in the cache.
+const EVICTION_CANDIDATE_COUNT: usize = 8;
+
 #[derive(Debug)]
-pub struct CacheImpl {
+struct CacheData {
     lru_hash_map: LinkedHashMap<CacheKey, CacheValue>,
 
     // Keep track of how much memory is used in the cache.
@@ -58,17 +151,37 @@ pub struct CacheImpl {
     misses: usize,
     inserts: usize,
     evictions: usize,
+
+    // How entries are chosen for eviction, and the total compute
+    // time of the evicted entries (that may need to be re-computed).
+    eviction_policy: CacheEvictionPolicy,
+    evicted_compute_seconds: f64,
+
+    // How new values are stored in RAM.
+    compression: CacheCompression,
+
+    // Pinned keys are never evicted. Keys may be pinned before the
+    // value is inserted.
+    pinned_keys: FxHashSet<CacheKey>,
+
+    // Values stored as part of another value (such as the layers of
+    // an image) map to the key of that value, and are pinned with it.
+    parent_keys: FxHashMap<CacheKey, CacheKey>,
+
+    // Optional second level of the cache; values evicted from RAM
+    // are written to disk.
+    disk_cache: Option<DiskCache>,
 }
 
-impl CacheImpl {
+impl CacheData {
     /// An empty cache, without any capacity.
-    pub fn new() -> CacheImpl {
+    fn new() -> CacheData {
         let capacity_bytes = 0;
-        CacheImpl::with_capacity(capacity_bytes)
+        CacheData::with_capacity(capacity_bytes)
     }
 
     /// Create a new cache with the given capacity (in bytes).
-    pub fn with_capacity(capacity_bytes: usize) -> CacheImpl {
+    fn with_capacity(capacity_bytes: usize) -> CacheData {
         let lru_hash_map = LinkedHashMap::new();
 
         let used_bytes = 0;
@@ -77,7 +190,7 @@ impl CacheImpl {
         let inserts = 0;
         let evictions = 0;
 
-        CacheImpl {
+        CacheData {
             lru_hash_map,
             capacity_bytes,
             used_bytes,
@@ -85,6 +198,12 @@ impl CacheImpl {
             misses,
             inserts,
             evictions,
+            eviction_policy: CacheEvictionPolicy::LeastRecentlyUsed,
+            evicted_compute_seconds: 0.0,
+            compression: CacheCompression::Uncompressed,
+            pinned_keys: FxHashSet::default(),
+            parent_keys: FxHashMap::default(),
+            disk_cache: None,
         }
     }
 
@@ -122,8 +241,90 @@ impl CacheImpl {
         self.capacity_bytes = value;
     }
 
+    pub fn eviction_policy(&self) -> CacheEvictionPolicy {
+        self.eviction_policy
+    }
+
+    pub fn set_eviction_policy(&mut self, value: CacheEvictionPolicy) {
+        self.eviction_policy = value;
+    }
+
+    pub fn compression(&self) -> CacheCompression {
+        self.compression
+    }
+
+    /// Set how new values are stored in RAM. Values already in the
+    /// cache are not changed.
+    pub fn set_compression(&mut self, value: CacheCompression) {
+        self.compression = value;
+    }
+
+    /// Record how long the value for 'key' took to compute.
+    ///
+    /// Only values without a compute time are changed, so that a
+    /// (fast) cache hit does not overwrite the original compute time.
+    pub fn set_compute_seconds(&mut self, key: CacheKey, seconds: f32) {
+        if let Some(value) = self.lru_hash_map.get_mut(&key) {
+            if value.compute_seconds == 0.0 {
+                value.compute_seconds = seconds;
+            }
+        }
+    }
+
+    /// Stop the value for 'key' from being evicted, until it is
+    /// unpinned. Pinned values are kept even if the cache is over
+    /// capacity. Values inserted with 'insert_child' are pinned with
+    /// their parent key.
+    pub fn pin(&mut self, key: CacheKey) {
+        debug!("Pin Cache: key={}", key);
+        self.pinned_keys.insert(key);
+    }
+
+    /// Allow the value for 'key' to be evicted again. Returns false
+    /// if the key was not pinned.
+    pub fn unpin(&mut self, key: CacheKey) -> bool {
+        debug!("Unpin Cache: key={}", key);
+        self.pinned_keys.remove(&key)
+    }
+
+    pub fn unpin_all(&mut self) {
+        self.pinned_keys.clear();
+    }
+
+    /// Is 'key' pinned, either directly or by pinning the key of its
+    /// parent value?
+    pub fn is_pinned(&self, key: CacheKey) -> bool {
+        if self.pinned_keys.contains(&key) {
+            return true;
+        }
+        match self.parent_keys.get(&key) {
+            Some(parent_key) => self.pinned_keys.contains(parent_key),
+            None => false,
+        }
+    }
+
+    /// Number of pinned values in the cache.
+    pub fn pinned_len(&self) -> usize {
+        self.lru_hash_map
+            .keys()
+            .filter(|key| self.is_pinned(**key))
+            .count()
+    }
+
+    /// Amount of memory used by pinned values.
+    pub fn pinned_bytes(&self) -> usize {
+        self.lru_hash_map
+            .iter()
+            .filter(|(key, _value)| self.is_pinned(**key))
+            .map(|(_key, value)| value.size_bytes())
+            .sum()
+    }
+
     /// Insert a new cache value into to the Cache. If a value already
-    /// exists with the same 'key' it will be evicted from the cache.
+    /// exists with the same 'key' it is replaced.
+    ///
+    /// Values larger than the capacity of the cache are not
+    /// inserted.
     pub fn insert(&mut self, key: CacheKey, value: CacheValue) {
         debug!("Insert into Cache: key={}", key);
         if self.capacity_bytes == 0 {
@@ -131,7 +332,21 @@ impl CacheImpl {
             return;
         }
 
+        let value = value.compress(self.compression);
         let value_bytes = value.size_bytes();
+        if value_bytes > self.capacity_bytes {
+            warn!(
+                "Cannot insert into Cache; value is larger than the capacity: key={} bytes={}",
+                key, value_bytes
+            );
+            return;
+        }
+
+        // The same key may be computed (and inserted) by more than
+        // one graph or thread at once.
+        if let Some(old_value) = self.lru_hash_map.remove(&key) {
+            self.used_bytes -= old_value.size_bytes();
+        }
 
         // Make space if necessary
         let expected_bytes = self.used_bytes + value_bytes;
@@ -146,25 +361,180 @@ impl CacheImpl {
         self.inserts += 1;
     }
 
+    /// Insert a value that is part of the value stored at
+    /// 'parent_key', such as a layer of an image. The value is
+    /// pinned and unpinned together with 'parent_key'.
+    pub fn insert_child(&mut self, parent_key: CacheKey, key: CacheKey, value: CacheValue) {
+        self.parent_keys.insert(key, parent_key);
+        self.insert(key, value);
+    }
+
+    /// Insert an image that was read ahead of time, if it fits in the
+    /// unused capacity of the cache; prefetched images never evict
+    /// other values. Returns false if the cache is full.
+    pub fn insert_prefetch_image(&mut self, image: Box<PrefetchImage>) -> bool {
+        let (key, value) = image.into_parts();
+        if self.lru_hash_map.contains_key(&key) {
+            return true;
+        }
+        let value = match value {
+            Some(value) => value,
+            // The file could not be read, the error is reported
+            // when the node is computed.
+            None => return true,
+        };
+        let value = value.compress(self.compression);
+        if (self.used_bytes + value.size_bytes()) >= self.capacity_bytes {
+            debug!("Prefetch budget reached: key={}", key);
+            return false;
+        }
+        self.insert(key, value);
+        true
+    }
+
+    /// Does the cache (in RAM or on disk) contain 'key'?
+    ///
+    /// Unlike 'get', this does not change the access order or the
+    /// hit/miss statistics.
+    pub fn contains(&self, key: CacheKey) -> bool {
+        if self.lru_hash_map.contains_key(&key) {
+            return true;
+        }
+        match &self.disk_cache {
+            Some(disk_cache) => disk_cache.contains_key(&key),
+            None => false,
+        }
+    }
+
     /// Get a value from the Cache, if it exists.
-    pub fn get(&mut self, key: &CacheKey) -> Option<&CacheValue> {
+    ///
+    /// If the value is not in RAM, but is in the disk cache, the
+    /// value is read from disk and moved back into RAM. Compressed
+    /// values are returned decompressed.
+    pub fn get(&mut self, key: &CacheKey) -> Option<CacheValue> {
         debug!("Query Cache: key={}", key);
-        let value = self.lru_hash_map.get_refresh(key);
-        match value {
-            Some(value) => {
-                self.hits += 1;
-                Some(value)
+        if self.lru_hash_map.contains_key(key) {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+            let disk_value = match self.disk_cache.as_mut() {
+                Some(disk_cache) => disk_cache.get(key),
+                None => None,
+            };
+            if let Some(value) = disk_value {
+                self.insert(*key, value);
             }
-            None => {
-                self.misses += 1;
-                None
+        }
+        self.lru_hash_map
+            .get_refresh(key)
+            .map(|value| value.decompress())
+    }
+
+    /// Use a directory on disk as a second level of the cache.
+    ///
+    /// Values evicted from RAM are written to the directory with the
+    /// given image type, until 'capacity_bytes' of disk space is
+    /// used. Returns false if the disk cache could not be created.
+    pub fn enable_disk_cache(
+        &mut self,
+        directory: &str,
+        capacity_bytes: usize,
+        image_type: DiskCacheImageType,
+    ) -> bool {
+        match DiskCache::new(directory, capacity_bytes, image_type) {
+            Ok(disk_cache) => {
+                self.disk_cache = Some(disk_cache);
+                true
+            }
+            Err(e) => {
+                error!("Cannot enable disk cache: {}", e);
+                self.disk_cache = None;
+                false
+            }
+        }
+    }
+
+    /// Stop using the disk cache. Files already written to disk are
+    /// left in place, to be re-used later.
+    pub fn disable_disk_cache(&mut self) {
+        self.disk_cache = None;
+    }
+
+    pub fn disk_cache_enabled(&self) -> bool {
+        self.disk_cache.is_some()
+    }
+
+    /// Number of entries in the disk cache.
+    pub fn disk_cache_len(&self) -> usize {
+        match &self.disk_cache {
+            Some(disk_cache) => disk_cache.len(),
+            None => 0,
+        }
+    }
+
+    /// Amount of disk space used by the disk cache.
+    pub fn disk_cache_used_bytes(&self) -> usize {
+        match &self.disk_cache {
+            Some(disk_cache) => disk_cache.used_bytes(),
+            None => 0,
+        }
+    }
+
+    /// Amount of disk space that can be used by the disk cache.
+    pub fn disk_cache_capacity_bytes(&self) -> usize {
+        match &self.disk_cache {
+            Some(disk_cache) => disk_cache.capacity_bytes(),
+            None => 0,
+        }
+    }
+
+    pub fn set_disk_cache_capacity_bytes(&mut self, value: usize) {
+        if let Some(disk_cache) = self.disk_cache.as_mut() {
+            disk_cache.set_capacity_bytes(value);
+        }
+    }
+
+    /// Remove all entries (in RAM and on disk) that were read from
+    /// 'path', or from any file inside the directory 'path'.
+    ///
+    /// Returns the number of entries removed. The removed entries
+    /// are not written to the disk cache.
+    pub fn invalidate_path(&mut self, path: &str) -> usize {
+        let path_expanded = shellexpand::full(path)
+            .map(|value| value.to_string())
+            .unwrap_or_else(|_| path.to_string());
+        let path = match Path::new(&path_expanded).canonicalize() {
+            Ok(full_path) => full_path,
+            Err(_) => PathBuf::from(&path_expanded),
+        };
+        debug!("Invalidate Cache: path={:?}", path);
+
+        let keys: Vec<CacheKey> = self
+            .lru_hash_map
+            .iter()
+            .filter(|(_key, value)| match &value.source_path {
+                Some(source_path) => source_path.starts_with(&path),
+                None => false,
+            })
+            .map(|(key, _value)| *key)
+            .collect();
+        let mut count = keys.len();
+        for key in keys {
+            if let Some(value) = self.lru_hash_map.remove(&key) {
+                self.used_bytes -= value.size_bytes();
             }
+            self.parent_keys.remove(&key);
         }
+
+        if let Some(disk_cache) = self.disk_cache.as_mut() {
+            count += disk_cache.invalidate_path(&path);
+        }
+        count
     }
 
     /// How much memory (in bytes) do you want to evict?
     ///
-    /// Strategy for removing items is Least Recently Used (LRU).
+    /// Strategy for removing items is chosen by the eviction policy.
     pub fn evict_bytes(&mut self, n_bytes: usize) {
         if self.lru_hash_map.len() == 0 {
             debug!("Cache Data: {}", self.data_debug_string());
@@ -193,15 +563,61 @@ impl CacheImpl {
         };
 
         while (self.used_bytes >= requested_capacity) && (self.lru_hash_map.len() > 0) {
-            self.evict();
+            // Only pinned entries are left.
+            if !self.evict() {
+                break;
+            }
         }
     }
 
     pub fn evict_all(&mut self) {
         debug!("EVICT ALL: count={}", self.lru_hash_map.len());
-        // Keep evicting until all entries are removed from the cache.
+        // Keep evicting until all (un-pinned) entries are removed
+        // from the cache.
         while self.lru_hash_map.len() > 0 {
-            self.evict();
+            if !self.evict() {
+                break;
+            }
+        }
+    }
+
+    /// The key of the next entry to be evicted, chosen by the
+    /// eviction policy.
+    fn find_eviction_key(&self) -> Option<CacheKey> {
+        // The least recently used (un-pinned) entries.
+        let candidates: Vec<(&CacheKey, &CacheValue)> = self
+            .lru_hash_map
+            .iter()
+            .filter(|(key, _value)| !self.is_pinned(**key))
+            .take(EVICTION_CANDIDATE_COUNT)
+            .collect();
+
+        // Trivial values are easy to re-compute, so they are always
+        // evicted first.
+        let trivial = candidates
+            .iter()
+            .find(|(_key, value)| value.cache_type == OperationCacheType::Trivial);
+        if let Some((key, _value)) = trivial {
+            return Some(**key);
+        }
+
+        match self.eviction_policy {
+            CacheEvictionPolicy::ComputeCost => {
+                // The entry with the lowest compute time per byte is
+                // the cheapest to re-compute and frees the most
+                // memory.
+                let mut best: Option<(CacheKey, f64)> = None;
+                for (key, value) in candidates {
+                    let size_bytes = value.size_bytes().max(1) as f64;
+                    let cost = value.compute_seconds as f64 / size_bytes;
+                    match best {
+                        Some((_, best_cost)) if best_cost <= cost => (),
+                        _ => best = Some((*key, cost)),
+                    }
+                }
+                best.map(|(key, _cost)| key)
+            }
+            _ => candidates.first().map(|(key, _value)| **key),
         }
     }
 
@@ -211,8 +627,12 @@ impl CacheImpl {
         // 'self.used_bytes' each time it runs and the used_bytes will
         // always decrease after each call.
         //
-        // Identify least recently used key, and remove the key.
-        if let Some((_key, value)) = self.lru_hash_map.pop_front() {
+        // Identify the key to evict, and remove the key.
+        let key = match self.find_eviction_key() {
+            Some(value) => value,
+            None => return success,
+        };
+        if let Some(value) = self.lru_hash_map.remove(&key) {
             let value_bytes = value.size_bytes();
             // There should not be any overflow problems with this
             // subtraction since we add and subtract exactly goes
@@ -221,32 +641,288 @@ impl CacheImpl {
             // size somewhere.
             self.used_bytes -= value_bytes;
 
+            // Trivial values are not worth writing to disk.
+            let write_to_disk =
+                value.cache_type != OperationCacheType::Trivial && self.disk_cache.is_some();
+            if write_to_disk {
+                if let Some(disk_cache) = self.disk_cache.as_mut() {
+                    disk_cache.insert(key, &value.decompress());
+                }
+            } else {
+                // The value is gone, and so is its parent.
+                self.parent_keys.remove(&key);
+            }
+
             success = true;
             self.evictions += 1;
+            self.evicted_compute_seconds += value.compute_seconds as f64;
         };
         success
     }
 
+    /// A snapshot of the cache statistics.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            inserts: self.inserts,
+            evictions: self.evictions,
+            used_bytes: self.used_bytes,
+            capacity_bytes: self.capacity_bytes,
+            count: self.lru_hash_map.len(),
+            pinned_count: self.pinned_len(),
+            pinned_bytes: self.pinned_bytes(),
+            disk_count: self.disk_cache_len(),
+            disk_used_bytes: self.disk_cache_used_bytes(),
+            eviction_policy: self.eviction_policy,
+            evicted_compute_seconds: self.evicted_compute_seconds,
+        }
+    }
+
+    /// Iterate over the details of each value in the cache, from the
+    /// least to the most recently used.
+    pub fn iter_entries(&self) -> impl Iterator<Item = CacheEntryInfo> + '_ {
+        self.lru_hash_map
+            .iter()
+            .enumerate()
+            .map(move |(order, (key, value))| {
+                let (width, height, num_channels, data_type) = match &value.compressed {
+                    Some(compressed) => (
+                        compressed.width(),
+                        compressed.height(),
+                        compressed.num_channels(),
+                        compressed.data_type(),
+                    ),
+                    None => (
+                        value.pixel_block.width(),
+                        value.pixel_block.height(),
+                        value.pixel_block.num_channels(),
+                        value.pixel_block.data_type(),
+                    ),
+                };
+                CacheEntryInfo {
+                    key: *key,
+                    size_bytes: value.size_bytes(),
+                    width,
+                    height,
+                    num_channels,
+                    data_type,
+                    last_access_order: order,
+                    pinned: self.is_pinned(*key),
+                }
+            })
+    }
+
+    pub fn entries(&self) -> Vec<CacheEntryInfo> {
+        self.iter_entries().collect()
+    }
+
     /// Convert the graph into a human-readable string, for debug
     /// printing.
     pub fn data_debug_string(&self) -> String {
         debug!("Cache Debug");
-        let string = format!(
-            "capacity={}GB used={}GB hit_ratio={}% hits={} misses={} insert_ratio={}% inserts={} evictions={} count={}",
-            self.capacity_bytes as f64 / BYTES_TO_GIGABYTES as f64,
-            self.used_bytes as f64 / BYTES_TO_GIGABYTES as f64,
-            (self.hits as f64 / self.misses as f64) * 100.0,
-            self.hits,
-            self.misses,
-            (self.inserts as f64 / self.evictions as f64) * 100.0,
-            self.inserts,
-            self.evictions,
-            self.lru_hash_map.len()
+        let stats = self.stats();
+        let mut string = format!(
+            "capacity={}GB used={}GB hit_ratio={}% hits={} misses={} eviction_ratio={}% inserts={} evictions={} count={}",
+            stats.capacity_bytes as f64 / BYTES_TO_GIGABYTES as f64,
+            stats.used_bytes as f64 / BYTES_TO_GIGABYTES as f64,
+            stats.hit_ratio(),
+            stats.hits,
+            stats.misses,
+            stats.eviction_ratio(),
+            stats.inserts,
+            stats.evictions,
+            stats.count
         );
+        string.push_str(&format!(
+            " eviction_policy={:?} evicted_compute_seconds={} pinned_count={} pinned={}GB",
+            stats.eviction_policy,
+            stats.evicted_compute_seconds,
+            stats.pinned_count,
+            stats.pinned_bytes as f64 / BYTES_TO_GIGABYTES as f64,
+        ));
+        if let Some(disk_cache) = &self.disk_cache {
+            string.push(' ');
+            string.push_str(&disk_cache.data_debug_string());
+        }
         string
     }
 }
 
+/// The cache shared between graphs and threads.
+///
+/// The cache data is locked only for the duration of each individual
+/// cache operation, so callers never hold a lock on the whole cache
+/// while computing a graph.
+#[derive(Debug)]
+pub struct CacheImpl {
+    data: Mutex<CacheData>,
+}
+
+impl CacheImpl {
+    /// An empty cache, without any capacity.
+    pub fn new() -> CacheImpl {
+        CacheImpl {
+            data: Mutex::new(CacheData::new()),
+        }
+    }
+
+    /// Create a new cache with the given capacity (in bytes).
+    pub fn with_capacity(capacity_bytes: usize) -> CacheImpl {
+        CacheImpl {
+            data: Mutex::new(CacheData::with_capacity(capacity_bytes)),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheData> {
+        // A panic while the lock is held does not leave the cache
+        // data invalid, so keep using it.
+        self.data.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn used_bytes(&self) -> usize {
+        self.lock().used_bytes()
+    }
+
+    pub fn capacity_bytes(&self) -> usize {
+        self.lock().capacity_bytes()
+    }
+
+    pub fn set_capacity_bytes(&self, value: usize) {
+        self.lock().set_capacity_bytes(value)
+    }
+
+    pub fn eviction_policy(&self) -> CacheEvictionPolicy {
+        self.lock().eviction_policy()
+    }
+
+    pub fn set_eviction_policy(&self, value: CacheEvictionPolicy) {
+        self.lock().set_eviction_policy(value)
+    }
+
+    pub fn compression(&self) -> CacheCompression {
+        self.lock().compression()
+    }
+
+    pub fn set_compression(&self, value: CacheCompression) {
+        self.lock().set_compression(value)
+    }
+
+    pub fn set_compute_seconds(&self, key: CacheKey, seconds: f32) {
+        self.lock().set_compute_seconds(key, seconds)
+    }
+
+    pub fn pin(&self, key: CacheKey) {
+        self.lock().pin(key)
+    }
+
+    pub fn unpin(&self, key: CacheKey) -> bool {
+        self.lock().unpin(key)
+    }
+
+    pub fn unpin_all(&self) {
+        self.lock().unpin_all()
+    }
+
+    pub fn is_pinned(&self, key: CacheKey) -> bool {
+        self.lock().is_pinned(key)
+    }
+
+    pub fn pinned_len(&self) -> usize {
+        self.lock().pinned_len()
+    }
+
+    pub fn pinned_bytes(&self) -> usize {
+        self.lock().pinned_bytes()
+    }
+
+    pub fn insert(&self, key: CacheKey, value: CacheValue) {
+        self.lock().insert(key, value)
+    }
+
+    pub fn insert_child(&self, parent_key: CacheKey, key: CacheKey, value: CacheValue) {
+        self.lock().insert_child(parent_key, key, value)
+    }
+
+    pub fn insert_prefetch_image(&self, image: Box<PrefetchImage>) -> bool {
+        self.lock().insert_prefetch_image(image)
+    }
+
+    pub fn contains(&self, key: CacheKey) -> bool {
+        self.lock().contains(key)
+    }
+
+    pub fn get(&self, key: &CacheKey) -> Option<CacheValue> {
+        self.lock().get(key)
+    }
+
+    pub fn enable_disk_cache(
+        &self,
+        directory: &str,
+        capacity_bytes: usize,
+        image_type: DiskCacheImageType,
+    ) -> bool {
+        self.lock()
+            .enable_disk_cache(directory, capacity_bytes, image_type)
+    }
+
+    pub fn disable_disk_cache(&self) {
+        self.lock().disable_disk_cache()
+    }
+
+    pub fn disk_cache_enabled(&self) -> bool {
+        self.lock().disk_cache_enabled()
+    }
+
+    pub fn disk_cache_len(&self) -> usize {
+        self.lock().disk_cache_len()
+    }
+
+    pub fn disk_cache_used_bytes(&self) -> usize {
+        self.lock().disk_cache_used_bytes()
+    }
+
+    pub fn disk_cache_capacity_bytes(&self) -> usize {
+        self.lock().disk_cache_capacity_bytes()
+    }
+
+    pub fn set_disk_cache_capacity_bytes(&self, value: usize) {
+        self.lock().set_disk_cache_capacity_bytes(value)
+    }
+
+    pub fn invalidate_path(&self, path: &str) -> usize {
+        self.lock().invalidate_path(path)
+    }
+
+    pub fn evict_bytes(&self, n_bytes: usize) {
+        self.lock().evict_bytes(n_bytes)
+    }
+
+    pub fn evict_all(&self) {
+        self.lock().evict_all()
+    }
+
+    pub fn evict(&self) -> bool {
+        self.lock().evict()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.lock().stats()
+    }
+
+    pub fn entries(&self) -> Vec<CacheEntryInfo> {
+        self.lock().entries()
+    }
+
+    pub fn data_debug_string(&self) -> String {
+        self.lock().data_debug_string()
+    }
+}
+
 pub fn create_cache_box_with_capacity(capacity_bytes: usize) -> Box<CacheImpl> {
     debug!("create_cache_box_with_capacity()");
     Box::new(CacheImpl::with_capacity(capacity_bytes))
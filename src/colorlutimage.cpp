@@ -39,14 +39,12 @@ internal::ImageShared get_color_transform_3dlut(
     int32_t edge_size,  // Common values; 20, 32 or 64.
     std::shared_ptr<Cache> &cache) noexcept
 {
-    auto cache_box = cache->get_box();  // Borrow the underlying cache object.
-    auto img = internal::get_color_transform_3dlut(
+    auto &cache_box = cache->get_box();
+    return internal::get_color_transform_3dlut(
         from_color_space,
         to_color_space,
         edge_size,
         cache_box);
-    cache->set_box(std::move(cache_box));  // Return the cache to it's owner.
-    return img;
 }
 
 internal::ImageShared get_color_ops_lut(
@@ -57,7 +55,7 @@ internal::ImageShared get_color_ops_lut(
 {
     // Borrow the underlying objects.
     auto stream_data_box = stream_data.get_box();
-    auto cache_box = cache->get_box();
+    auto &cache_box = cache->get_box();
 
     auto img = internal::get_color_ops_lut(
         stream_data_box,
@@ -66,7 +64,6 @@ internal::ImageShared get_color_ops_lut(
         cache_box);
 
     // Return the memory to it's owner.
-    cache->set_box(std::move(cache_box));
     stream_data.set_box(std::move(stream_data_box));
     return img;
 }
@@ -25,9 +25,9 @@
 /// Popular resolutions are: 20x20x20, 32x32x32 or 64x64x64
 //
 use log::debug;
-use std::collections::hash_map::DefaultHasher;
 use std::hash::Hasher;
-use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Instant;
 
 use crate::cache::CacheImpl;
 use crate::cache::CachedImage;
@@ -38,7 +38,9 @@ use crate::cxxbridge::ffi::DataType;
 use crate::cxxbridge::ffi::ImageShared;
 use crate::cxxbridge::ffi::ImageSpec;
 use crate::cxxbridge::ffi::Matrix4;
+use crate::data::OperationCacheType;
 use crate::data::COLOR_SPACE_NAME_LINEAR;
+use crate::hashutils::StableHasher;
 use crate::ops::bake;
 use crate::pixelblock::pixelblock::PixelBlock;
 use crate::stream::StreamDataImplRc;
@@ -143,12 +145,12 @@ pub fn get_color_transform_3dlut(
     from_color_space: &str,
     to_color_space: &str,
     cube_size: i32,
-    cache: &mut Box<CacheImpl>,
+    cache: &Box<CacheImpl>,
 ) -> ImageShared {
     let num_channels: i32 = 3;
 
     // Calculate the hash value.
-    let mut hasher = DefaultHasher::new();
+    let mut hasher = StableHasher::new();
     hasher.write(b"3DLUT Size:");
     hasher.write_i32(cube_size);
     hasher.write(b"From:");
@@ -169,18 +171,24 @@ pub fn get_color_transform_3dlut(
         }
         _ => {
             debug!("Color LUT Transform - Cache Miss");
+            let start = Instant::now();
             let img = generate_color_transform_3dlut(
                 cube_size,
                 num_channels,
                 from_color_space,
                 to_color_space,
             );
-            let pixel_block_rc = Rc::new(*img.pixel_block.clone());
+            let pixel_block_arc = Arc::new(*img.pixel_block.clone());
             let cached_img = CachedImage {
-                pixel_block: pixel_block_rc.clone(),
+                pixel_block: pixel_block_arc.clone(),
                 spec: img.spec.clone(),
                 data_window: img.data_window,
                 display_window: img.display_window,
+                source_path: None,
+                node_type: None,
+                compute_seconds: start.elapsed().as_secs_f32(),
+                cache_type: OperationCacheType::BoundByCPU,
+                compressed: None,
             };
             cache.insert(hash_value, cached_img);
             img
@@ -230,14 +238,14 @@ pub fn get_color_ops_lut(
     stream_data: &StreamDataImplRc,
     cube_size: i32,
     num_channels: i32,
-    cache: &mut Box<CacheImpl>,
+    cache: &Box<CacheImpl>,
 ) -> ImageShared {
     debug!(
         "colorlutimage::get_color_ops_lut: cube_size={} num_channels={}",
         cube_size, num_channels
     );
 
-    let mut hasher = DefaultHasher::new();
+    let mut hasher = StableHasher::new();
     hasher.write(b"Color Ops LUT");
     hasher.write(b"LUT Size:");
     hasher.write_i32(cube_size);
@@ -260,14 +268,20 @@ pub fn get_color_ops_lut(
         }
         _ => {
             debug!("Color Operations LUT - Cache Miss");
+            let start = Instant::now();
             let color_ops = stream_data.color_ops();
             let img = generate_color_ops_lut(cube_size, num_channels, color_ops);
-            let pixel_block_rc = Rc::new(*img.pixel_block.clone());
+            let pixel_block_arc = Arc::new(*img.pixel_block.clone());
             let cached_img = CachedImage {
-                pixel_block: pixel_block_rc.clone(),
+                pixel_block: pixel_block_arc.clone(),
                 spec: img.spec.clone(),
                 data_window: img.data_window,
                 display_window: img.display_window,
+                source_path: None,
+                node_type: None,
+                compute_seconds: start.elapsed().as_secs_f32(),
+                cache_type: OperationCacheType::BoundByCPU,
+                compressed: None,
             };
             cache.insert(hash_value, cached_img);
             img
@@ -19,13 +19,13 @@
  *
  */
 
-use std::collections::hash_map::DefaultHasher;
 use std::hash;
 use std::hash::Hash;
 use std::hash::Hasher;
 
 use crate::attrblock::AttrBlock;
 use crate::data::HashValue;
+use crate::hashutils::StableHasher;
 
 pub mod colorgrade;
 
@@ -73,7 +73,7 @@ pub trait ColorOp: AttrBlock + ColorOpClone {
 
 /// Calcluate hash for a Vec of ColorOp instances.
 pub fn color_ops_hash(color_ops: &Vec<Box<dyn ColorOp>>) -> HashValue {
-    let mut state = DefaultHasher::default();
+    let mut state = StableHasher::default();
     for color_op in color_ops {
         color_op.hash(&mut state);
     }
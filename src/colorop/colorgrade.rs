@@ -19,7 +19,6 @@
  *
  */
 
-use std::collections::hash_map::DefaultHasher;
 use std::hash;
 use std::hash::Hash;
 use std::hash::Hasher;
@@ -32,6 +31,7 @@ use crate::cxxbridge::ffi::Vector4i32;
 use crate::data::FrameValue;
 use crate::data::HashValue;
 use crate::hashutils::HashableF32;
+use crate::hashutils::StableHasher;
 use crate::ops;
 
 #[derive(Debug, Clone)]
@@ -205,7 +205,7 @@ impl Default for ColorOpGrade {
 
 impl ColorOp for ColorOpGrade {
     fn hash_color_op(&self) -> HashValue {
-        let mut state = DefaultHasher::default();
+        let mut state = StableHasher::default();
         self.hash(&mut state);
         state.finish()
     }
@@ -278,7 +278,7 @@ impl ColorOp for ColorOpGrade {
 }
 
 impl AttrBlock for ColorOpGrade {
-    fn attr_hash(&self, _frame: FrameValue, state: &mut DefaultHasher) {
+    fn attr_hash(&self, _frame: FrameValue, state: &mut StableHasher) {
         self.hash(state)
     }
 
@@ -32,6 +32,7 @@
 
 #include <OpenImageIO/imagebufalgo.h>
 #include <OpenImageIO/color.h>
+#include <OpenImageIO/strutil.h>
 
 // OCIO
 #include <OpenColorIO/OpenColorIO.h>
@@ -56,6 +57,22 @@ bool ocio_print_color_spaces() {
     return ok;
 }
 
+// Is the color space name known to the color configuration used by
+// 'oiio_color_convert_inplace'? Names are compared case-insensitive.
+bool oiio_color_space_exists(const rust::Str color_space) {
+    auto color_space_name_str = std::string(color_space);
+
+    OIIO::ColorConfig colorConfig;
+    auto num_color_spaces = colorConfig.getNumColorSpaces();
+    for (auto i = 0; i < num_color_spaces; i++) {
+        auto name = colorConfig.getColorSpaceNameByIndex(i);
+        if (OIIO::Strutil::iequals(name, color_space_name_str)) {
+            return true;
+        }
+    }
+    return false;
+}
+
 bool oiio_color_convert_inplace(
         rust::Slice<float> pixel_data,
         int width, int height, int num_channels,
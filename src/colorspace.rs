@@ -23,6 +23,12 @@ use log::debug;
 use std::time::Instant;
 
 use crate::cxxbridge::ffi::oiio_color_convert_inplace;
+use crate::cxxbridge::ffi::oiio_color_space_exists;
+
+/// Is the color space name known to the color configuration?
+pub fn color_space_exists(color_space: &str) -> bool {
+    oiio_color_space_exists(color_space)
+}
 
 pub fn color_convert_inplace(
     pixel_data: &mut [f32],
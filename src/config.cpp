@@ -49,9 +49,38 @@ std::size_t Config::cache_ram_capacity_bytes() const noexcept {
     return this->inner.inner->cache_ram_capacity_bytes();
 }
 
+std::string Config::cache_disk_directory() const noexcept {
+    auto rust_str = this->inner.inner->cache_disk_directory();
+    return std::string(rust_str);
+}
+
+std::size_t Config::cache_disk_capacity_bytes() const noexcept {
+    return this->inner.inner->cache_disk_capacity_bytes();
+}
+
+DiskCacheImageType Config::cache_disk_image_type() const noexcept {
+    return this->inner.inner->cache_disk_image_type();
+}
+
+CacheCompression Config::cache_ram_compression() const noexcept {
+    return this->inner.inner->cache_ram_compression();
+}
+
+ImageIOBackend Config::imageio_backend() const noexcept {
+    return this->inner.inner->imageio_backend();
+}
+
 std::string Config::data_debug_string() const noexcept {
     auto rust_string = this->inner.inner->data_debug_string();
     return std::string(rust_string);
 }
 
+ImageIOBackend image_io_backend() noexcept {
+    return internal::image_io_backend();
+}
+
+void set_image_io_backend(ImageIOBackend backend) noexcept {
+    internal::set_image_io_backend(backend);
+}
+
 } // namespace open_comp_graph
@@ -30,8 +30,15 @@ use std::default::Default;
 use std::env;
 use std::fs;
 
+use crate::cache;
 use crate::cxxbridge::ffi::get_total_system_memory_as_bytes;
+use crate::cxxbridge::ffi::CacheCompression;
 use crate::cxxbridge::ffi::ConfigImplShared;
+use crate::cxxbridge::ffi::DiskCacheImageType;
+use crate::cxxbridge::ffi::ImageIOBackend;
+use crate::data::BYTES_TO_GIGABYTES;
+use crate::diskcache;
+use crate::imageio;
 
 #[derive(Debug, PartialEq, Deserialize)]
 struct ConfigCache {
@@ -40,12 +47,36 @@ struct ConfigCache {
     ///
     /// This value is expected to be between 0.0 and 100.0.
     ram_capacity_percent: f32,
+
+    /// How images are stored in RAM; "none", "lossless" or "lossy".
+    /// Defaults to "none".
+    #[serde(default)]
+    ram_compression: String,
+
+    /// The directory used to store the disk cache. An empty
+    /// directory disables the disk cache.
+    #[serde(default)]
+    disk_directory: String,
+
+    /// How much disk space to use for the disk cache, in gigabytes.
+    #[serde(default)]
+    disk_capacity_gigabytes: f32,
+
+    /// The image type used to write disk cache files; "jpeg",
+    /// "jpeg2000_lossy", "jpeg2000_lossless", "exr_lossy" or
+    /// "exr_lossless". Defaults to "exr_lossy".
+    #[serde(default)]
+    disk_image_type: String,
 }
 
 impl Default for ConfigCache {
     fn default() -> Self {
         ConfigCache {
             ram_capacity_percent: 0.0,
+            ram_compression: "".to_string(),
+            disk_directory: "".to_string(),
+            disk_capacity_gigabytes: 0.0,
+            disk_image_type: "".to_string(),
         }
     }
 }
@@ -61,6 +92,29 @@ impl ConfigCache {
     pub fn ram_capacity_percent(&self) -> f32 {
         (self.ram_capacity_percent / 100.0).min(0.0).max(1.0)
     }
+
+    pub fn disk_directory(&self) -> &str {
+        &self.disk_directory
+    }
+
+    pub fn ram_compression(&self) -> CacheCompression {
+        match self.ram_compression.as_str() {
+            "" => CacheCompression::Uncompressed,
+            value => cache::compression_from_name(value),
+        }
+    }
+
+    pub fn disk_capacity_bytes(&self) -> usize {
+        let gigabytes = self.disk_capacity_gigabytes.max(0.0);
+        (gigabytes as f64 * BYTES_TO_GIGABYTES as f64) as usize
+    }
+
+    pub fn disk_image_type(&self) -> DiskCacheImageType {
+        match self.disk_image_type.as_str() {
+            "" => DiskCacheImageType::EXR_Lossy_Half16,
+            value => diskcache::image_type_from_name(value),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Deserialize)]
@@ -82,10 +136,37 @@ impl ConfigThreading {
     }
 }
 
+#[derive(Debug, PartialEq, Deserialize)]
+struct ConfigImageIO {
+    /// The library used to read and write images; "oiio"
+    /// (OpenImageIO) or "rust". Defaults to "oiio".
+    #[serde(default)]
+    backend: String,
+}
+
+impl Default for ConfigImageIO {
+    fn default() -> Self {
+        ConfigImageIO {
+            backend: "".to_string(),
+        }
+    }
+}
+
+impl ConfigImageIO {
+    pub fn backend(&self) -> ImageIOBackend {
+        match self.backend.as_str() {
+            "" => ImageIOBackend::OpenImageIO,
+            value => imageio::backend_from_name(value),
+        }
+    }
+}
+
 #[derive(Debug, Default, PartialEq, Deserialize)]
 pub struct ConfigImpl {
     cache: ConfigCache,
     threading: ConfigThreading,
+    #[serde(default)]
+    imageio: ConfigImageIO,
 }
 
 fn load_config(file_path: &str) -> Result<ConfigImpl, serde_yaml::Error> {
@@ -103,14 +184,39 @@ impl ConfigImpl {
         self.cache.ram_capacity_percent()
     }
 
+    pub fn cache_disk_directory(&self) -> &str {
+        self.cache.disk_directory()
+    }
+
+    pub fn cache_disk_capacity_bytes(&self) -> usize {
+        self.cache.disk_capacity_bytes()
+    }
+
+    pub fn cache_disk_image_type(&self) -> DiskCacheImageType {
+        self.cache.disk_image_type()
+    }
+
+    pub fn cache_ram_compression(&self) -> CacheCompression {
+        self.cache.ram_compression()
+    }
+
+    pub fn imageio_backend(&self) -> ImageIOBackend {
+        self.imageio.backend()
+    }
+
     /// Convert the graph into a human-readable string, for debug
     /// printing.
     pub fn data_debug_string(&self) -> String {
         debug!("Config Debug");
         let string = format!(
-            "cache_ram_capacity_percent={} cache_ram_capacity_bytes={}",
+            "cache_ram_capacity_percent={} cache_ram_capacity_bytes={} cache_disk_directory={:?} cache_disk_capacity_bytes={} cache_disk_image_type={:?} cache_ram_compression={:?} imageio_backend={:?}",
             self.cache_ram_capacity_percent(),
             self.cache_ram_capacity_bytes(),
+            self.cache_disk_directory(),
+            self.cache_disk_capacity_bytes(),
+            self.cache_disk_image_type(),
+            self.cache_ram_compression(),
+            self.imageio_backend(),
         );
         string
     }
@@ -27,6 +27,8 @@ use crate::colorlutimage::get_color_ops_lut;
 use crate::colorlutimage::get_color_transform_3dlut;
 use crate::config::get_config;
 use crate::config::ConfigImpl;
+use crate::frameset::create_frame_set_box;
+use crate::frameset::FrameSetImpl;
 use crate::geom::export_mesh;
 use crate::geom::plane::create_geometry_plane_box;
 use crate::geom::plane::GeometryPlaneImpl;
@@ -34,12 +36,17 @@ use crate::graph::create_graph_box;
 use crate::graph::GraphImpl;
 use crate::hashutils::generate_id_from_name;
 use crate::hashutils::generate_random_id;
+use crate::imageio::image_io_backend;
+use crate::imageio::set_image_io_backend;
+use crate::imagesequence::find_image_sequences;
 use crate::logger::initialize;
 use crate::node::create_node_box_with_id;
 use crate::node::NodeImpl;
 use crate::pixelblock::pixelblock::PixelBlock;
 use crate::pixelblock::utils::channel_size_bytes;
 use crate::pixelblock::utils::stride_num_channels;
+use crate::prefetch::read_prefetch_image;
+use crate::prefetch::PrefetchImage;
 use crate::stream::create_stream_data_box;
 use crate::stream::create_stream_data_box_rc;
 use crate::stream::StreamDataImpl;
@@ -159,6 +166,12 @@ pub mod ffi {
         inner: Box<ConfigImpl>,
     }
 
+    #[derive(Debug)]
+    #[namespace = "open_comp_graph::internal"]
+    pub(crate) struct FrameSetImplShared {
+        inner: Box<FrameSetImpl>,
+    }
+
     #[derive(Debug, Clone)]
     #[namespace = "open_comp_graph::internal"]
     pub(crate) struct ImageSpec {
@@ -168,8 +181,132 @@ pub mod ffi {
         orientation: ImageOrientation,
         unassociated_alpha: bool,
         dither: i32,
+        metadata: Vec<ImageMetadata>,
+    }
+
+    // A named value stored in an image file's header, such as
+    // "DateTime", "smpte:TimeCode" or a custom studio key.
+    //
+    // Only the values matching 'data_type' are used; the other
+    // values are empty.
+    #[derive(Debug, Clone)]
+    #[namespace = "open_comp_graph::internal"]
+    pub(crate) struct ImageMetadata {
+        name: String,
+        data_type: ImageMetadataType,
+        int_values: Vec<i32>,
+        float_values: Vec<f32>,
+        string_value: String,
+    }
+    impl Vec<ImageMetadata> {}
+
+    // A problem found in the graph before it is executed.
+    //
+    // The 'frame' is only used for issues that change per-frame,
+    // such as a missing file in an image sequence.
+    #[derive(Debug, Clone)]
+    #[namespace = "open_comp_graph"]
+    pub(crate) struct ValidationIssue {
+        node_id: u64,
+        kind: ValidationIssueKind,
+        frame: f64,
+        message: String,
+    }
+    impl Vec<ValidationIssue> {}
+
+    // Is the output of a node at 'frame' stored in the cache?
+    #[derive(Debug, Copy, Clone)]
+    #[namespace = "open_comp_graph"]
+    pub(crate) struct CachedFrame {
+        frame: f64,
+        cached: bool,
+    }
+    impl Vec<CachedFrame> {}
+
+    // An image that can be read ahead of time, and stored in the
+    // cache with 'key', before node 'node_id' is computed at 'frame'.
+    // The image is read at 'proxy_scale'.
+    #[derive(Debug, Clone)]
+    #[namespace = "open_comp_graph"]
+    pub(crate) struct PrefetchRequest {
+        key: u64,
+        node_id: u64,
+        frame: f64,
+        file_path: String,
+        proxy_scale: ProxyScale,
+    }
+    impl Vec<PrefetchRequest> {}
+
+    // An image sequence found in a directory on disk.
+    //
+    // 'file_path' uses '#' characters for the frame number, so it can
+    // be used as a ReadImage node's "file_path". 'missing_frames_spec'
+    // is a frame spec string (see FrameSet) of the frames between
+    // 'first_frame' and 'last_frame' without a file, such as
+    // "1003-1004,1010", or empty if no frames are missing.
+    #[derive(Debug, Clone)]
+    #[namespace = "open_comp_graph"]
+    pub(crate) struct ImageSequence {
+        file_path: String,
+        first_frame: i32,
+        last_frame: i32,
+        padding: i32,
+        frame_count: usize,
+        missing_frames_spec: String,
+    }
+    impl Vec<ImageSequence> {}
+
+    // A change made to a graph, sent to the graph's observers.
+    //
+    // 'src_node_id' and 'input_num' are only used by connection
+    // events, and 'attr_name' is only used by attribute events.
+    #[derive(Debug, Clone)]
+    #[namespace = "open_comp_graph"]
+    pub(crate) struct GraphEvent {
+        kind: GraphEventKind,
+        node_id: u64,
+        src_node_id: u64,
+        input_num: u8,
+        attr_name: String,
     }
 
+    // A snapshot of the cache statistics.
+    #[derive(Debug, Copy, Clone)]
+    #[namespace = "open_comp_graph"]
+    pub(crate) struct CacheStats {
+        hits: usize,
+        misses: usize,
+        inserts: usize,
+        evictions: usize,
+        used_bytes: usize,
+        capacity_bytes: usize,
+        count: usize,
+        pinned_count: usize,
+        pinned_bytes: usize,
+        disk_count: usize,
+        disk_used_bytes: usize,
+        eviction_policy: CacheEvictionPolicy,
+        evicted_compute_seconds: f64,
+    }
+
+    // Details of a single value stored in the cache.
+    //
+    // 'last_access_order' is zero for the least recently used value,
+    // and increases for more recently used values.
+    #[derive(Debug, Copy, Clone)]
+    #[namespace = "open_comp_graph"]
+    pub(crate) struct CacheEntryInfo {
+        key: u64,
+        size_bytes: usize,
+        width: i32,
+        height: i32,
+        num_channels: i32,
+        data_type: DataType,
+        last_access_order: usize,
+        pinned: bool,
+    }
+    impl Vec<CacheEntryInfo> {}
+
     #[derive(Debug)]
     #[namespace = "open_comp_graph::internal"]
     pub(crate) struct ImageShared {
@@ -239,9 +376,15 @@ pub mod ffi {
         // Checkerboard,
         // Noise,
 
-        // // Time????
+        // Time
+        #[cxx_name = "kFrameBlend"]
+        FrameBlend = 10,
         // FrameHold,
 
+        // Metadata
+        #[cxx_name = "kMetadata"]
+        Metadata = 11,
+
         // Transform
         #[cxx_name = "kTransform"]
         Transform = 5,
@@ -298,6 +441,73 @@ pub mod ffi {
         Uninitialized = 255,
     }
 
+    // The reason a node has a Warning or Error NodeStatus.
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq)]
+    #[namespace = "open_comp_graph"]
+    pub(crate) enum NodeErrorKind {
+        #[cxx_name = "kNone"]
+        None = 0,
+        #[cxx_name = "kDisabled"]
+        Disabled = 1,
+        #[cxx_name = "kMissingInput"]
+        MissingInput = 2,
+        #[cxx_name = "kFileNotFound"]
+        FileNotFound = 3,
+        #[cxx_name = "kFileWriteFailure"]
+        FileWriteFailure = 4,
+        #[cxx_name = "kInvalidAttribute"]
+        InvalidAttribute = 5,
+        #[cxx_name = "kComputeFailure"]
+        ComputeFailure = 6,
+        #[cxx_name = "kUninitialized"]
+        Uninitialized = 255,
+    }
+
+    // The kinds of problems found when validating a graph.
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq)]
+    #[namespace = "open_comp_graph"]
+    pub(crate) enum ValidationIssueKind {
+        #[cxx_name = "kNodeNotFound"]
+        NodeNotFound = 0,
+        #[cxx_name = "kMissingInput"]
+        MissingInput = 1,
+        #[cxx_name = "kUnknownColorSpace"]
+        UnknownColorSpace = 2,
+        #[cxx_name = "kInvalidEnumValue"]
+        InvalidEnumValue = 3,
+        #[cxx_name = "kChannelCountMismatch"]
+        ChannelCountMismatch = 4,
+        #[cxx_name = "kFileNotFound"]
+        FileNotFound = 5,
+        #[cxx_name = "kLayerNotFound"]
+        LayerNotFound = 6,
+        #[cxx_name = "kInvalidAttribute"]
+        InvalidAttribute = 7,
+        #[cxx_name = "kUninitialized"]
+        Uninitialized = 255,
+    }
+
+    // The kinds of changes made to a graph.
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq)]
+    #[namespace = "open_comp_graph"]
+    pub(crate) enum GraphEventKind {
+        #[cxx_name = "kNodeAdded"]
+        NodeAdded = 0,
+        #[cxx_name = "kNodeRemoved"]
+        NodeRemoved = 1,
+        #[cxx_name = "kConnected"]
+        Connected = 2,
+        #[cxx_name = "kDisconnected"]
+        Disconnected = 3,
+        #[cxx_name = "kAttrChanged"]
+        AttrChanged = 4,
+        #[cxx_name = "kUninitialized"]
+        Uninitialized = 255,
+    }
+
     #[repr(u8)]
     #[derive(Debug, Copy, Clone, Hash)]
     #[namespace = "open_comp_graph"]
@@ -367,12 +577,53 @@ pub mod ffi {
         Uninitialized = 255,
     }
 
+    // The type of value stored in an ImageMetadata.
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq)]
+    #[namespace = "open_comp_graph"]
+    pub(crate) enum ImageMetadataType {
+        // NOTE: Keep these indexes in-line with the "From" trait
+        // below.
+        #[cxx_name = "kInt"]
+        Int = 0,
+        #[cxx_name = "kFloat"]
+        Float = 1,
+        #[cxx_name = "kString"]
+        String = 2,
+        #[cxx_name = "kUninitialized"]
+        Uninitialized = 255,
+    }
+
+    // How the Metadata node changes the image metadata.
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq)]
+    #[namespace = "open_comp_graph"]
+    pub(crate) enum MetadataMode {
+        // NOTE: Keep these indexes in-line with the "From" trait
+        // below.
+        //
+        // Add the metadata value, or replace the existing value.
+        #[cxx_name = "kSet"]
+        Set = 0,
+        //
+        // Remove the named metadata value.
+        #[cxx_name = "kRemove"]
+        Remove = 1,
+        //
+        // Remove all metadata values.
+        #[cxx_name = "kRemoveAll"]
+        RemoveAll = 2,
+        //
+        #[cxx_name = "kUninitialized"]
+        Uninitialized = 255,
+    }
+
     // The orientation of an image.
     //
     // https://openimageio.readthedocs.io/en/release-2.2.8.0/stdmetadata.html#cmdoption-arg-Orientation
     //
     #[repr(u8)]
-    #[derive(Debug, Copy, Clone, Hash)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq)]
     #[namespace = "open_comp_graph"]
     pub(crate) enum ImageOrientation {
         // NOTE: Keep these indexes in-line with the "From" trait
@@ -438,6 +689,59 @@ pub mod ffi {
         Uninitialized = 255,
     }
 
+    // How images are stored in the RAM cache.
+    //
+    // 'Lossless' compresses the pixel bytes. 'LossyHalf16' converts
+    // Float32 pixels to Half16 before compressing the bytes. Images
+    // are decompressed when they are read from the cache.
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq)]
+    #[namespace = "open_comp_graph"]
+    pub enum CacheCompression {
+        #[cxx_name = "kUncompressed"]
+        Uncompressed = 0,
+        #[cxx_name = "kLossless"]
+        Lossless = 1,
+        #[cxx_name = "kLossyHalf16"]
+        LossyHalf16 = 2,
+        #[cxx_name = "kUninitialized"]
+        Uninitialized = 255,
+    }
+
+    // The library used to read and write image files.
+    //
+    // 'OpenImageIO' uses the OpenImageIO C++ library. 'Rust' uses
+    // pure-Rust decoders and encoders, without the OpenImageIO C++
+    // library, but supports fewer file formats.
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq)]
+    #[namespace = "open_comp_graph"]
+    pub enum ImageIOBackend {
+        #[cxx_name = "kOpenImageIO"]
+        OpenImageIO = 0,
+        #[cxx_name = "kRust"]
+        Rust = 1,
+        #[cxx_name = "kUninitialized"]
+        Uninitialized = 255,
+    }
+
+    // How the cache chooses which entries to evict when it is full.
+    //
+    // 'LeastRecentlyUsed' evicts the entry that was used the longest
+    // time ago. 'ComputeCost' evicts the entry that is cheapest to
+    // re-compute per byte, out of the least recently used entries.
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq)]
+    #[namespace = "open_comp_graph"]
+    pub enum CacheEvictionPolicy {
+        #[cxx_name = "kLeastRecentlyUsed"]
+        LeastRecentlyUsed = 0,
+        #[cxx_name = "kComputeCost"]
+        ComputeCost = 1,
+        #[cxx_name = "kUninitialized"]
+        Uninitialized = 255,
+    }
+
     // Crop the input image the display window before writing?
     //
     // When 'auto' is enabled, if the image format to be writen does
@@ -457,6 +761,114 @@ pub mod ffi {
         Uninitialized = 255,
     }
 
+    // What does the ReadImage node use to detect that a file has
+    // changed?
+    //
+    // 'Path' only uses the file path, so a file that is overwritten
+    // on disk keeps using the old cached image. 'ModifiedTime' also
+    // uses the file's modification time and size, and 'Content'
+    // hashes the entire contents of the file (slow for large files).
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq)]
+    #[namespace = "open_comp_graph"]
+    pub enum ReadImageHashMode {
+        #[cxx_name = "kPath"]
+        Path = 0,
+        #[cxx_name = "kModifiedTime"]
+        ModifiedTime = 1,
+        #[cxx_name = "kContent"]
+        Content = 2,
+        #[cxx_name = "kUninitialized"]
+        Uninitialized = 255,
+    }
+
+    // What a ReadImage node outputs for frames before the first frame
+    // (or after the last frame) of the frame range.
+    //
+    // 'Hold' uses the first (or last) frame, 'Loop' repeats the frame
+    // range, 'Bounce' plays the frame range forwards then backwards,
+    // and 'Black' outputs a black image.
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq)]
+    #[namespace = "open_comp_graph"]
+    pub enum ReadImageFrameRangeMode {
+        #[cxx_name = "kHold"]
+        Hold = 0,
+        #[cxx_name = "kLoop"]
+        Loop = 1,
+        #[cxx_name = "kBounce"]
+        Bounce = 2,
+        #[cxx_name = "kBlack"]
+        Black = 3,
+        #[cxx_name = "kUninitialized"]
+        Uninitialized = 255,
+    }
+
+    // What a ReadImage node outputs when the file for a frame does
+    // not exist.
+    //
+    // 'Error' fails to compute the node, 'Black' outputs a black
+    // image, 'Nearest' reads the nearest frame that exists, and
+    // 'ColorBars' outputs the default colour bars image (with a
+    // warning).
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq)]
+    #[namespace = "open_comp_graph"]
+    pub enum ReadImageMissingFrameMode {
+        #[cxx_name = "kError"]
+        Error = 0,
+        #[cxx_name = "kBlack"]
+        Black = 1,
+        #[cxx_name = "kNearest"]
+        Nearest = 2,
+        #[cxx_name = "kColorBars"]
+        ColorBars = 3,
+        #[cxx_name = "kUninitialized"]
+        Uninitialized = 255,
+    }
+
+    // How a ReadImage node changes the alpha of the image read from
+    // disk.
+    //
+    // 'Auto' keeps the pixels as they are read, 'Premultiply'
+    // multiplies the colour channels by alpha, and 'Unpremultiply'
+    // divides the colour channels by alpha. The image spec
+    // 'unassociated_alpha' value is set to match the pixels.
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq)]
+    #[namespace = "open_comp_graph"]
+    pub enum ReadImageAlphaMode {
+        #[cxx_name = "kAuto"]
+        Auto = 0,
+        #[cxx_name = "kPremultiply"]
+        Premultiply = 1,
+        #[cxx_name = "kUnpremultiply"]
+        Unpremultiply = 2,
+        #[cxx_name = "kUninitialized"]
+        Uninitialized = 255,
+    }
+
+    // The resolution images are read and computed at, for fast
+    // review.
+    //
+    // 'Half', 'Quarter' and 'Eighth' divide the width and height of
+    // the image (and all pixel-space values) by 2, 4 and 8.
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq, PartialOrd)]
+    #[namespace = "open_comp_graph"]
+    pub enum ProxyScale {
+        #[cxx_name = "kFull"]
+        Full = 0,
+        #[cxx_name = "kHalf"]
+        Half = 1,
+        #[cxx_name = "kQuarter"]
+        Quarter = 2,
+        #[cxx_name = "kEighth"]
+        Eighth = 3,
+        #[cxx_name = "kUninitialized"]
+        Uninitialized = 255,
+    }
+
     // JPEG Image Chroma SubSampling values.
     //
     // https://en.wikipedia.org/wiki/Chroma_subsampling
@@ -529,6 +941,8 @@ pub mod ffi {
 
         fn ocio_print_color_spaces() -> bool;
 
+        fn oiio_color_space_exists(color_space: &str) -> bool;
+
         fn oiio_color_convert_inplace(
             pixel_data: &mut [f32],
             width: i32,
@@ -653,10 +1067,24 @@ pub mod ffi {
         fn oiio_get_thread_count(num_threads: &mut i32) -> bool;
         fn oiio_set_thread_count(num_threads: i32) -> bool;
         fn oiio_read_image(file_path: &String, image: &mut ImageShared) -> bool;
+        fn oiio_read_image_num_channels(file_path: &String, num_channels: &mut i32) -> bool;
+        fn oiio_read_image_header(file_path: &String, image: &mut ImageShared) -> bool;
+        fn oiio_read_image_channel_names(
+            file_path: &String,
+            channel_names: &mut Vec<String>) -> bool;
+        fn oiio_read_image_channels(
+            file_path: &String,
+            channel_names: &Vec<String>,
+            image: &mut ImageShared) -> bool;
         fn oiio_write_image(
             file_path: &String,
             image: &ImageShared,
             compress: &ImageCompression) -> bool;
+        fn oiio_write_image_channels(
+            file_path: &String,
+            image: &ImageShared,
+            channel_names: &Vec<String>,
+            compress: &ImageCompression) -> bool;
     }
 
     // System Memory Utilities
@@ -666,6 +1094,16 @@ pub mod ffi {
         fn get_total_system_memory_as_bytes() -> usize;
     }
 
+    // Graph Observer
+    #[namespace = "open_comp_graph::internal"]
+    unsafe extern "C++" {
+        include!("opencompgraph/internal/graph_observer.h");
+
+        type GraphObserverHandle;
+
+        fn notify(self: Pin<&mut GraphObserverHandle>, event: &GraphEvent);
+    }
+
     // PixelBlock
     #[namespace = "open_comp_graph::internal"]
     extern "Rust" {
@@ -711,6 +1149,7 @@ pub mod ffi {
         fn data_window(&self) -> BBox2Di;
         fn color_matrix(&self) -> Matrix4;
         fn clone_image_spec(&self) -> ImageSpec;
+        fn proxy_scale(&self) -> ProxyScale;
         fn deformers_len(&self) -> usize;
         fn apply_deformers(
             &self,
@@ -730,6 +1169,11 @@ pub mod ffi {
         fn pixel_height(&self) -> i32;
         fn pixel_num_channels(&self) -> i32;
         fn pixel_data_type(&self) -> DataType;
+        fn layers_len(&self) -> usize;
+        fn layer_name(&self, index: usize) -> &str;
+        fn layer_channel_names(&self, index: usize) -> Vec<String>;
+        fn layer_data_window(&self, index: usize) -> BBox2Di;
+        fn layer_pixel_buffer(&self, index: usize) -> &[u8];
 
         // Creation
         fn create_stream_data_box_rc() -> Box<StreamDataImplRc>;
@@ -764,7 +1208,34 @@ pub mod ffi {
         fn len(&self) -> usize;
         fn used_bytes(&self) -> usize;
         fn capacity_bytes(&self) -> usize;
-        fn set_capacity_bytes(&mut self, value: usize);
+        fn set_capacity_bytes(&self, value: usize);
+        fn invalidate_path(&self, path: &str) -> usize;
+        fn eviction_policy(&self) -> CacheEvictionPolicy;
+        fn set_eviction_policy(&self, value: CacheEvictionPolicy);
+        fn compression(&self) -> CacheCompression;
+        fn set_compression(&self, value: CacheCompression);
+        fn pin(&self, key: u64);
+        fn unpin(&self, key: u64) -> bool;
+        fn unpin_all(&self);
+        fn is_pinned(&self, key: u64) -> bool;
+        fn pinned_len(&self) -> usize;
+        fn pinned_bytes(&self) -> usize;
+        fn contains(&self, key: u64) -> bool;
+        fn insert_prefetch_image(&self, image: Box<PrefetchImage>) -> bool;
+        fn stats(&self) -> CacheStats;
+        fn entries(&self) -> Vec<CacheEntryInfo>;
+        fn enable_disk_cache(
+            &self,
+            directory: &str,
+            capacity_bytes: usize,
+            image_type: DiskCacheImageType,
+        ) -> bool;
+        fn disable_disk_cache(&self);
+        fn disk_cache_enabled(&self) -> bool;
+        fn disk_cache_len(&self) -> usize;
+        fn disk_cache_used_bytes(&self) -> usize;
+        fn disk_cache_capacity_bytes(&self) -> usize;
+        fn set_disk_cache_capacity_bytes(&self, value: usize);
         fn data_debug_string(&self) -> String;
 
         // Creation
@@ -778,11 +1249,16 @@ pub mod ffi {
         type GraphImpl;
         fn state(&self) -> GraphState;
         fn execute_status(&self) -> ExecuteStatus;
+        fn proxy_scale(&self) -> ProxyScale;
+        fn set_proxy_scale(&mut self, proxy_scale: ProxyScale);
         fn add_node(&mut self, op_box: Box<NodeImpl>) -> usize;
         fn remove_node(&mut self, node_id: u64) -> bool;
 
         fn node_attr_exists(&self, node_id: u64, name: &str) -> AttrState;
         fn node_status(&self, node_id: u64) -> NodeStatus;
+        fn node_error_kind(&self, node_id: u64) -> NodeErrorKind;
+        fn node_status_message(&self, node_id: u64) -> String;
+        fn node_hash(&self, node_id: u64, frame: f64) -> u64;
         fn get_node_attr_f32(&self, node_id: u64, name: &str) -> f32;
         fn get_node_attr_i32(&self, node_id: u64, name: &str) -> i32;
         unsafe fn get_node_attr_str<'a, 'b>(&'b self, node_id: u64, name: &'a str) -> &'b str;
@@ -793,11 +1269,24 @@ pub mod ffi {
         fn node_exists(&mut self, node_id: u64) -> bool;
         fn disconnect_input(&mut self, dst_node_id: u64, input_num: u8);
         fn connect(&mut self, src_node_id: u64, dst_node_id: u64, input_num: u8);
+        fn add_observer(&mut self, observer: UniquePtr<GraphObserverHandle>) -> u64;
+        fn remove_observer(&mut self, observer_id: u64) -> bool;
+        fn validate(&self, node_id: u64, frames: &[f64]) -> Vec<ValidationIssue>;
         fn execute(
             &mut self,
             node_id: u64,
             frames: &[f64],
-            cache: &mut Box<CacheImpl>) -> ExecuteStatus;
+            cache: &Box<CacheImpl>) -> ExecuteStatus;
+        fn cached_frames(
+            &self,
+            node_id: u64,
+            frames: &[f64],
+            cache: &Box<CacheImpl>) -> Vec<CachedFrame>;
+        fn prefetch_requests(
+            &self,
+            node_id: u64,
+            frames: &[f64],
+            cache: &Box<CacheImpl>) -> Vec<PrefetchRequest>;
         fn data_debug_string(&self) -> String;
         fn output_stream(&self) -> StreamDataImplShared;
 
@@ -848,17 +1337,40 @@ pub mod ffi {
             from_color_space: &str,
             to_color_space: &str,
             cube_size: i32,
-            cache: &mut Box<CacheImpl>,
+            cache: &Box<CacheImpl>,
         ) -> ImageShared;
 
         fn get_color_ops_lut(
             stream_data: &Box<StreamDataImplRc>,
             cube_size: i32,
             num_channels: i32,
-            cache: &mut Box<CacheImpl>,
+            cache: &Box<CacheImpl>,
         ) -> ImageShared;
     }
 
+    // Prefetch
+    #[namespace = "open_comp_graph::internal"]
+    extern "Rust" {
+        type PrefetchImage;
+        fn key(&self) -> u64;
+        fn size_bytes(&self) -> usize;
+
+        fn read_prefetch_image(request: &PrefetchRequest) -> Box<PrefetchImage>;
+    }
+
+    // Image IO
+    #[namespace = "open_comp_graph::internal"]
+    extern "Rust" {
+        fn image_io_backend() -> ImageIOBackend;
+        fn set_image_io_backend(backend: ImageIOBackend);
+    }
+
+    // Image Sequence
+    #[namespace = "open_comp_graph::internal"]
+    extern "Rust" {
+        fn find_image_sequences(directory: &str) -> Vec<ImageSequence>;
+    }
+
     // Geometry
     #[namespace = "open_comp_graph::internal"]
     extern "Rust" {
@@ -884,11 +1396,39 @@ pub mod ffi {
         type ConfigImpl;
         fn cache_ram_capacity_bytes(&self) -> usize;
         fn cache_ram_capacity_percent(&self) -> f32;
+        fn cache_disk_directory(&self) -> &str;
+        fn cache_disk_capacity_bytes(&self) -> usize;
+        fn cache_disk_image_type(&self) -> DiskCacheImageType;
+        fn cache_ram_compression(&self) -> CacheCompression;
+        fn imageio_backend(&self) -> ImageIOBackend;
         fn data_debug_string(&self) -> String;
 
         fn get_config(file_name: &str) -> ConfigImplShared;
     }
 
+    // Frame Set
+    #[namespace = "open_comp_graph::internal"]
+    extern "Rust" {
+        type FrameSetImpl;
+        fn set_from_spec(&mut self, spec: &str) -> bool;
+        fn len(&self) -> usize;
+        fn is_empty(&self) -> bool;
+        fn first_frame(&self) -> i32;
+        fn last_frame(&self) -> i32;
+        fn contains(&self, frame: i32) -> bool;
+        fn to_vec_i32(&self) -> Vec<i32>;
+        fn to_vec_f64(&self) -> Vec<f64>;
+        fn merge(&mut self, other: &FrameSetImpl);
+        fn chunk_count(&self, chunk_size: usize) -> usize;
+        fn chunk_box(&self, chunk_size: usize, chunk_index: usize) -> Box<FrameSetImpl>;
+        fn to_spec_string(&self) -> String;
+        fn data_debug_string(&self) -> String;
+
+        // Creation
+        fn create_frame_set_box() -> Box<FrameSetImpl>;
+        fn create_frame_set_shared() -> FrameSetImplShared;
+    }
+
     // Hashing Utilities
     #[namespace = "open_comp_graph::internal"]
     extern "Rust" {
@@ -932,6 +1472,43 @@ impl From<i32> for ffi::MergeImageMode {
     }
 }
 
+impl From<i32> for ffi::ImageMetadataType {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => ffi::ImageMetadataType::Int,
+            1 => ffi::ImageMetadataType::Float,
+            2 => ffi::ImageMetadataType::String,
+            _ => ffi::ImageMetadataType::Uninitialized,
+        }
+    }
+}
+
+impl From<i32> for ffi::MetadataMode {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => ffi::MetadataMode::Set,
+            1 => ffi::MetadataMode::Remove,
+            2 => ffi::MetadataMode::RemoveAll,
+            _ => ffi::MetadataMode::Uninitialized,
+        }
+    }
+}
+
+impl From<i32> for ffi::NodeErrorKind {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => ffi::NodeErrorKind::None,
+            1 => ffi::NodeErrorKind::Disabled,
+            2 => ffi::NodeErrorKind::MissingInput,
+            3 => ffi::NodeErrorKind::FileNotFound,
+            4 => ffi::NodeErrorKind::FileWriteFailure,
+            5 => ffi::NodeErrorKind::InvalidAttribute,
+            6 => ffi::NodeErrorKind::ComputeFailure,
+            _ => ffi::NodeErrorKind::Uninitialized,
+        }
+    }
+}
+
 impl From<i32> for ffi::LensDistortDirection {
     fn from(value: i32) -> Self {
         match value {
@@ -982,6 +1559,64 @@ impl From<i32> for ffi::CropOnWrite {
     }
 }
 
+impl From<i32> for ffi::ReadImageHashMode {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => ffi::ReadImageHashMode::Path,
+            1 => ffi::ReadImageHashMode::ModifiedTime,
+            2 => ffi::ReadImageHashMode::Content,
+            _ => ffi::ReadImageHashMode::Uninitialized,
+        }
+    }
+}
+
+impl From<i32> for ffi::ReadImageFrameRangeMode {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => ffi::ReadImageFrameRangeMode::Hold,
+            1 => ffi::ReadImageFrameRangeMode::Loop,
+            2 => ffi::ReadImageFrameRangeMode::Bounce,
+            3 => ffi::ReadImageFrameRangeMode::Black,
+            _ => ffi::ReadImageFrameRangeMode::Uninitialized,
+        }
+    }
+}
+
+impl From<i32> for ffi::ReadImageMissingFrameMode {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => ffi::ReadImageMissingFrameMode::Error,
+            1 => ffi::ReadImageMissingFrameMode::Black,
+            2 => ffi::ReadImageMissingFrameMode::Nearest,
+            3 => ffi::ReadImageMissingFrameMode::ColorBars,
+            _ => ffi::ReadImageMissingFrameMode::Uninitialized,
+        }
+    }
+}
+
+impl From<i32> for ffi::ReadImageAlphaMode {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => ffi::ReadImageAlphaMode::Auto,
+            1 => ffi::ReadImageAlphaMode::Premultiply,
+            2 => ffi::ReadImageAlphaMode::Unpremultiply,
+            _ => ffi::ReadImageAlphaMode::Uninitialized,
+        }
+    }
+}
+
+impl From<i32> for ffi::ProxyScale {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => ffi::ProxyScale::Full,
+            1 => ffi::ProxyScale::Half,
+            2 => ffi::ProxyScale::Quarter,
+            3 => ffi::ProxyScale::Eighth,
+            _ => ffi::ProxyScale::Uninitialized,
+        }
+    }
+}
+
 impl From<i32> for ffi::JpegChromaSubSampling {
     fn from(value: i32) -> Self {
         match value {
@@ -1021,6 +1656,13 @@ fn create_cache_shared_with_capacity(capacity_bytes: usize) -> ffi::CacheImplSha
     }
 }
 
+fn create_frame_set_shared() -> ffi::FrameSetImplShared {
+    debug!("create_frame_set_shared()");
+    ffi::FrameSetImplShared {
+        inner: create_frame_set_box(),
+    }
+}
+
 fn create_graph_shared() -> ffi::GraphImplShared {
     debug!("create_graph_shared()");
     ffi::GraphImplShared {
@@ -101,6 +101,14 @@ pub enum ErrorCode {
     Uninitialized,
 }
 
+/// How an Operation is categorized in terms of the cache.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OperationCacheType {
+    Trivial,
+    BoundByIO,
+    BoundByCPU,
+}
+
 bitflags! {
     #[derive(Default)]
     pub struct NodeComputeMode: u32 {
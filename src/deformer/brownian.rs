@@ -19,7 +19,6 @@
  *
  */
 
-use std::collections::hash_map::DefaultHasher;
 use std::hash;
 use std::hash::Hash;
 use std::hash::Hasher;
@@ -30,6 +29,7 @@ use crate::cxxbridge::ffi::BBox2Df;
 use crate::data::FrameValue;
 use crate::deformer::Deformer;
 use crate::hashutils::HashableF32;
+use crate::hashutils::StableHasher;
 use crate::math::interp;
 
 #[derive(Debug, Clone)]
@@ -132,7 +132,7 @@ fn _apply(
 
 impl Deformer for DeformerBrownian {
     fn hash_deformer(&self) -> u64 {
-        let mut state = DefaultHasher::default();
+        let mut state = StableHasher::default();
         self.hash(&mut state);
         state.finish()
     }
@@ -164,7 +164,7 @@ impl Deformer for DeformerBrownian {
 }
 
 impl AttrBlock for DeformerBrownian {
-    fn attr_hash(&self, _frame: FrameValue, state: &mut DefaultHasher) {
+    fn attr_hash(&self, _frame: FrameValue, state: &mut StableHasher) {
         self.hash(state)
     }
 
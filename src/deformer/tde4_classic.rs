@@ -20,7 +20,6 @@
  */
 
 use log::debug;
-use std::collections::hash_map::DefaultHasher;
 use std::hash;
 use std::hash::Hash;
 use std::hash::Hasher;
@@ -33,6 +32,7 @@ use crate::data::FrameValue;
 use crate::deformer::ldpk_utils::LensDistortionPlugin;
 use crate::deformer::Deformer;
 use crate::hashutils::HashableF32;
+use crate::hashutils::StableHasher;
 use crate::math::interp;
 
 // Note: All names end with a null terminator character because the
@@ -157,7 +157,7 @@ fn _apply(obj: &DeformerTde4Classic, xd: f32, yd: f32, inverse: bool) -> (f32, f
 
 impl Deformer for DeformerTde4Classic {
     fn hash_deformer(&self) -> u64 {
-        let mut state = DefaultHasher::default();
+        let mut state = StableHasher::default();
         self.hash(&mut state);
         state.finish()
     }
@@ -330,7 +330,7 @@ impl Deformer for DeformerTde4Classic {
 }
 
 impl AttrBlock for DeformerTde4Classic {
-    fn attr_hash(&self, _frame: FrameValue, state: &mut DefaultHasher) {
+    fn attr_hash(&self, _frame: FrameValue, state: &mut StableHasher) {
         self.hash(state)
     }
 
@@ -21,7 +21,6 @@
 
 // use log::warn;
 use nalgebra as na;
-use std::collections::hash_map::DefaultHasher;
 use std::hash;
 use std::hash::Hash;
 use std::hash::Hasher;
@@ -33,6 +32,7 @@ use crate::cxxbridge::ffi::TransformationOrder;
 use crate::data::FrameValue;
 use crate::deformer::Deformer;
 use crate::hashutils::HashableF32;
+use crate::hashutils::StableHasher;
 use crate::math::interp;
 use crate::math::xform;
 
@@ -141,7 +141,7 @@ fn _apply(
 
 impl Deformer for DeformerTransform {
     fn hash_deformer(&self) -> u64 {
-        let mut state = DefaultHasher::default();
+        let mut state = StableHasher::default();
         self.hash(&mut state);
         state.finish()
     }
@@ -173,7 +173,7 @@ impl Deformer for DeformerTransform {
 }
 
 impl AttrBlock for DeformerTransform {
-    fn attr_hash(&self, _frame: FrameValue, state: &mut DefaultHasher) {
+    fn attr_hash(&self, _frame: FrameValue, state: &mut StableHasher) {
         self.hash(state)
     }
 
@@ -0,0 +1,499 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+use linked_hash_map::LinkedHashMap;
+use log::{debug, warn};
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::cache::CachedImage;
+use crate::cxxbridge::ffi::BBox2Di;
+use crate::cxxbridge::ffi::DataType;
+use crate::cxxbridge::ffi::DiskCacheImageType;
+use crate::cxxbridge::ffi::ExrCompression;
+use crate::cxxbridge::ffi::ImageCompression;
+use crate::cxxbridge::ffi::ImageShared;
+use crate::cxxbridge::ffi::ImageSpec;
+use crate::cxxbridge::ffi::JpegChromaSubSampling;
+use crate::cxxbridge::ffi::NodeType;
+use crate::data::OperationCacheType;
+use crate::data::BYTES_TO_GIGABYTES;
+use crate::imageio;
+
+const FILE_NAME_PREFIX: &str = "ocg_";
+
+type DiskCacheKey = u64;
+
+/// The image details that are lost, or changed, when the image is
+/// written to disk, so they can be restored when it is read back.
+#[derive(Debug, Clone)]
+struct DiskCacheImageInfo {
+    spec: ImageSpec,
+    display_window: BBox2Di,
+    data_window: BBox2Di,
+    source_path: Option<PathBuf>,
+    node_type: Option<NodeType>,
+    compute_seconds: f32,
+    cache_type: OperationCacheType,
+}
+
+#[derive(Debug, Clone)]
+struct DiskCacheEntry {
+    path: PathBuf,
+    size_bytes: usize,
+    // Files found on disk from an earlier session do not have this
+    // information, and use the details stored in the file instead.
+    info: Option<DiskCacheImageInfo>,
+}
+
+/// The file extension used for each disk cache image type.
+pub fn file_extension(image_type: DiskCacheImageType) -> &'static str {
+    match image_type {
+        DiskCacheImageType::JPEG_UInt8 => "jpg",
+        DiskCacheImageType::JPEG_2000_Lossy_UInt8 => "jp2",
+        DiskCacheImageType::JPEG_2000_Lossless_UInt8 => "jp2",
+        DiskCacheImageType::EXR_Lossy_Half16 => "exr",
+        DiskCacheImageType::EXR_Lossless_Half16 => "exr",
+        _ => "exr",
+    }
+}
+
+/// Parse the disk cache image type from a name, as used in the
+/// configuration file.
+pub fn image_type_from_name(name: &str) -> DiskCacheImageType {
+    match name.to_lowercase().as_str() {
+        "jpeg" => DiskCacheImageType::JPEG_UInt8,
+        "jpeg2000_lossy" => DiskCacheImageType::JPEG_2000_Lossy_UInt8,
+        "jpeg2000_lossless" => DiskCacheImageType::JPEG_2000_Lossless_UInt8,
+        "exr_lossy" => DiskCacheImageType::EXR_Lossy_Half16,
+        "exr_lossless" => DiskCacheImageType::EXR_Lossless_Half16,
+        _ => DiskCacheImageType::Uninitialized,
+    }
+}
+
+fn pixel_data_type(image_type: DiskCacheImageType) -> DataType {
+    match image_type {
+        DiskCacheImageType::JPEG_UInt8 => DataType::UInt8,
+        DiskCacheImageType::JPEG_2000_Lossy_UInt8 => DataType::UInt8,
+        DiskCacheImageType::JPEG_2000_Lossless_UInt8 => DataType::UInt8,
+        _ => DataType::Half16,
+    }
+}
+
+// NOTE: JPEG 2000 compression settings are not exposed by the image
+// writer, so the lossy and lossless JPEG 2000 types are currently
+// written with the same (OpenImageIO default) settings.
+fn image_compression(image_type: DiskCacheImageType) -> ImageCompression {
+    let exr_compression = match image_type {
+        DiskCacheImageType::EXR_Lossy_Half16 => ExrCompression::Dwaa,
+        _ => ExrCompression::Zip,
+    };
+    ImageCompression {
+        exr_compression,
+        exr_dwa_compression_level: 45,
+        png_compression_level: 6,
+        jpeg_compression_level: 90,
+        jpeg_subsampling: JpegChromaSubSampling::None444,
+        jpeg_progressive: false,
+    }
+}
+
+/// Parse the cache key from a disk cache file name, if the file name
+/// was made by the disk cache.
+fn key_from_file_name(file_name: &str, extension: &str) -> Option<DiskCacheKey> {
+    let name = file_name.strip_prefix(FILE_NAME_PREFIX)?;
+    let name = name.strip_suffix(extension)?;
+    let name = name.strip_suffix('.')?;
+    u64::from_str_radix(name, 16).ok()
+}
+
+/// A cache of images stored as files in a directory on disk.
+///
+/// Used as a second level behind the RAM cache. Images evicted from
+/// RAM are written to disk, and are read back on a RAM cache miss.
+/// The directory has its own capacity, and the least recently used
+/// files are deleted when the capacity is exceeded.
+#[derive(Debug)]
+pub struct DiskCache {
+    directory: PathBuf,
+    image_type: DiskCacheImageType,
+    lru_hash_map: LinkedHashMap<DiskCacheKey, DiskCacheEntry>,
+
+    // Keep track of how much disk space is used in the cache.
+    capacity_bytes: usize,
+    used_bytes: usize,
+
+    // Diagnostics; hits vs misses.
+    hits: usize,
+    misses: usize,
+    inserts: usize,
+    evictions: usize,
+}
+
+impl DiskCache {
+    /// Create a disk cache in the directory. The directory is
+    /// created if it does not exist.
+    ///
+    /// Files written by an earlier disk cache (with the same image
+    /// type) are found and can be re-used.
+    pub fn new(
+        directory: &str,
+        capacity_bytes: usize,
+        image_type: DiskCacheImageType,
+    ) -> Result<DiskCache, String> {
+        debug!(
+            "DiskCache::new(directory={:?}, capacity_bytes={}, image_type={:?})",
+            directory, capacity_bytes, image_type
+        );
+        if image_type == DiskCacheImageType::Uninitialized {
+            return Err("invalid disk cache image type".to_string());
+        }
+        let directory_expanded = shellexpand::full(directory)
+            .map_err(|e| format!("could not expand disk cache directory: {}", e))?;
+        let directory = PathBuf::from(directory_expanded.as_ref());
+        fs::create_dir_all(&directory).map_err(|e| {
+            format!(
+                "could not create disk cache directory {:?}: {}",
+                directory, e
+            )
+        })?;
+
+        let mut disk_cache = DiskCache {
+            directory,
+            image_type,
+            lru_hash_map: LinkedHashMap::new(),
+            capacity_bytes,
+            used_bytes: 0,
+            hits: 0,
+            misses: 0,
+            inserts: 0,
+            evictions: 0,
+        };
+        disk_cache.find_existing_files();
+        disk_cache.evict_to_capacity();
+        Ok(disk_cache)
+    }
+
+    /// Add the files already in the directory, oldest first, so
+    /// that older files are evicted first.
+    fn find_existing_files(&mut self) {
+        let read_dir = match fs::read_dir(&self.directory) {
+            Ok(value) => value,
+            Err(e) => {
+                warn!("Could not read disk cache directory: {}", e);
+                return;
+            }
+        };
+        let extension = file_extension(self.image_type);
+        let mut files = Vec::new();
+        for dir_entry in read_dir.flatten() {
+            let file_name = dir_entry.file_name();
+            let key = match key_from_file_name(&file_name.to_string_lossy(), extension) {
+                Some(value) => value,
+                None => continue,
+            };
+            let metadata = match dir_entry.metadata() {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            files.push((modified, key, dir_entry.path(), metadata.len() as usize));
+        }
+        files.sort_by_key(|x| x.0);
+
+        debug!("Disk cache found {} existing file(s)", files.len());
+        for (_modified, key, path, size_bytes) in files {
+            let entry = DiskCacheEntry {
+                path,
+                size_bytes,
+                info: None,
+            };
+            self.lru_hash_map.insert(key, entry);
+            self.used_bytes += size_bytes;
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    pub fn image_type(&self) -> DiskCacheImageType {
+        self.image_type
+    }
+
+    /// Number of files in the disk cache.
+    pub fn len(&self) -> usize {
+        self.lru_hash_map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lru_hash_map.is_empty()
+    }
+
+    /// Amount of disk space used by the cache.
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes
+    }
+
+    /// Amount of disk space that can be used by the cache.
+    pub fn capacity_bytes(&self) -> usize {
+        self.capacity_bytes
+    }
+
+    /// Set the capacity of the disk cache. Files are deleted until
+    /// the used disk space fits in the capacity.
+    pub fn set_capacity_bytes(&mut self, value: usize) {
+        self.capacity_bytes = value;
+        self.evict_to_capacity();
+    }
+
+    pub fn contains_key(&self, key: &DiskCacheKey) -> bool {
+        self.lru_hash_map.contains_key(key)
+    }
+
+    fn file_path(&self, key: DiskCacheKey) -> PathBuf {
+        let extension = file_extension(self.image_type);
+        let file_name = format!("{}{:016x}.{}", FILE_NAME_PREFIX, key, extension);
+        self.directory.join(file_name)
+    }
+
+    /// Write the image to disk. If the image is already stored it
+    /// is not written again.
+    pub fn insert(&mut self, key: DiskCacheKey, value: &CachedImage) -> bool {
+        debug!("Insert into Disk Cache: key={}", key);
+        if self.lru_hash_map.get_refresh(&key).is_some() {
+            return true;
+        }
+        if self.capacity_bytes == 0 {
+            return false;
+        }
+
+        let mut pixel_block = (*value.pixel_block).clone();
+        pixel_block.convert_into_data_type(pixel_data_type(self.image_type));
+        let image = ImageShared {
+            pixel_block: Box::new(pixel_block),
+            display_window: value.display_window,
+            data_window: value.data_window,
+            spec: value.spec.clone(),
+        };
+
+        let path = self.file_path(key);
+        let path_string = path.to_string_lossy().to_string();
+        let num_threads = 0;
+        let crop_to_display_window = false;
+        let compress = image_compression(self.image_type);
+        let ok = imageio::write_image(
+            &image,
+            &path_string,
+            num_threads,
+            crop_to_display_window,
+            compress,
+        );
+        if !ok {
+            return false;
+        }
+        let size_bytes = match fs::metadata(&path) {
+            Ok(metadata) => metadata.len() as usize,
+            Err(e) => {
+                warn!("Disk cache file was not written: {:?} {}", path, e);
+                return false;
+            }
+        };
+
+        let entry = DiskCacheEntry {
+            path,
+            size_bytes,
+            info: Some(DiskCacheImageInfo {
+                spec: value.spec.clone(),
+                display_window: value.display_window,
+                data_window: value.data_window,
+                source_path: value.source_path.clone(),
+                node_type: value.node_type,
+                compute_seconds: value.compute_seconds,
+                cache_type: value.cache_type,
+            }),
+        };
+        self.lru_hash_map.insert(key, entry);
+        self.used_bytes += size_bytes;
+        self.inserts += 1;
+        self.evict_to_capacity();
+        true
+    }
+
+    /// Read an image from the disk cache, if it exists.
+    pub fn get(&mut self, key: &DiskCacheKey) -> Option<CachedImage> {
+        debug!("Query Disk Cache: key={}", key);
+        let entry = match self.lru_hash_map.get_refresh(key) {
+            Some(value) => value.clone(),
+            None => {
+                self.misses += 1;
+                return None;
+            }
+        };
+        if !entry.path.is_file() {
+            // The file has been deleted by something else.
+            warn!("Disk cache file is missing: {:?}", entry.path);
+            self.remove(key);
+            self.misses += 1;
+            return None;
+        }
+
+        let path_string = entry.path.to_string_lossy().to_string();
+        let num_threads = 0;
+        let image = match imageio::read_image(&path_string, num_threads) {
+            Ok(image) if (image.pixel_block.width() > 0) && (image.pixel_block.height() > 0) => {
+                image
+            }
+            _ => {
+                // The file could not be read, for example if it was
+                // truncated when a previous session crashed.
+                warn!("Disk cache file could not be read: {:?}", entry.path);
+                self.remove(key);
+                self.misses += 1;
+                return None;
+            }
+        };
+        let info = match entry.info {
+            Some(info)
+                if (info.data_window.width() == image.pixel_block.width())
+                    && (info.data_window.height() == image.pixel_block.height()) =>
+            {
+                info
+            }
+            _ => DiskCacheImageInfo {
+                spec: image.spec,
+                display_window: image.display_window,
+                data_window: image.data_window,
+                source_path: None,
+                node_type: None,
+                compute_seconds: 0.0,
+                cache_type: OperationCacheType::BoundByCPU,
+            },
+        };
+        self.hits += 1;
+        Some(CachedImage {
+            pixel_block: Arc::new(*image.pixel_block),
+            spec: info.spec,
+            display_window: info.display_window,
+            data_window: info.data_window,
+            source_path: info.source_path,
+            node_type: info.node_type,
+            compute_seconds: info.compute_seconds,
+            cache_type: info.cache_type,
+            compressed: None,
+        })
+    }
+
+    /// Delete all files that were read from 'path', or from any file
+    /// inside the directory 'path'. Returns the number of files
+    /// deleted.
+    ///
+    /// Files found on disk from an earlier session do not know their
+    /// source path, and are not deleted.
+    pub fn invalidate_path(&mut self, path: &Path) -> usize {
+        let keys: Vec<DiskCacheKey> = self
+            .lru_hash_map
+            .iter()
+            .filter(|(_key, entry)| match &entry.info {
+                Some(DiskCacheImageInfo {
+                    source_path: Some(source_path),
+                    ..
+                }) => source_path.starts_with(path),
+                _ => false,
+            })
+            .map(|(key, _entry)| *key)
+            .collect();
+        for key in &keys {
+            self.remove(key);
+        }
+        keys.len()
+    }
+
+    /// Remove the entry and delete the file.
+    fn remove(&mut self, key: &DiskCacheKey) -> bool {
+        match self.lru_hash_map.remove(key) {
+            Some(entry) => {
+                self.delete_entry_file(entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn delete_entry_file(&mut self, entry: DiskCacheEntry) {
+        // There should not be any overflow problems with this
+        // subtraction since we add and subtract exactly what goes
+        // into the cache.
+        self.used_bytes -= entry.size_bytes;
+        if let Err(e) = fs::remove_file(&entry.path) {
+            warn!("Could not delete disk cache file {:?}: {}", entry.path, e);
+        }
+    }
+
+    /// Delete the least recently used files until the used disk
+    /// space fits within the capacity.
+    fn evict_to_capacity(&mut self) {
+        while (self.used_bytes > self.capacity_bytes) && !self.lru_hash_map.is_empty() {
+            self.evict();
+        }
+    }
+
+    /// Delete the least recently used file.
+    pub fn evict(&mut self) -> bool {
+        match self.lru_hash_map.pop_front() {
+            Some((key, entry)) => {
+                debug!("Evict from Disk Cache: key={}", key);
+                self.delete_entry_file(entry);
+                self.evictions += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Delete all files in the disk cache.
+    pub fn evict_all(&mut self) {
+        debug!("EVICT ALL (Disk): count={}", self.lru_hash_map.len());
+        while self.evict() {}
+    }
+
+    /// Convert the disk cache into a human-readable string, for
+    /// debug printing.
+    pub fn data_debug_string(&self) -> String {
+        format!(
+            "disk_directory={:?} disk_capacity={}GB disk_used={}GB disk_hits={} disk_misses={} disk_inserts={} disk_evictions={} disk_count={}",
+            self.directory,
+            self.capacity_bytes as f64 / BYTES_TO_GIGABYTES as f64,
+            self.used_bytes as f64 / BYTES_TO_GIGABYTES as f64,
+            self.hits,
+            self.misses,
+            self.inserts,
+            self.evictions,
+            self.lru_hash_map.len()
+        )
+    }
+}
@@ -0,0 +1,105 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+#include <opencompgraph/frameset.h>
+
+namespace open_comp_graph {
+
+FrameSet::FrameSet() noexcept
+        : inner{internal::create_frame_set_shared()} {
+}
+
+FrameSet::FrameSet(const char* spec) noexcept
+        : inner{internal::create_frame_set_shared()} {
+    this->inner.inner->set_from_spec(spec);
+}
+
+FrameSet::FrameSet(rust::Box<internal::FrameSetImpl> box) noexcept
+        : inner{std::move(box)} {
+}
+
+rust::Box<internal::FrameSetImpl> FrameSet::get_box() noexcept {
+    return std::move(this->inner.inner);
+}
+
+void FrameSet::set_box(rust::Box<internal::FrameSetImpl> box) noexcept {
+    this->inner.inner = std::move(box);
+}
+
+bool FrameSet::parse(const char* spec) noexcept {
+    return this->inner.inner->set_from_spec(spec);
+}
+
+std::size_t FrameSet::len() const noexcept {
+    return this->inner.inner->len();
+}
+
+bool FrameSet::is_empty() const noexcept {
+    return this->inner.inner->is_empty();
+}
+
+int32_t FrameSet::first_frame() const noexcept {
+    return this->inner.inner->first_frame();
+}
+
+int32_t FrameSet::last_frame() const noexcept {
+    return this->inner.inner->last_frame();
+}
+
+bool FrameSet::contains(int32_t frame) const noexcept {
+    return this->inner.inner->contains(frame);
+}
+
+std::vector<int32_t> FrameSet::frames() const noexcept {
+    auto rust_frames = this->inner.inner->to_vec_i32();
+    return std::vector<int32_t>(rust_frames.begin(), rust_frames.end());
+}
+
+std::vector<double> FrameSet::frames_f64() const noexcept {
+    auto rust_frames = this->inner.inner->to_vec_f64();
+    return std::vector<double>(rust_frames.begin(), rust_frames.end());
+}
+
+void FrameSet::merge(const FrameSet &other) noexcept {
+    this->inner.inner->merge(*other.inner.inner);
+}
+
+std::vector<FrameSet> FrameSet::chunks(std::size_t chunk_size) const noexcept {
+    std::vector<FrameSet> chunks;
+    auto count = this->inner.inner->chunk_count(chunk_size);
+    chunks.reserve(count);
+    for (std::size_t i = 0; i < count; ++i) {
+        chunks.emplace_back(this->inner.inner->chunk_box(chunk_size, i));
+    }
+    return chunks;
+}
+
+std::string FrameSet::to_spec_string() const noexcept {
+    auto rust_string = this->inner.inner->to_spec_string();
+    return std::string(rust_string);
+}
+
+std::string FrameSet::data_debug_string() const noexcept {
+    auto rust_string = this->inner.inner->data_debug_string();
+    return std::string(rust_string);
+}
+
+} // namespace open_comp_graph
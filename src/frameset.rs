@@ -0,0 +1,337 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+//! Sets of frame numbers, parsed from a frame range "spec" string.
+//!
+//! The frame spec syntax is a comma separated list of items, each
+//! item may be:
+//!
+//! - A single frame; "1001" or "-5".
+//! - An inclusive range of frames; "1001-1100" or "-10--5".
+//! - A range of frames with a step; "1-100x2".
+//!
+//! For example "1,5,10-20" is the frames 1, 5 and 10 to 20.
+//!
+//! Frames are kept sorted and unique, so the order and repetition
+//! of items in the spec string does not matter.
+
+use log::{debug, warn};
+
+/// The largest number of frames a frame spec may expand to, so a
+/// spec such as "1-2000000000" is an error rather than allocating
+/// billions of frames.
+const MAX_FRAME_COUNT: usize = 1_000_000;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FrameSetImpl {
+    frames: Vec<i32>,
+}
+
+/// Parse an integer, with an optional leading '-' sign, from the
+/// start of the string. Returns the number and the rest of the
+/// string.
+fn parse_leading_integer(value: &str) -> Result<(i32, &str), String> {
+    let digits_start = if value.starts_with('-') { 1 } else { 0 };
+    let digits_end = value[digits_start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(value.len(), |i| i + digits_start);
+    if digits_end == digits_start {
+        return Err(format!("expected a frame number: {:?}", value));
+    }
+    match value[..digits_end].parse::<i32>() {
+        Ok(number) => Ok((number, &value[digits_end..])),
+        Err(e) => Err(format!("invalid frame number {:?}: {}", value, e)),
+    }
+}
+
+/// Parse a single item of a frame spec; "1", "1-10" or "1-10x2".
+fn parse_item(item: &str) -> Result<(i32, i32, i32), String> {
+    let (start, rest) = parse_leading_integer(item)?;
+    if rest.is_empty() {
+        return Ok((start, start, 1));
+    }
+
+    let rest = match rest.strip_prefix('-') {
+        Some(value) => value,
+        None => return Err(format!("expected '-' in frame range: {:?}", item)),
+    };
+    let (end, rest) = parse_leading_integer(rest)?;
+    if end < start {
+        return Err(format!("frame range end is before start: {:?}", item));
+    }
+    if rest.is_empty() {
+        return Ok((start, end, 1));
+    }
+
+    let rest = match rest.strip_prefix('x') {
+        Some(value) => value,
+        None => return Err(format!("expected 'x' in frame range: {:?}", item)),
+    };
+    let (step, rest) = parse_leading_integer(rest)?;
+    if !rest.is_empty() {
+        return Err(format!("unexpected characters in frame range: {:?}", item));
+    }
+    if step <= 0 {
+        return Err(format!("frame range step must be positive: {:?}", item));
+    }
+    Ok((start, end, step))
+}
+
+impl FrameSetImpl {
+    pub fn new() -> FrameSetImpl {
+        FrameSetImpl { frames: Vec::new() }
+    }
+
+    /// Create a frame set from a list of frames, in any order.
+    pub fn from_frames(frames: &[i32]) -> FrameSetImpl {
+        let mut frames = frames.to_vec();
+        frames.sort_unstable();
+        frames.dedup();
+        FrameSetImpl { frames }
+    }
+
+    /// Parse a frame spec string, such as "1001-1100", "1-100x2" or
+    /// "1,5,10-20".
+    pub fn from_spec(spec: &str) -> Result<FrameSetImpl, String> {
+        debug!("FrameSet.from_spec: {:?}", spec);
+        let mut frames = Vec::new();
+        for item in spec.split(',') {
+            let item: String = item.chars().filter(|c| !c.is_whitespace()).collect();
+            if item.is_empty() {
+                continue;
+            }
+            let (start, end, step) = parse_item(&item)?;
+            let count = ((end as i64 - start as i64) / step as i64 + 1) as usize;
+            if frames.len() + count > MAX_FRAME_COUNT {
+                return Err(format!(
+                    "frame spec has more than {} frames: {:?}",
+                    MAX_FRAME_COUNT, spec
+                ));
+            }
+            frames.extend((start..=end).step_by(step as usize));
+        }
+        Ok(FrameSetImpl::from_frames(&frames))
+    }
+
+    /// Replace the frames with the frames parsed from the spec
+    /// string. Returns false (and leaves the frames unchanged) if
+    /// the spec cannot be parsed.
+    pub fn set_from_spec(&mut self, spec: &str) -> bool {
+        match FrameSetImpl::from_spec(spec) {
+            Ok(value) => {
+                *self = value;
+                true
+            }
+            Err(e) => {
+                warn!("Invalid frame spec: {}", e);
+                false
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// The first (lowest) frame, or zero if the frame set is empty.
+    pub fn first_frame(&self) -> i32 {
+        self.frames.first().copied().unwrap_or(0)
+    }
+
+    /// The last (highest) frame, or zero if the frame set is empty.
+    pub fn last_frame(&self) -> i32 {
+        self.frames.last().copied().unwrap_or(0)
+    }
+
+    pub fn contains(&self, frame: i32) -> bool {
+        self.frames.binary_search(&frame).is_ok()
+    }
+
+    pub fn frames(&self) -> &[i32] {
+        &self.frames
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, i32> {
+        self.frames.iter()
+    }
+
+    pub fn to_vec_i32(&self) -> Vec<i32> {
+        self.frames.clone()
+    }
+
+    /// The frames as floating point values, ready to be executed.
+    pub fn to_vec_f64(&self) -> Vec<f64> {
+        self.frames.iter().map(|x| *x as f64).collect()
+    }
+
+    /// Add all the frames from another frame set into this one.
+    pub fn merge(&mut self, other: &FrameSetImpl) {
+        self.frames.extend_from_slice(&other.frames);
+        self.frames.sort_unstable();
+        self.frames.dedup();
+    }
+
+    /// Split the frames into chunks of (at most) 'chunk_size'
+    /// frames, for example to distribute frames across many render
+    /// tasks.
+    pub fn chunks(&self, chunk_size: usize) -> Vec<FrameSetImpl> {
+        if chunk_size == 0 {
+            return vec![self.clone()];
+        }
+        self.frames
+            .chunks(chunk_size)
+            .map(|x| FrameSetImpl { frames: x.to_vec() })
+            .collect()
+    }
+
+    /// The number of chunks 'chunks()' would return.
+    pub fn chunk_count(&self, chunk_size: usize) -> usize {
+        if chunk_size == 0 {
+            return 1;
+        }
+        self.frames.len().div_ceil(chunk_size)
+    }
+
+    /// A single chunk of the frames, see 'chunks()'.
+    pub fn chunk_box(&self, chunk_size: usize, chunk_index: usize) -> Box<FrameSetImpl> {
+        let frames = match chunk_size {
+            0 => self.frames.clone(),
+            _ => self
+                .frames
+                .chunks(chunk_size)
+                .nth(chunk_index)
+                .map_or(Vec::new(), |x| x.to_vec()),
+        };
+        Box::new(FrameSetImpl { frames })
+    }
+
+    /// Convert the frames into a compact frame spec string, the
+    /// opposite of 'from_spec()'.
+    pub fn to_spec_string(&self) -> String {
+        let mut items = Vec::new();
+        let mut index = 0;
+        while index < self.frames.len() {
+            let start = self.frames[index];
+            if index + 1 == self.frames.len() {
+                items.push(format!("{}", start));
+                break;
+            }
+
+            // Extend the range for as long as the step between
+            // frames stays the same.
+            let step = self.frames[index + 1] - start;
+            let mut end_index = index + 1;
+            while end_index + 1 < self.frames.len()
+                && (self.frames[end_index + 1] - self.frames[end_index]) == step
+            {
+                end_index += 1;
+            }
+
+            let end = self.frames[end_index];
+            let count = end_index - index + 1;
+            if count == 2 && step != 1 {
+                // Two frames with a gap are clearer as a list.
+                items.push(format!("{}", start));
+                index += 1;
+                continue;
+            }
+            match step {
+                1 => items.push(format!("{}-{}", start, end)),
+                _ => items.push(format!("{}-{}x{}", start, end, step)),
+            }
+            index = end_index + 1;
+        }
+        items.join(",")
+    }
+
+    /// Convert the frame set into a human-readable string, for
+    /// debug printing.
+    pub fn data_debug_string(&self) -> String {
+        format!("len={} spec={}", self.len(), self.to_spec_string())
+    }
+}
+
+pub fn create_frame_set_box() -> Box<FrameSetImpl> {
+    debug!("create_frame_set_box()");
+    Box::new(FrameSetImpl::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_range() {
+        let frame_set = FrameSetImpl::from_spec("1001-1100").unwrap();
+        assert_eq!(frame_set.len(), 100);
+        assert_eq!(frame_set.first_frame(), 1001);
+        assert_eq!(frame_set.last_frame(), 1100);
+    }
+
+    #[test]
+    fn test_parse_step_and_list() {
+        let frame_set = FrameSetImpl::from_spec("1-10x2").unwrap();
+        assert_eq!(frame_set.frames(), &[1, 3, 5, 7, 9]);
+
+        let frame_set = FrameSetImpl::from_spec("1, 5, 10-12").unwrap();
+        assert_eq!(frame_set.frames(), &[1, 5, 10, 11, 12]);
+    }
+
+    #[test]
+    fn test_parse_negative() {
+        let frame_set = FrameSetImpl::from_spec("-3--1,-1-1").unwrap();
+        assert_eq!(frame_set.frames(), &[-3, -2, -1, 0, 1]);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(FrameSetImpl::from_spec("10-1").is_err());
+        assert!(FrameSetImpl::from_spec("1-10x0").is_err());
+        assert!(FrameSetImpl::from_spec("1-10y2").is_err());
+        assert!(FrameSetImpl::from_spec("abc").is_err());
+        assert!(FrameSetImpl::from_spec("1-2000000000").is_err());
+        assert!(FrameSetImpl::from_spec("1-2000000000x10000").is_ok());
+    }
+
+    #[test]
+    fn test_merge_and_chunk() {
+        let mut frame_set = FrameSetImpl::from_spec("1-5").unwrap();
+        frame_set.merge(&FrameSetImpl::from_spec("4-8").unwrap());
+        assert_eq!(frame_set.len(), 8);
+
+        let chunks = frame_set.chunks(3);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(frame_set.chunk_count(3), 3);
+        assert_eq!(chunks[2].frames(), &[7, 8]);
+        assert_eq!(frame_set.chunk_box(3, 2).frames(), &[7, 8]);
+    }
+
+    #[test]
+    fn test_to_spec_string() {
+        let spec = "1,5,10-20,30-40x2";
+        let frame_set = FrameSetImpl::from_spec(spec).unwrap();
+        assert_eq!(frame_set.to_spec_string(), spec);
+    }
+}
@@ -22,6 +22,7 @@
 #include <iostream>
 #include <string>
 #include <opencompgraph/graph.h>
+#include <opencompgraph/internal/graph_observer.h>
 #include <opencompgraph/node.h>
 #include <opencompgraph/stream.h>
 
@@ -41,6 +42,14 @@ ExecuteStatus Graph::execute_status() const noexcept {
     return this->inner.inner->execute_status();
 }
 
+ProxyScale Graph::proxy_scale() const noexcept {
+    return this->inner.inner->proxy_scale();
+}
+
+void Graph::set_proxy_scale(ProxyScale proxy_scale) noexcept {
+    this->inner.inner->set_proxy_scale(proxy_scale);
+}
+
 Node Graph::create_node(NodeType node_type) noexcept {
     auto id = internal::generate_random_id();
     auto node = Node(node_type, id);
@@ -113,6 +122,22 @@ NodeStatus Graph::node_status(const Node &node) const noexcept {
     return this->inner.inner->node_status(node_id);
 }
 
+NodeErrorKind Graph::node_error_kind(const Node &node) const noexcept {
+    auto node_id = node.get_id();
+    return this->inner.inner->node_error_kind(node_id);
+}
+
+std::string Graph::node_status_message(const Node &node) const noexcept {
+    auto node_id = node.get_id();
+    auto rust_string = this->inner.inner->node_status_message(node_id);
+    return std::string(rust_string);
+}
+
+uint64_t Graph::node_hash(const Node &node, double frame) const noexcept {
+    auto node_id = node.get_id();
+    return this->inner.inner->node_hash(node_id, frame);
+}
+
 bool Graph::node_exists(const Node &node) noexcept {
     auto node_id = node.get_id();
     return this->inner.inner->node_exists(node_id);
@@ -129,6 +154,38 @@ void Graph::connect(const Node& src_node, const Node& dst_node, uint8_t input_nu
     this->inner.inner->connect(src_node_id, dst_node_id, input_num);
 }
 
+uint64_t Graph::add_observer(std::shared_ptr<GraphObserver> observer) noexcept {
+    auto handle = std::make_unique<internal::GraphObserverHandle>(std::move(observer));
+    return this->inner.inner->add_observer(std::move(handle));
+}
+
+bool Graph::remove_observer(uint64_t observer_id) noexcept {
+    return this->inner.inner->remove_observer(observer_id);
+}
+
+rust::Vec<ValidationIssue> Graph::validate(const Node &node,
+                                          std::vector<int32_t> &frames) const noexcept {
+    std::vector<double> float_frames;
+    float_frames.reserve(frames.size());
+    for (uint32_t i = 0; i < frames.size(); ++i) {
+        float_frames.push_back(static_cast<double>(frames[i]));
+    }
+    return Graph::validate(node, float_frames);
+}
+
+rust::Vec<ValidationIssue> Graph::validate(const Node &node,
+                                          std::vector<double> &frames) const noexcept {
+    auto node_id = node.get_id();
+    rust::Slice<const double> slice_frames{frames.data(), frames.size()};
+    return this->inner.inner->validate(node_id, slice_frames);
+}
+
+rust::Vec<ValidationIssue> Graph::validate(const Node &node,
+                                          const FrameSet &frames) const noexcept {
+    auto float_frames = frames.frames_f64();
+    return Graph::validate(node, float_frames);
+}
+
 ExecuteStatus Graph::execute(const Node &node,
                              std::vector<int32_t> &frames,
                              std::shared_ptr<Cache> &cache) noexcept {
@@ -148,10 +205,55 @@ ExecuteStatus Graph::execute(const Node &node,
                              std::shared_ptr<Cache> &cache) noexcept {
     auto node_id = node.get_id();
     rust::Slice<const double> slice_frames{frames.data(), frames.size()};
-    auto cache_box = cache->get_box();  // Borrow the underlying cache object.
-    auto status = this->inner.inner->execute(node_id, slice_frames, cache_box);
-    cache->set_box(std::move(cache_box));  // Return the cache to it's owner.
-    return status;
+    // The cache locks itself around each individual cache operation.
+    auto &cache_box = cache->get_box();
+    return this->inner.inner->execute(node_id, slice_frames, cache_box);
+}
+
+ExecuteStatus Graph::execute(const Node &node,
+                             const FrameSet &frames,
+                             std::shared_ptr<Cache> &cache) noexcept {
+    auto float_frames = frames.frames_f64();
+    return Graph::execute(
+        node,
+        float_frames,
+        cache);
+}
+
+rust::Vec<CachedFrame> Graph::cached_frames(const Node &node,
+                                           std::vector<int32_t> &frames,
+                                           std::shared_ptr<Cache> &cache) const noexcept {
+    std::vector<double> float_frames;
+    float_frames.reserve(frames.size());
+    for (uint32_t i = 0; i < frames.size(); ++i) {
+        float_frames.push_back(static_cast<double>(frames[i]));
+    }
+    return Graph::cached_frames(node, float_frames, cache);
+}
+
+rust::Vec<CachedFrame> Graph::cached_frames(const Node &node,
+                                           std::vector<double> &frames,
+                                           std::shared_ptr<Cache> &cache) const noexcept {
+    auto node_id = node.get_id();
+    rust::Slice<const double> slice_frames{frames.data(), frames.size()};
+    auto &cache_box = cache->get_box();
+    return this->inner.inner->cached_frames(node_id, slice_frames, cache_box);
+}
+
+rust::Vec<CachedFrame> Graph::cached_frames(const Node &node,
+                                           const FrameSet &frames,
+                                           std::shared_ptr<Cache> &cache) const noexcept {
+    auto float_frames = frames.frames_f64();
+    return Graph::cached_frames(node, float_frames, cache);
+}
+
+rust::Vec<PrefetchRequest> Graph::prefetch_requests(const Node &node,
+                                                    std::vector<double> &frames,
+                                                    std::shared_ptr<Cache> &cache) const noexcept {
+    auto node_id = node.get_id();
+    rust::Slice<const double> slice_frames{frames.data(), frames.size()};
+    auto &cache_box = cache->get_box();
+    return this->inner.inner->prefetch_requests(node_id, slice_frames, cache_box);
 }
 
 std::string Graph::data_debug_string() const noexcept {
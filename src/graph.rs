@@ -20,22 +20,32 @@
  */
 
 use anyhow::Result;
+use cxx::UniquePtr;
 use log::{debug, error, warn};
 use petgraph;
 use petgraph::dot::{Config, Dot};
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
 use rustc_hash::FxHashMap;
+use rustc_hash::FxHashSet;
 use std::collections::VecDeque;
 use std::rc::Rc;
 use std::time::Instant;
 
 use crate::cache::CacheImpl;
 use crate::cxxbridge::ffi::AttrState;
+use crate::cxxbridge::ffi::CachedFrame;
 use crate::cxxbridge::ffi::ExecuteStatus;
+use crate::cxxbridge::ffi::GraphEvent;
+use crate::cxxbridge::ffi::GraphObserverHandle;
 use crate::cxxbridge::ffi::GraphState;
+use crate::cxxbridge::ffi::NodeErrorKind;
 use crate::cxxbridge::ffi::NodeStatus;
+use crate::cxxbridge::ffi::PrefetchRequest;
+use crate::cxxbridge::ffi::ProxyScale;
 use crate::cxxbridge::ffi::StreamDataImplShared;
+use crate::cxxbridge::ffi::ValidationIssue;
+use crate::cxxbridge::ffi::ValidationIssueKind;
 use crate::data::EdgeWeight;
 use crate::data::ErrorCode;
 use crate::data::FrameValue;
@@ -46,6 +56,7 @@ use crate::data::NodeComputeMode;
 use crate::data::NodeIdx;
 use crate::data::NodeWeight;
 use crate::graphiter::UpstreamEvalSearch;
+use crate::graphobserver::GraphObservers;
 use crate::node::NodeImpl;
 use crate::stream::StreamDataImpl;
 use crate::stream::StreamDataImplRc;
@@ -61,6 +72,8 @@ pub struct GraphImpl {
     output: Rc<StreamDataImpl>,
     state: GraphState,
     status: ExecuteStatus,
+    observers: GraphObservers,
+    proxy_scale: ProxyScale,
 }
 
 impl GraphImpl {
@@ -72,6 +85,8 @@ impl GraphImpl {
         let output = Rc::new(StreamDataImpl::new());
         let state = GraphState::Uninitialized;
         let status = ExecuteStatus::Uninitialized;
+        let observers = GraphObservers::new();
+        let proxy_scale = ProxyScale::Full;
         GraphImpl {
             nodes,
             ids,
@@ -79,6 +94,8 @@ impl GraphImpl {
             output,
             state,
             status,
+            observers,
+            proxy_scale,
         }
     }
 
@@ -92,6 +109,29 @@ impl GraphImpl {
         self.status
     }
 
+    /// The resolution images are read at, for fast review.
+    pub fn proxy_scale(&self) -> ProxyScale {
+        self.proxy_scale
+    }
+
+    /// Set the resolution images are read at.
+    ///
+    /// ReadImage nodes use the lowest resolution of the graph's
+    /// proxy scale and their own 'proxy_scale' attribute.
+    /// Pixel-space attributes of downstream nodes (such as crop
+    /// windows and translations) are scaled to match, so the graph
+    /// looks the same at any proxy scale.
+    pub fn set_proxy_scale(&mut self, proxy_scale: ProxyScale) {
+        if proxy_scale == ProxyScale::Uninitialized {
+            warn!("Invalid proxy scale: {:?}", proxy_scale);
+            return;
+        }
+        if proxy_scale != self.proxy_scale {
+            self.proxy_scale = proxy_scale;
+            self.state = GraphState::Dirty;
+        }
+    }
+
     /// Add a new node to the graph.
     pub fn add_node(&mut self, node_box: Box<NodeImpl>) -> usize {
         let id = node_box.get_id();
@@ -103,6 +143,7 @@ impl GraphImpl {
         let index = self.graph.add_node(id).index();
         debug!("Add Node index={} id={}", index, id);
         assert_eq!(index, nodes_index);
+        self.observers.notify(&GraphEvent::node_added(id));
         index
     }
 
@@ -118,9 +159,30 @@ impl GraphImpl {
         };
         // TODO: Zero out the now empty fields.
         let node_index = petgraph::graph::NodeIndex::new(node_idx);
+
+        // The edges connected to the node are removed with it.
+        let removed_edges: Vec<_> = self
+            .graph
+            .edges_directed(node_index, Direction::Incoming)
+            .chain(self.graph.edges_directed(node_index, Direction::Outgoing))
+            .map(|x| {
+                let src_node_id = self.ids[x.source().index()];
+                let dst_node_id = self.ids[x.target().index()];
+                (src_node_id, dst_node_id, *x.weight())
+            })
+            .collect();
+
         match self.graph.remove_node(node_index) {
             Some(_value) => {
                 self.state = GraphState::Dirty;
+                for (src_node_id, dst_node_id, input_num) in removed_edges {
+                    self.observers.notify(&GraphEvent::disconnected(
+                        src_node_id,
+                        dst_node_id,
+                        input_num,
+                    ));
+                }
+                self.observers.notify(&GraphEvent::node_removed(node_id));
                 true
             }
             None => false,
@@ -161,6 +223,10 @@ impl GraphImpl {
         // successful (there is no way to confirm this currently.)
         self.state = GraphState::Dirty;
         node_box.set_attr_str(name, value);
+        if let AttrState::Exists = node_box.attr_exists(name) {
+            self.observers
+                .notify(&GraphEvent::attr_changed(node_id, name));
+        }
     }
 
     pub fn get_node_attr_i32(&self, node_id: Identifier, name: &str) -> i32 {
@@ -186,6 +252,10 @@ impl GraphImpl {
         // successful (there is no way to confirm this currently.)
         self.state = GraphState::Dirty;
         node_box.set_attr_i32(name, value);
+        if let AttrState::Exists = node_box.attr_exists(name) {
+            self.observers
+                .notify(&GraphEvent::attr_changed(node_id, name));
+        }
     }
 
     pub fn get_node_attr_f32(&self, node_id: Identifier, name: &str) -> f32 {
@@ -211,6 +281,10 @@ impl GraphImpl {
         // successful (there is no way to confirm this currently.)
         self.state = GraphState::Dirty;
         node_box.set_attr_f32(name, value);
+        if let AttrState::Exists = node_box.attr_exists(name) {
+            self.observers
+                .notify(&GraphEvent::attr_changed(node_id, name));
+        }
     }
 
     pub fn node_status(&self, node_id: Identifier) -> NodeStatus {
@@ -224,6 +298,50 @@ impl GraphImpl {
         node_box.get_status()
     }
 
+    /// The reason for the node's last Warning or Error status.
+    pub fn node_error_kind(&self, node_id: Identifier) -> NodeErrorKind {
+        let node_box = match self.find_node_index_from_id(node_id) {
+            Some(value) => &self.nodes[value],
+            None => {
+                warn!("Node id not found: id={}", node_id);
+                return NodeErrorKind::Uninitialized;
+            }
+        };
+        node_box.get_error_kind()
+    }
+
+    /// A human-readable message explaining the node's last Warning
+    /// or Error status. The message is empty if there is no problem.
+    pub fn node_status_message(&self, node_id: Identifier) -> String {
+        let node_box = match self.find_node_index_from_id(node_id) {
+            Some(value) => &self.nodes[value],
+            None => {
+                warn!("Node id not found: id={}", node_id);
+                return format!("node not found: id={}", node_id);
+            }
+        };
+        node_box.get_status_message().to_string()
+    }
+
+    /// The hash value of the node at 'frame', including all upstream
+    /// nodes. This is the key used to store the node's output in the
+    /// cache, and is stable across processes and platforms.
+    pub fn node_hash(&self, node_id: Identifier, frame: FrameValue) -> HashValue {
+        let node_idx = match self.find_node_index_from_id(node_id) {
+            Some(value) => value,
+            None => {
+                warn!("Node id not found: id={}", node_id);
+                return 0;
+            }
+        };
+        let node_indexes = self.find_all_upstream_nodes(node_idx);
+        let hash_cache = self.compute_hash_values(&node_indexes, frame);
+        match hash_cache.get(&node_idx) {
+            Some(value) => *value,
+            None => 0,
+        }
+    }
+
     fn node_attrs_data_debug_string(&self, node_id: Identifier) -> String {
         let node_box = match self.find_node_index_from_id(node_id) {
             Some(value) => &self.nodes[value],
@@ -291,7 +409,12 @@ impl GraphImpl {
                 );
                 self.graph.remove_edge(edge_index);
                 self.state = GraphState::Dirty;
-                ()
+                let src_node_id = self.ids[edge_src_index.index()];
+                self.observers.notify(&GraphEvent::disconnected(
+                    src_node_id,
+                    dst_node_id,
+                    input_num,
+                ));
             }
         }
     }
@@ -329,6 +452,22 @@ impl GraphImpl {
 
         self.graph.update_edge(src_index, dst_index, input_num);
         self.state = GraphState::Dirty;
+        self.observers
+            .notify(&GraphEvent::connected(src_node_id, dst_node_id, input_num));
+    }
+
+    /// Add an observer, to be notified when nodes are added or
+    /// removed, connected or disconnected, or when node attributes
+    /// are changed.
+    ///
+    /// Returns an id used to remove the observer.
+    pub fn add_observer(&mut self, observer: UniquePtr<GraphObserverHandle>) -> u64 {
+        self.observers.add(observer)
+    }
+
+    /// Remove an observer previously added with 'add_observer'.
+    pub fn remove_observer(&mut self, observer_id: u64) -> bool {
+        self.observers.remove(observer_id)
     }
 
     // Get the stack of indices to be computed, going upstream
@@ -387,6 +526,30 @@ impl GraphImpl {
         Ok(inputs)
     }
 
+    /// Evaluate the first upstream input of the node at each of the
+    /// given frames, returning one input stream per frame.
+    fn compute_node_input_stream_data_at_frames(
+        &mut self,
+        node_idx: NodeIdx,
+        frames: &[FrameValue],
+        cache: &Box<CacheImpl>,
+    ) -> Result<Vec<Rc<StreamDataImpl>>, ErrorCode> {
+        let mut inputs = Vec::<Rc<StreamDataImpl>>::new();
+
+        let parent_node_indexes = self.find_direct_upstream_nodes(node_idx.index());
+        let parent_node_index = match parent_node_indexes.first() {
+            Some(value) => *value,
+            None => return Ok(inputs),
+        };
+        let input_node_indexes = self.find_all_upstream_nodes(parent_node_index.index());
+        for frame in frames {
+            debug!("Execute Input Frame: {}", *frame);
+            self.execute_frame(&input_node_indexes, *frame, cache)?;
+            inputs.push(self.output.clone());
+        }
+        Ok(inputs)
+    }
+
     /// Get upstream parent inputs (so we can calculate the node
     /// hash).
     fn compute_node_input_hash_values(
@@ -411,6 +574,53 @@ impl GraphImpl {
         input_hash_values
     }
 
+    /// Get the hash of the (first) upstream parent at each of the
+    /// 'frames', the same inputs as
+    /// 'compute_node_input_stream_data_at_frames'.
+    fn compute_node_input_hash_values_at_frames(
+        &self,
+        node_idx: NodeIdx,
+        frames: &[FrameValue],
+    ) -> Vec<HashValue> {
+        let parent_node_indexes = self.find_direct_upstream_nodes(node_idx.index());
+        let parent_node_index = match parent_node_indexes.first() {
+            Some(value) => *value,
+            None => return Vec::new(),
+        };
+        let input_node_indexes = self.find_all_upstream_nodes(parent_node_index.index());
+        frames
+            .iter()
+            .map(|frame| {
+                let hash_cache = self.compute_hash_values(&input_node_indexes, *frame);
+                hash_cache[&parent_node_index.index()]
+            })
+            .collect()
+    }
+
+    /// Compute all hash values for each node in the entire connected
+    /// graph.
+    fn compute_hash_values(
+        &self,
+        node_indexes: &[NodeIdx],
+        frame: FrameValue,
+    ) -> FxHashMap<GraphIdx, HashValue> {
+        // Start at upstream nodes and move down toward main node.
+        let mut hash_cache = FxHashMap::<GraphIdx, HashValue>::default();
+        for node_index in node_indexes.iter().rev() {
+            debug!("Compute Node Hash: {:?}", node_index);
+            let node = &self.nodes[node_index.index()];
+            let input_hash_values = match node.hash_input_frames(frame) {
+                None => self.compute_node_input_hash_values(*node_index, &hash_cache),
+                Some(input_frames) => {
+                    self.compute_node_input_hash_values_at_frames(*node_index, &input_frames)
+                }
+            };
+            let hash_value = node.hash(frame, self.proxy_scale, &input_hash_values);
+            hash_cache.insert(node_index.index(), hash_value);
+        }
+        hash_cache
+    }
+
     /// Compute the node.
     fn compute_node_output(
         &mut self,
@@ -419,17 +629,12 @@ impl GraphImpl {
         // Frame is floating point so we can evaluate sub-frames and
         // frame blending.
         frame: FrameValue,
+        hash_value: HashValue,
         node_compute_mode: NodeComputeMode,
-        cache: &mut Box<CacheImpl>,
-        stream_data_cache: &mut FxHashMap<GraphIdx, Rc<StreamDataImpl>>,
+        cache: &Box<CacheImpl>,
     ) -> Result<(), ErrorCode> {
         let node = &mut self.nodes[node_index];
 
-        // TODO: Store the amount of time the node took to compute,
-        // and how much (new) memory it requires. Then use the
-        // meta-information to weight the importance of the item in
-        // the cache.
-
         // TODO: The node compute should be split into multiple
         // methods.
         //
@@ -449,11 +654,16 @@ impl GraphImpl {
         // Both 'B' and 'C' expect 'A' to have already been called so
         // that any data structures are valid and up-to-date.
 
-        match node.compute(frame, node_compute_mode, &inputs, &mut self.output, cache) {
-            NodeStatus::Valid | NodeStatus::Warning => {
-                stream_data_cache.insert(node_index, self.output.clone());
-                Ok(())
-            }
+        match node.compute(
+            frame,
+            self.proxy_scale,
+            hash_value,
+            node_compute_mode,
+            &inputs,
+            &mut self.output,
+            cache,
+        ) {
+            NodeStatus::Valid | NodeStatus::Warning => Ok(()),
             NodeStatus::Uninitialized => {
                 error!("Node is uninitialized: node_index={}", node_index);
                 Err(ErrorCode::Uninitialized)
@@ -473,26 +683,12 @@ impl GraphImpl {
         &mut self,
         node_indexes: &Vec<NodeIdx>,
         frame: FrameValue,
-        cache: &mut Box<CacheImpl>,
+        cache: &Box<CacheImpl>,
     ) -> Result<(), ErrorCode> {
         debug!("Execute Frame Context: {}", frame);
         let start = Instant::now();
 
-        // Compute all hash values for each node in the entire
-        // connected graph.
-        //
-        // Start at upstream nodes and move down toward main node.
-        let mut hash_cache = FxHashMap::<GraphIdx, HashValue>::default();
-        let mut hash_values = Vec::new();
-        for node_index in node_indexes.iter().rev() {
-            debug!("Compute Node Hash: {:?}", node_index);
-            let node = &self.nodes[node_index.index()];
-            let input_hash_values =
-                self.compute_node_input_hash_values(*node_index, &mut hash_cache);
-            let hash_value = node.hash(frame, &input_hash_values);
-            hash_cache.insert(node_index.index(), hash_value);
-            hash_values.push(hash_value);
-        }
+        let hash_cache = self.compute_hash_values(node_indexes, frame);
 
         // Validate node, start at node and walk up the graph.
         let mut validated_node_indexes = Vec::new();
@@ -530,16 +726,28 @@ impl GraphImpl {
         for (node_index, node_compute_mode) in validated_node_indexes.iter().rev() {
             debug!("Compute Node: {:?}", node_index);
 
-            let node_inputs =
-                self.compute_node_input_stream_data(*node_index, &stream_data_cache)?;
+            // Nodes may need their input evaluated at different
+            // frames, such as sub-frames for frame blending.
+            let hash_value = hash_cache.get(&node_index.index()).unwrap();
+            let input_frames =
+                self.nodes[node_index.index()].input_frames(frame, *hash_value, cache);
+            let node_inputs = match input_frames {
+                None => self.compute_node_input_stream_data(*node_index, &stream_data_cache)?,
+                Some(input_frames) => self.compute_node_input_stream_data_at_frames(
+                    *node_index,
+                    &input_frames,
+                    cache,
+                )?,
+            };
             self.compute_node_output(
                 &node_inputs,
                 node_index.index(),
                 frame,
+                *hash_value,
                 *node_compute_mode,
                 cache,
-                &mut stream_data_cache,
             )?;
+            stream_data_cache.insert(node_index.index(), self.output.clone());
         }
         let duration = start.elapsed();
         debug!("Frame Execute {} total time: {:?}", frame, duration);
@@ -547,6 +755,51 @@ impl GraphImpl {
         Ok(())
     }
 
+    /// Check the network upstream of the node for problems, without
+    /// computing any pixels.
+    ///
+    /// The frames are used to check that file paths exist for each
+    /// frame that would be executed.
+    pub fn validate(&self, node_id: Identifier, frames: &[FrameValue]) -> Vec<ValidationIssue> {
+        debug!("Validate: {}", node_id);
+        let mut issues = Vec::new();
+
+        let start_node_idx = match self.find_node_index_from_id(node_id) {
+            Some(value) => value,
+            None => {
+                issues.push(ValidationIssue::new(
+                    node_id,
+                    ValidationIssueKind::NodeNotFound,
+                    format!("node not found: id={}", node_id),
+                ));
+                return issues;
+            }
+        };
+        let node_indexes = self.find_all_upstream_nodes(start_node_idx);
+
+        // Start at upstream nodes, so the number of channels from
+        // each input is known before the downstream node is checked.
+        let mut num_channels_cache = FxHashMap::<GraphIdx, Option<i32>>::default();
+        for node_index in node_indexes.iter().rev() {
+            if num_channels_cache.contains_key(&node_index.index()) {
+                // The node is upstream of more than one node, and has
+                // already been checked.
+                continue;
+            }
+            let input_num_channels: Vec<Option<i32>> = self
+                .find_direct_upstream_nodes(node_index.index())
+                .iter()
+                .map(|x| num_channels_cache.get(&x.index()).copied().flatten())
+                .collect();
+
+            let node = &self.nodes[node_index.index()];
+            let num_channels = node.validate(frames, &input_num_channels, &mut issues);
+            num_channels_cache.insert(node_index.index(), num_channels);
+        }
+        debug!("Validate found {} issue(s)", issues.len());
+        issues
+    }
+
     /// Compute the graph!
     //
     // TODO: Add an "executor" variable to this method to as the
@@ -562,7 +815,7 @@ impl GraphImpl {
         &mut self,
         start_node_id: u64,
         frames: &[FrameValue],
-        cache: &mut Box<CacheImpl>,
+        cache: &Box<CacheImpl>,
     ) -> ExecuteStatus {
         debug!("Execute: {}", start_node_id);
         let start = Instant::now();
@@ -609,6 +862,89 @@ impl GraphImpl {
         self.status
     }
 
+    /// Which of the 'frames' of the node are stored in the cache.
+    ///
+    /// Only the hash values are computed, the graph is not executed
+    /// and the cache is not modified. Nodes that do not store their
+    /// output in the cache are never reported as cached.
+    pub fn cached_frames(
+        &self,
+        node_id: Identifier,
+        frames: &[FrameValue],
+        cache: &Box<CacheImpl>,
+    ) -> Vec<CachedFrame> {
+        let node_idx = match self.find_node_index_from_id(node_id) {
+            Some(value) => value,
+            None => {
+                warn!("Node id not found: id={}", node_id);
+                return Vec::new();
+            }
+        };
+        let node_indexes = self.find_all_upstream_nodes(node_idx);
+        frames
+            .iter()
+            .map(|frame| {
+                let hash_cache = self.compute_hash_values(&node_indexes, *frame);
+                let cached = match hash_cache.get(&node_idx) {
+                    Some(hash_value) => cache.contains(*hash_value),
+                    None => false,
+                };
+                CachedFrame {
+                    frame: *frame,
+                    cached,
+                }
+            })
+            .collect()
+    }
+
+    /// The images that can be read ahead of time, before the node is
+    /// computed at each of the 'frames' (in the order given).
+    ///
+    /// Only upstream nodes that read files (such as ReadImage) can be
+    /// prefetched, and images already in the cache are skipped.
+    pub fn prefetch_requests(
+        &self,
+        node_id: Identifier,
+        frames: &[FrameValue],
+        cache: &Box<CacheImpl>,
+    ) -> Vec<PrefetchRequest> {
+        let node_idx = match self.find_node_index_from_id(node_id) {
+            Some(value) => value,
+            None => {
+                warn!("Node id not found: id={}", node_id);
+                return Vec::new();
+            }
+        };
+        let node_indexes = self.find_all_upstream_nodes(node_idx);
+        let mut keys = FxHashSet::<HashValue>::default();
+        let mut requests = Vec::new();
+        for frame in frames {
+            let hash_cache = self.compute_hash_values(&node_indexes, *frame);
+            for node_index in &node_indexes {
+                let node = &self.nodes[node_index.index()];
+                let (path, proxy_scale) = match node.prefetch_path(*frame, self.proxy_scale) {
+                    Some(value) => value,
+                    None => continue,
+                };
+                let key = match hash_cache.get(&node_index.index()) {
+                    Some(value) => *value,
+                    None => continue,
+                };
+                if cache.contains(key) || !keys.insert(key) {
+                    continue;
+                }
+                requests.push(PrefetchRequest {
+                    key,
+                    node_id: node.get_id(),
+                    frame: *frame,
+                    file_path: path.to_string_lossy().to_string(),
+                    proxy_scale,
+                });
+            }
+        }
+        requests
+    }
+
     /// Convert the graph into a human-readable string, for debug
     /// printing.
     pub fn data_debug_string(&self) -> String {
@@ -619,10 +955,11 @@ impl GraphImpl {
         );
         for (i, id) in self.ids.iter().enumerate() {
             let node_status = self.node_status(*id);
+            let node_status_message = self.node_status_message(*id);
             let attrs = self.node_attrs_data_debug_string(*id);
             let line = format!(
-                "index={} id={} status={:?} attrs={}\n",
-                i, *id, node_status, attrs
+                "index={} id={} status={:?} message={:?} attrs={}\n",
+                i, *id, node_status, node_status_message, attrs
             );
             string.push_str(line.as_str());
         }
@@ -0,0 +1,44 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+#include <opencompgraph/graph_observer.h>
+#include <opencompgraph/internal/graph_observer.h>
+
+namespace open_comp_graph {
+
+GraphObserver::~GraphObserver() {}
+
+namespace internal {
+
+GraphObserverHandle::GraphObserverHandle(std::shared_ptr<GraphObserver> observer)
+        : m_observer{std::move(observer)} {
+}
+
+GraphObserverHandle::~GraphObserverHandle() {}
+
+void GraphObserverHandle::notify(const GraphEvent &event) {
+    if (m_observer) {
+        m_observer->notify(event);
+    }
+}
+
+} // namespace internal
+} // namespace open_comp_graph
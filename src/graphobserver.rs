@@ -0,0 +1,182 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+use cxx::UniquePtr;
+use log::debug;
+use std::fmt;
+
+use crate::cxxbridge::ffi::GraphEvent;
+use crate::cxxbridge::ffi::GraphEventKind;
+use crate::cxxbridge::ffi::GraphObserverHandle;
+use crate::data::Identifier;
+
+impl GraphEvent {
+    fn new(
+        kind: GraphEventKind,
+        node_id: Identifier,
+        src_node_id: Identifier,
+        input_num: u8,
+        attr_name: &str,
+    ) -> GraphEvent {
+        GraphEvent {
+            kind,
+            node_id,
+            src_node_id,
+            input_num,
+            attr_name: attr_name.to_string(),
+        }
+    }
+
+    pub fn node_added(node_id: Identifier) -> GraphEvent {
+        GraphEvent::new(GraphEventKind::NodeAdded, node_id, 0, 0, "")
+    }
+
+    pub fn node_removed(node_id: Identifier) -> GraphEvent {
+        GraphEvent::new(GraphEventKind::NodeRemoved, node_id, 0, 0, "")
+    }
+
+    /// The source node was connected to the destination node's
+    /// input.
+    pub fn connected(
+        src_node_id: Identifier,
+        dst_node_id: Identifier,
+        input_num: u8,
+    ) -> GraphEvent {
+        GraphEvent::new(
+            GraphEventKind::Connected,
+            dst_node_id,
+            src_node_id,
+            input_num,
+            "",
+        )
+    }
+
+    /// The source node was disconnected from the destination node's
+    /// input.
+    pub fn disconnected(
+        src_node_id: Identifier,
+        dst_node_id: Identifier,
+        input_num: u8,
+    ) -> GraphEvent {
+        GraphEvent::new(
+            GraphEventKind::Disconnected,
+            dst_node_id,
+            src_node_id,
+            input_num,
+            "",
+        )
+    }
+
+    pub fn attr_changed(node_id: Identifier, attr_name: &str) -> GraphEvent {
+        GraphEvent::new(GraphEventKind::AttrChanged, node_id, 0, 0, attr_name)
+    }
+
+    pub fn kind(&self) -> GraphEventKind {
+        self.kind
+    }
+
+    pub fn node_id(&self) -> Identifier {
+        self.node_id
+    }
+
+    pub fn src_node_id(&self) -> Identifier {
+        self.src_node_id
+    }
+
+    pub fn input_num(&self) -> u8 {
+        self.input_num
+    }
+
+    pub fn attr_name(&self) -> &str {
+        &self.attr_name
+    }
+}
+
+/// The observers registered on a graph, notified each time the graph
+/// is edited.
+///
+/// Observers are called while the graph is being edited, so an
+/// observer must not call back into the same graph.
+pub struct GraphObservers {
+    next_id: u64,
+    // Boxed so the list stays Unpin; the C++ observer type is not.
+    observers: Vec<(u64, Box<UniquePtr<GraphObserverHandle>>)>,
+}
+
+impl fmt::Debug for GraphObservers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphObservers")
+            .field("count", &self.observers.len())
+            .finish()
+    }
+}
+
+impl GraphObservers {
+    pub fn new() -> GraphObservers {
+        GraphObservers {
+            next_id: 1,
+            observers: Vec::new(),
+        }
+    }
+
+    /// Add an observer, returning an id used to remove the observer
+    /// later.
+    pub fn add(&mut self, observer: UniquePtr<GraphObserverHandle>) -> u64 {
+        let observer_id = self.next_id;
+        self.next_id += 1;
+        debug!("Add Graph Observer id={}", observer_id);
+        self.observers.push((observer_id, Box::new(observer)));
+        observer_id
+    }
+
+    /// Remove the observer with the given id. Returns false if the
+    /// observer was not found.
+    pub fn remove(&mut self, observer_id: u64) -> bool {
+        debug!("Remove Graph Observer id={}", observer_id);
+        let count = self.observers.len();
+        self.observers.retain(|(id, _)| *id != observer_id);
+        self.observers.len() != count
+    }
+
+    pub fn len(&self) -> usize {
+        self.observers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+
+    /// Send the event to all observers, in the order they were added.
+    pub fn notify(&mut self, event: &GraphEvent) {
+        debug!("Graph Event: {:?}", event);
+        for (_id, observer) in self.observers.iter_mut() {
+            if let Some(observer) = (**observer).as_mut() {
+                observer.notify(event);
+            }
+        }
+    }
+}
+
+impl Default for GraphObservers {
+    fn default() -> GraphObservers {
+        GraphObservers::new()
+    }
+}
@@ -21,14 +21,108 @@
 
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
-use rustc_hash::FxHasher;
 use std::hash::{Hash, Hasher};
 use std::mem;
 
 use crate::data::HashValue;
 
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A hasher with a fixed, documented algorithm, used for all
+/// `HashValue`s.
+///
+/// The algorithm is 64-bit FNV-1a. Integers are fed to the hasher
+/// as little-endian bytes, and `usize`/`isize` are widened to 64-bit,
+/// so the same inputs give the same hash value on every platform,
+/// process and Rust version. This allows hash values to be used as
+/// keys for caches that outlive the process (such as the disk
+/// cache).
+///
+/// Changing this algorithm (or how any type feeds itself to the
+/// hasher) invalidates all persistent caches.
+#[derive(Debug, Copy, Clone)]
+pub struct StableHasher {
+    state: u64,
+}
+
+impl StableHasher {
+    pub fn new() -> StableHasher {
+        StableHasher {
+            state: FNV_OFFSET_BASIS,
+        }
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> StableHasher {
+        StableHasher::new()
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= *byte as u64;
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.write(&[i]);
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i8(&mut self, i: i8) {
+        self.write_u8(i as u8);
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+}
+
 pub fn calculate_hash<T: Hash>(t: &T) -> HashValue {
-    let mut s = FxHasher::default();
+    let mut s = StableHasher::new();
     t.hash(&mut s);
     s.finish()
 }
@@ -91,3 +185,89 @@ impl HashableF32 {
         HashableF32(integer_decode_f32(val))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cxxbridge::ffi::NodeType;
+    use crate::cxxbridge::ffi::ProxyScale;
+    use crate::node::create_node_box_with_id;
+
+    // Known-answer values for 64-bit FNV-1a.
+    #[test]
+    fn test_stable_hasher_known_values() {
+        let mut state = StableHasher::new();
+        state.write(b"");
+        assert_eq!(state.finish(), 0xcbf29ce484222325);
+
+        let mut state = StableHasher::new();
+        state.write(b"a");
+        assert_eq!(state.finish(), 0xaf63dc4c8601ec8c);
+
+        let mut state = StableHasher::new();
+        state.write(b"foobar");
+        assert_eq!(state.finish(), 0x85944171f73967e8);
+    }
+
+    #[test]
+    fn test_stable_hasher_little_endian() {
+        let mut state_a = StableHasher::new();
+        state_a.write_u32(1);
+        let mut state_b = StableHasher::new();
+        state_b.write(&[1, 0, 0, 0]);
+        assert_eq!(state_a.finish(), state_b.finish());
+
+        let mut state_a = StableHasher::new();
+        state_a.write_usize(42);
+        let mut state_b = StableHasher::new();
+        state_b.write_u64(42);
+        assert_eq!(state_a.finish(), state_b.finish());
+    }
+
+    #[test]
+    fn test_calculate_hash_pinned() {
+        assert_eq!(generate_id_from_name("read"), 9887152648366607054);
+        assert_eq!(calculate_hash(&HashableF32::new(0.5)), 10739076388119865083);
+    }
+
+    // These values must only change when the hashing of a node
+    // deliberately changes; any change invalidates persistent caches.
+    //
+    // The node hashes are chained the same way as
+    // 'GraphImpl::execute', for the graph "ReadImage -> Grade -> Null".
+    #[test]
+    fn test_graph_hash_pinned() {
+        let mut read_node = create_node_box_with_id(NodeType::ReadImage, 1);
+        let mut grade_node = create_node_box_with_id(NodeType::Grade, 2);
+        let null_node = create_node_box_with_id(NodeType::Null, 3);
+        read_node.set_attr_str("file_path", "/plates/shot.####.exr");
+        grade_node.set_attr_f32("multiply_r", 2.0);
+
+        let read_hash = read_node.hash(1001.0, ProxyScale::Full, &vec![]);
+        let grade_hash = grade_node.hash(1001.0, ProxyScale::Full, &vec![read_hash]);
+        let null_hash = null_node.hash(1001.0, ProxyScale::Full, &vec![grade_hash]);
+        assert_eq!(read_hash, 1981810848794306792);
+        assert_eq!(grade_hash, 15227349724752346023);
+        assert_eq!(null_hash, 8490634344478651683);
+
+        // The ReadImage path changes with the frame number.
+        assert_eq!(
+            read_node.hash(1002.0, ProxyScale::Full, &vec![]),
+            6729946430601655427
+        );
+
+        // Reading at a proxy scale changes the ReadImage hash (and
+        // so every downstream node), full resolution does not.
+        let read_proxy_hash = read_node.hash(1001.0, ProxyScale::Half, &vec![]);
+        assert_ne!(read_proxy_hash, read_hash);
+        read_node.set_attr_i32("proxy_scale", 1);
+        assert_eq!(
+            read_node.hash(1001.0, ProxyScale::Full, &vec![]),
+            read_proxy_hash
+        );
+        assert_eq!(
+            read_node.hash(1001.0, ProxyScale::Half, &vec![]),
+            read_proxy_hash
+        );
+    }
+}
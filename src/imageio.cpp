@@ -21,9 +21,11 @@
 
 // STL
 // #include <sstream>  // stringstream
+#include <algorithm>  // min
 #include <cctype>   // tolower
 #include <iostream>
 #include <memory>
+#include <vector>
 
 #include <rust/cxx.h>
 
@@ -77,7 +79,9 @@ bool oiio_read_image(const rust::String &file_path, ImageShared &image) {
             << "oiio_read_image: Cannot open image with less than 3 channels (RGB).\n";
         return false;
     }
-    std::max(num_channels, 4);
+    // Any channels after RGBA, such as Z or AOVs, are read as layers
+    // with 'oiio_read_image_channels'.
+    num_channels = std::min(num_channels, 4);
 
     auto pixel_data_type = oiio_format_to_ocg_format(oiio_data_type);
     auto channel_num_bytes = channel_size_bytes(pixel_data_type);
@@ -85,7 +89,9 @@ bool oiio_read_image(const rust::String &file_path, ImageShared &image) {
         static_cast<int32_t>(stride_num_channels(num_channels, pixel_data_type));
     auto pixel_size_bytes = padded_num_channels * channel_num_bytes;
 
-    bool ok = oiio_allocate_image(spec, image);
+    OIIO::ImageSpec rgba_spec = spec;
+    rgba_spec.nchannels = num_channels;
+    bool ok = oiio_allocate_image(rgba_spec, image);
 
     auto pixel_data = pixelblock_get_pixel_data_ptr_read_write(image.pixel_block);
     if (pixel_data == nullptr) {
@@ -98,21 +104,18 @@ bool oiio_read_image(const rust::String &file_path, ImageShared &image) {
     //
     // https://openimageio.readthedocs.io/en/release-2.2.8.0/stdmetadata.html#cmdoption-arg-oiio-ColorSpace
     //
-    // TODO: Add more metadata support, such as these commonly used
-    // metadata fields:
-    //
-    // "DateTime"
-    // "ImageDescription"
-    // "Artist"
-    // "DocumentName"
-    // "ResolutionUnit"
-    // "XResolution"
-    // "YResolution"
+    // Other metadata fields, such as "DateTime", "ImageDescription"
+    // or "smpte:TimeCode", are read into 'image.spec.metadata' below.
     //
     std::string colorspace_text = spec.get_string_attribute("oiio:ColorSpace", "");
     float gamma = spec.get_float_attribute("oiio:Gamma", 1.0f);
     float pixel_aspect = spec.get_float_attribute("PixelAspectRatio", 1.0f);
-    int orientation = spec.get_int_attribute("Orientation", 0);
+    // OpenImageIO orientation values start at 1 (normal), and
+    // ImageOrientation values start at 0.
+    int orientation = spec.get_int_attribute("Orientation", 1) - 1;
+    if ((orientation < 0) || (orientation > 7)) {
+        orientation = 0;
+    }
     int unassociated_alpha = spec.get_int_attribute("oiio:UnassociatedAlpha", 0);
     std::string desc_text = spec.get_string_attribute("ImageDescription", "");
     int dither = spec.get_int_attribute("oiio:dither", 0);
@@ -132,6 +135,7 @@ bool oiio_read_image(const rust::String &file_path, ImageShared &image) {
     image.spec.orientation = static_cast<ImageOrientation>(orientation);
     image.spec.unassociated_alpha = unassociated_alpha != 0;
     image.spec.dither = dither;
+    oiio_read_metadata(spec, image.spec.metadata);
 
     // // TODO: Find which channels are z-depth and which channels are
     // // alpha.
@@ -158,6 +162,151 @@ bool oiio_read_image(const rust::String &file_path, ImageShared &image) {
     return true;
 }
 
+// Read only the image header to find the number of channels; no
+// pixels are read.
+bool oiio_read_image_num_channels(const rust::String &file_path, int32_t &num_channels) {
+    auto filename = std::string(file_path);
+    auto in = OIIO::ImageInput::open(filename);
+    if (!in) {
+        std::cerr
+            << "oiio_read_image_num_channels: failed to open file name: "
+            << filename << '\n';
+        return false;
+    }
+    const OIIO::ImageSpec &spec = in->spec();
+    num_channels = spec.nchannels;
+    in->close();
+    return true;
+}
+
+// Read only the image header to find the data and display windows
+// and the orientation; no pixels are read.
+bool oiio_read_image_header(const rust::String &file_path, ImageShared &image) {
+    auto filename = std::string(file_path);
+    auto in = OIIO::ImageInput::open(filename);
+    if (!in) {
+        std::cerr
+            << "oiio_read_image_header: failed to open file name: "
+            << filename << '\n';
+        return false;
+    }
+    const OIIO::ImageSpec &spec = in->spec();
+
+    // The windows are moved so the display window starts at 0,0,
+    // the same as 'oiio_allocate_image'.
+    image.data_window.min_x = spec.x + spec.full_x;
+    image.data_window.min_y = spec.y + spec.full_y;
+    image.data_window.max_x = spec.x + spec.width + spec.full_x;
+    image.data_window.max_y = spec.y + spec.height + spec.full_y;
+    image.display_window.min_x = 0;
+    image.display_window.min_y = 0;
+    image.display_window.max_x = spec.full_width;
+    image.display_window.max_y = spec.full_height;
+
+    // OpenImageIO orientation values start at 1 (normal), and
+    // ImageOrientation values start at 0.
+    int orientation = spec.get_int_attribute("Orientation", 1) - 1;
+    if ((orientation < 0) || (orientation > 7)) {
+        orientation = 0;
+    }
+    image.spec.orientation = static_cast<ImageOrientation>(orientation);
+    in->close();
+    return true;
+}
+
+// Read only the image header to find the names of all channels; no
+// pixels are read.
+bool oiio_read_image_channel_names(const rust::String &file_path,
+                                   rust::Vec<rust::String> &channel_names) {
+    auto filename = std::string(file_path);
+    auto in = OIIO::ImageInput::open(filename);
+    if (!in) {
+        std::cerr
+            << "oiio_read_image_channel_names: failed to open file name: "
+            << filename << '\n';
+        return false;
+    }
+    const OIIO::ImageSpec &spec = in->spec();
+    for (auto &channel_name : spec.channelnames) {
+        channel_names.push_back(rust::String(channel_name));
+    }
+    in->close();
+    return true;
+}
+
+// Read the named channels of an image (such as "depth.Z") into a
+// Float32 pixel block, in the order given.
+bool oiio_read_image_channels(const rust::String &file_path,
+                              const rust::Vec<rust::String> &channel_names,
+                              ImageShared &image) {
+    auto filename = std::string(file_path);
+    auto in = OIIO::ImageInput::open(filename);
+    if (!in) {
+        std::cerr
+            << "oiio_read_image_channels: failed to open file name: "
+            << filename << '\n';
+        return false;
+    }
+    int subimage = 0;
+    int miplevel = 0;
+    const OIIO::ImageSpec &spec = in->spec();
+
+    // Find the channel index of each name.
+    std::vector<int> channel_indices;
+    for (auto &channel_name : channel_names) {
+        auto index = spec.channelindex(std::string(channel_name));
+        if (index < 0) {
+            std::cerr
+                << "oiio_read_image_channels: channel not found: "
+                << std::string(channel_name) << '\n';
+            in->close();
+            return false;
+        }
+        channel_indices.push_back(index);
+    }
+
+    OIIO::ImageSpec layer_spec = spec;
+    layer_spec.nchannels = static_cast<int>(channel_indices.size());
+    layer_spec.format = OIIO::TypeDesc::FLOAT;
+    bool ok = oiio_allocate_image(layer_spec, image);
+    auto pixel_data = pixelblock_get_pixel_data_ptr_read_write(image.pixel_block);
+    if (!ok || pixel_data == nullptr) {
+        std::cerr
+            << "oiio_read_image_channels: Failed to get pixel data pointer.\n";
+        in->close();
+        return false;
+    }
+    image.spec.color_space = rust::String(
+        spec.get_string_attribute("oiio:ColorSpace", ""));
+
+    // Read each channel into place; the channels of a layer may not
+    // be next to each other in the file.
+    auto channel_num_bytes = sizeof(float);
+    OIIO::stride_t xstride = channel_indices.size() * channel_num_bytes;
+    OIIO::stride_t ystride = xstride * spec.width;
+    OIIO::stride_t zstride = OIIO::AutoStride;
+    for (size_t i = 0; i < channel_indices.size(); ++i) {
+        int chbegin = channel_indices[i];
+        int chend = chbegin + 1;
+        auto channel_data = static_cast<uint8_t*>(pixel_data) + (i * channel_num_bytes);
+        ok = in->read_image(
+            subimage, miplevel,
+            chbegin, chend,
+            OIIO::TypeDesc::FLOAT,
+            channel_data,
+            xstride, ystride, zstride);
+        if (!ok) {
+            std::cerr
+                << "oiio_read_image_channels: ERROR: "
+                << in->geterror() << '\n';
+            in->close();
+            return false;
+        }
+    }
+    in->close();
+    return true;
+}
+
 bool name_has_suffix(std::string const &name, std::string const &suffix) {
     if (name.length() >= suffix.length()) {
         return 0 == name.compare(name.length() - suffix.length(), suffix.length(), suffix);
@@ -166,8 +315,13 @@ bool name_has_suffix(std::string const &name, std::string const &suffix) {
 }
 
 
-bool oiio_write_image(const rust::String &file_path, const ImageShared &image,
-                      const ImageCompression &compress) {
+// Write the image, naming the channels with 'channel_names' (the
+// default names are used when the list is empty).
+bool oiio_write_image_with_channel_names(
+        const rust::String &file_path,
+        const ImageShared &image,
+        const rust::Vec<rust::String> &channel_names,
+        const ImageCompression &compress) {
     // std::cerr << "oiio_write_image..." << file_path << '\n';
     const int32_t xres = image.pixel_block->width();
     const int32_t yres = image.pixel_block->height();
@@ -217,12 +371,36 @@ bool oiio_write_image(const rust::String &file_path, const ImageShared &image,
         return false;
     }
 
+    if (!channel_names.empty()) {
+        if (static_cast<int32_t>(channel_names.size()) != channels) {
+            std::cerr
+                << "oiio_write_image: "
+                << "Channel names do not match the number of channels. "
+                << "Cannot write image! "
+                << filename << '\n';
+            return false;
+        }
+        spec.channelnames.clear();
+        for (auto &channel_name : channel_names) {
+            spec.channelnames.push_back(std::string(channel_name));
+        }
+        spec.alpha_channel = spec.channelindex("A");
+        spec.z_channel = spec.channelindex("Z");
+    }
+
+    // Write the image metadata first, so the values below always
+    // match the ImageSpec.
+    oiio_write_metadata(image.spec.metadata, spec);
+
     // Set the 'colorspace', and other metadata, so that the image
     // writing can correctly convert the data for the intended format.
     auto color_space_str = std::string(image.spec.color_space);
     auto gamma = static_cast<float>(image.spec.gamma);
     auto pixel_aspect = static_cast<float>(image.spec.pixel_aspect);
-    auto orientation = static_cast<int32_t>(image.spec.orientation);
+    auto orientation = static_cast<int32_t>(image.spec.orientation) + 1;
+    if (orientation > 8) {
+        orientation = 1;
+    }
     auto unassociated_alpha = static_cast<int32_t>(image.spec.unassociated_alpha);
     auto dither = static_cast<int32_t>(image.spec.dither);
     // std::cerr << "OUT Color Space: " << color_space_str << '\n';
@@ -322,5 +500,20 @@ bool oiio_write_image(const rust::String &file_path, const ImageShared &image,
     return true;
 }
 
+bool oiio_write_image(const rust::String &file_path, const ImageShared &image,
+                      const ImageCompression &compress) {
+    auto channel_names = rust::Vec<rust::String>();
+    return oiio_write_image_with_channel_names(
+        file_path, image, channel_names, compress);
+}
+
+bool oiio_write_image_channels(const rust::String &file_path,
+                               const ImageShared &image,
+                               const rust::Vec<rust::String> &channel_names,
+                               const ImageCompression &compress) {
+    return oiio_write_image_with_channel_names(
+        file_path, image, channel_names, compress);
+}
+
 } // namespace internal
 } // namespace open_comp_graph
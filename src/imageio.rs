@@ -19,41 +19,109 @@
  *
  */
 
-use image;
+pub mod exrio;
+pub mod rustio;
+
 use log::debug;
 use log::warn;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
 use std::time::Instant;
 
+use crate::config::get_config;
 use crate::cxxbridge::ffi::oiio_get_thread_count;
 use crate::cxxbridge::ffi::oiio_read_image;
+use crate::cxxbridge::ffi::oiio_read_image_channel_names;
+use crate::cxxbridge::ffi::oiio_read_image_channels;
+use crate::cxxbridge::ffi::oiio_read_image_header;
+use crate::cxxbridge::ffi::oiio_read_image_num_channels;
 use crate::cxxbridge::ffi::oiio_set_thread_count;
 use crate::cxxbridge::ffi::oiio_write_image;
+use crate::cxxbridge::ffi::oiio_write_image_channels;
 use crate::cxxbridge::ffi::BBox2Di;
 use crate::cxxbridge::ffi::DataType;
 use crate::cxxbridge::ffi::ImageCompression;
+use crate::cxxbridge::ffi::ImageIOBackend;
 use crate::cxxbridge::ffi::ImageShared;
 use crate::cxxbridge::ffi::ImageSpec;
-use crate::imagebuffer::create_image_buffer_rgb_u8;
-use crate::imagebuffer::create_image_buffer_rgba_u8;
 use crate::ops::imagecrop;
-use crate::pixelblock::dynimage::from_dynamic_image;
 use crate::pixelblock::pixelblock::PixelBlock;
 
-pub fn read_image(path: &String, num_threads: i32) -> ImageShared {
+/// The backend used to read and write images, or 'Uninitialized'
+/// until the backend is first used.
+static BACKEND: AtomicU8 = AtomicU8::new(255);
+
+pub fn backend_from_name(name: &str) -> ImageIOBackend {
+    match name.to_lowercase().as_str() {
+        "oiio" => ImageIOBackend::OpenImageIO,
+        "rust" => ImageIOBackend::Rust,
+        _ => ImageIOBackend::Uninitialized,
+    }
+}
+
+/// The backend used to read and write images.
+///
+/// Defaults to the "imageio: backend" value in the
+/// "open_comp_graph.yaml" configuration file, or OpenImageIO if the
+/// value is not a known backend name.
+pub fn image_io_backend() -> ImageIOBackend {
+    let value = BACKEND.load(Ordering::Relaxed);
+    if value != ImageIOBackend::Uninitialized.repr {
+        return ImageIOBackend { repr: value };
+    }
+    let config = get_config("open_comp_graph.yaml");
+    let backend = match config.inner.imageio_backend() {
+        ImageIOBackend::Uninitialized => {
+            warn!("Unknown \"imageio: backend\" in configuration file, using OpenImageIO.");
+            ImageIOBackend::OpenImageIO
+        }
+        value => value,
+    };
+    // Do not replace a backend set while the config was read.
+    let _ = BACKEND.compare_exchange(
+        ImageIOBackend::Uninitialized.repr,
+        backend.repr,
+        Ordering::Relaxed,
+        Ordering::Relaxed,
+    );
+    ImageIOBackend {
+        repr: BACKEND.load(Ordering::Relaxed),
+    }
+}
+
+/// Change the backend used to read and write images, for all
+/// threads.
+pub fn set_image_io_backend(backend: ImageIOBackend) {
+    match backend {
+        ImageIOBackend::OpenImageIO | ImageIOBackend::Rust => {
+            BACKEND.store(backend.repr, Ordering::Relaxed)
+        }
+        _ => warn!("Invalid image IO backend: {:?}", backend),
+    }
+}
+
+/// An image without any pixels, used when reading an image fails.
+pub fn empty_image() -> ImageShared {
+    ImageShared {
+        pixel_block: Box::new(PixelBlock::empty(DataType::Float32)),
+        display_window: BBox2Di::new(0, 0, 0, 0),
+        data_window: BBox2Di::new(0, 0, 0, 0),
+        spec: ImageSpec::new(),
+    }
+}
+
+/// Read an image file, returning a message describing why the image
+/// could not be read on failure.
+pub fn read_image(path: &String, num_threads: i32) -> Result<ImageShared, String> {
     debug!("Reading... {:?}", path);
     debug!("num_threads: {:?}", num_threads);
     let start = Instant::now();
 
-    let use_oiio = true;
-    let image = match use_oiio {
-        true => {
+    let image = match image_io_backend() {
+        ImageIOBackend::Rust => rustio::read_image(path),
+        _ => {
             // Use OpenImageIO C++ library to read the image path.
-            let mut image = ImageShared {
-                pixel_block: Box::new(PixelBlock::empty(DataType::Float32)),
-                display_window: BBox2Di::new(0, 0, 0, 0),
-                data_window: BBox2Di::new(0, 0, 0, 0),
-                spec: ImageSpec::new(),
-            };
+            let mut image = empty_image();
 
             // Overrides the number of threads used for reading.
             let mut old_num_threads = 0;
@@ -61,30 +129,175 @@ pub fn read_image(path: &String, num_threads: i32) -> ImageShared {
             oiio_set_thread_count(num_threads);
             let ok = oiio_read_image(&path, &mut image);
             oiio_set_thread_count(old_num_threads);
-            if ok == false {
-                warn!("Reading image failed: {:?}", path);
+            match ok {
+                true => Ok(image),
+                false => Err("failed to read image".to_string()),
             }
-
-            image
         }
+    };
+    let duration = start.elapsed();
+    debug!("Reading total time: {:?}", duration);
+
+    if let Err(e) = &image {
+        warn!("Reading image failed: {:?} {}", path, e);
+    }
+    image
+}
+
+/// Read the number of channels in an image file, without reading
+/// any pixels.
+pub fn read_image_num_channels(path: &String) -> Option<i32> {
+    debug!("Reading number of channels... {:?}", path);
+    if image_io_backend() == ImageIOBackend::Rust {
+        return match rustio::read_image_num_channels(path) {
+            Ok(num_channels) => Some(num_channels),
+            Err(e) => {
+                warn!("Reading image header failed: {:?} {}", path, e);
+                None
+            }
+        };
+    }
+    let mut num_channels = 0;
+    let ok = oiio_read_image_num_channels(path, &mut num_channels);
+    match ok {
+        true => Some(num_channels),
         false => {
-            // Use Rust "image" Crate.
-            let img = image::open(path).unwrap();
-            let pixel_block = Box::new(from_dynamic_image(img));
-            let display_window = BBox2Di::new(0, 0, pixel_block.width(), pixel_block.height());
-            let data_window = display_window.clone();
-            ImageShared {
-                pixel_block,
-                display_window,
-                data_window,
-                spec: ImageSpec::new(),
+            warn!("Reading image header failed: {:?}", path);
+            None
+        }
+    }
+}
+
+/// Read the data and display windows and the orientation of an image
+/// file, without reading any pixels. The pixel block of the image is
+/// empty.
+pub fn read_image_header(path: &String) -> Option<ImageShared> {
+    debug!("Reading header... {:?}", path);
+    let image = match image_io_backend() {
+        ImageIOBackend::Rust => rustio::read_image_header(path),
+        _ => {
+            let mut image = empty_image();
+            match oiio_read_image_header(path, &mut image) {
+                true => Ok(image),
+                false => Err("failed to read header".to_string()),
+            }
+        }
+    };
+    match image {
+        Ok(value) => Some(value),
+        Err(e) => {
+            warn!("Reading image header failed: {:?} {}", path, e);
+            None
+        }
+    }
+}
+
+/// Read the names of all channels in an image file, without reading
+/// any pixels.
+pub fn read_image_channel_names(path: &String) -> Option<Vec<String>> {
+    debug!("Reading channel names... {:?}", path);
+    let channel_names = match image_io_backend() {
+        ImageIOBackend::Rust => rustio::read_image_channel_names(path),
+        _ => {
+            let mut channel_names = Vec::new();
+            match oiio_read_image_channel_names(path, &mut channel_names) {
+                true => Ok(channel_names),
+                false => Err("failed to read channel names".to_string()),
+            }
+        }
+    };
+    match channel_names {
+        Ok(value) => Some(value),
+        Err(e) => {
+            warn!("Reading image header failed: {:?} {}", path, e);
+            None
+        }
+    }
+}
+
+/// Read the named channels of an image file (such as "depth.Z") into
+/// a Float32 image, in the order given.
+pub fn read_image_channels(
+    path: &String,
+    channel_names: &[String],
+    num_threads: i32,
+) -> Option<ImageShared> {
+    debug!("Reading channels... {:?} {:?}", path, channel_names);
+    let start = Instant::now();
+
+    let image = match image_io_backend() {
+        ImageIOBackend::Rust => rustio::read_image_channels(path, channel_names),
+        _ => {
+            let mut image = empty_image();
+            let mut old_num_threads = 0;
+            oiio_get_thread_count(&mut old_num_threads);
+            oiio_set_thread_count(num_threads);
+            let ok = oiio_read_image_channels(path, &channel_names.to_vec(), &mut image);
+            oiio_set_thread_count(old_num_threads);
+            match ok {
+                true => Ok(image),
+                false => Err("failed to read channels".to_string()),
             }
         }
     };
     let duration = start.elapsed();
-    debug!("Reading total time: {:?}", duration);
+    debug!("Reading channels total time: {:?}", duration);
 
-    image
+    match image {
+        Ok(value) => Some(value),
+        Err(e) => {
+            warn!("Reading image channels failed: {:?} {}", path, e);
+            None
+        }
+    }
+}
+
+/// Read an image file (if 'with_image' is true) and each list of
+/// named channels (for image layers), in the order given.
+///
+/// The Rust backend reads the file only once. A layer is None if its
+/// channels cannot be read. An error is returned if the image is
+/// requested and cannot be read.
+pub fn read_image_layers(
+    path: &String,
+    with_image: bool,
+    layer_channel_names: &[Vec<String>],
+    num_threads: i32,
+) -> Result<(Option<ImageShared>, Vec<Option<ImageShared>>), String> {
+    if image_io_backend() != ImageIOBackend::Rust {
+        let image = match with_image {
+            true => Some(read_image(path, num_threads)?),
+            false => None,
+        };
+        let layers = layer_channel_names
+            .iter()
+            .map(|channel_names| read_image_channels(path, channel_names, num_threads))
+            .collect();
+        return Ok((image, layers));
+    }
+
+    debug!("Reading layers... {:?} {:?}", path, layer_channel_names);
+    let start = Instant::now();
+    let images = rustio::read_image_layers(path, with_image, layer_channel_names);
+    let duration = start.elapsed();
+    debug!("Reading layers total time: {:?}", duration);
+
+    match images {
+        Ok((image, layers)) => {
+            let image = match with_image {
+                true => Some(image.unwrap_or_else(empty_image)),
+                false => None,
+            };
+            Ok((image, layers))
+        }
+        Err(e) => {
+            warn!("Reading image failed: {:?} {}", path, e);
+            match with_image {
+                true => Err(e),
+                false => Ok((None, layer_channel_names.iter().map(|_| None).collect())),
+            }
+        }
+    }
 }
 
 pub fn write_image(
@@ -93,6 +306,26 @@ pub fn write_image(
     num_threads: i32,
     crop_to_display_window: bool,
     compress: ImageCompression,
+) -> bool {
+    write_image_channels(
+        image,
+        &[],
+        path,
+        num_threads,
+        crop_to_display_window,
+        compress,
+    )
+}
+
+/// Write an image, with a name for each channel. The default channel
+/// names (R, G, B and A) are used when 'channel_names' is empty.
+pub fn write_image_channels(
+    image: &ImageShared,
+    channel_names: &[String],
+    path: &String,
+    num_threads: i32,
+    crop_to_display_window: bool,
+    compress: ImageCompression,
 ) -> bool {
     debug!("Writing... {:?}", path);
     debug!("num_threads: {:?}", num_threads);
@@ -109,7 +342,7 @@ pub fn write_image(
         pixel_block: Box::new(new_pixel_block),
         display_window: new_display_window,
         data_window: new_data_window,
-        spec: ImageSpec::new(),
+        spec: image.spec.clone(),
     };
 
     if crop_to_display_window == true {
@@ -126,9 +359,17 @@ pub fn write_image(
         debug!("Crop Ok: {:?}", crop_ok);
     }
 
-    let use_oiio = true;
-    let ok = match use_oiio {
-        true => {
+    let ok = match image_io_backend() {
+        ImageIOBackend::Rust => {
+            match rustio::write_image_channels(path, &image_out, channel_names, &compress) {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("Rust image IO: {}", e);
+                    false
+                }
+            }
+        }
+        _ => {
             // Use OpenImageIO C++ library to write the image path.
 
             // Overrides the number of threads used for writing.
@@ -136,32 +377,17 @@ pub fn write_image(
             oiio_get_thread_count(&mut old_num_threads);
             oiio_set_thread_count(num_threads);
 
-            let ok = oiio_write_image(&path, &image_out, &compress);
+            let ok = match channel_names.len() {
+                0 => oiio_write_image(path, &image_out, &compress),
+                _ => {
+                    oiio_write_image_channels(path, &image_out, &channel_names.to_vec(), &compress)
+                }
+            };
 
             oiio_set_thread_count(old_num_threads);
 
             ok
         }
-        false => {
-            // Use Rust "image" Crate.
-            let mut ok = false;
-            let num_channels = image.pixel_block.num_channels();
-            if num_channels == 3 {
-                let img = create_image_buffer_rgb_u8(&image_out);
-                ok = match img.save(path) {
-                    Ok(_value) => true,
-                    Err(_) => false,
-                };
-            }
-            if num_channels == 4 {
-                let img = create_image_buffer_rgba_u8(&image_out);
-                ok = match img.save(path) {
-                    Ok(_value) => true,
-                    Err(_) => false,
-                };
-            }
-            ok
-        }
     };
     if ok == false {
         warn!("Writing image failed: {:?}", path);
@@ -0,0 +1,885 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+// Read and write OpenEXR images with the pure-Rust "exr" crate.
+//
+// Only the first part of the file is used. The R, G, B and A
+// channels are the image, and other channels can be read by name
+// (for image layers). The data window and display window are converted the same
+// way as the OpenImageIO reader ('oiio_allocate_image'), so both
+// backends create the same images. Header attributes are read as
+// image metadata using the OpenImageIO names.
+
+use exr::meta::header::standard_names;
+use exr::prelude::attribute::KeyCode;
+use exr::prelude::attribute::TimeCode;
+use exr::prelude::AnyChannel;
+use exr::prelude::AnyChannels;
+use exr::prelude::AttributeValue;
+use exr::prelude::Blocks;
+use exr::prelude::Compression;
+use exr::prelude::Encoding;
+use exr::prelude::FlatSamples;
+use exr::prelude::Image;
+use exr::prelude::ImageAttributes;
+use exr::prelude::IntegerBounds;
+use exr::prelude::Layer;
+use exr::prelude::LayerAttributes;
+use exr::prelude::LineOrder;
+use exr::prelude::MetaData;
+use exr::prelude::ReadChannels;
+use exr::prelude::ReadLayers;
+use exr::prelude::Text;
+use exr::prelude::Vec2;
+use exr::prelude::WritableImage;
+use half::f16;
+use log::debug;
+use log::warn;
+use std::convert::TryInto;
+
+use crate::cxxbridge::ffi::BBox2Di;
+use crate::cxxbridge::ffi::BlockSize;
+use crate::cxxbridge::ffi::DataType;
+use crate::cxxbridge::ffi::ExrCompression;
+use crate::cxxbridge::ffi::ImageCompression;
+use crate::cxxbridge::ffi::ImageMetadata;
+use crate::cxxbridge::ffi::ImageMetadataType;
+use crate::cxxbridge::ffi::ImageShared;
+use crate::cxxbridge::ffi::ImageSpec;
+use crate::imagelayer::RGBA_CHANNEL_NAMES;
+use crate::pixelblock::datablock::DataBlock;
+use crate::pixelblock::pixelblock::PixelBlock;
+use crate::pixelblock::utils::stride_num_channels;
+
+/// The OpenImageIO header attribute used for the color space.
+const COLOR_SPACE_ATTR_NAME: &str = "oiio:ColorSpace";
+
+/// The OpenImageIO metadata names of the EXR time code and key code.
+const TIME_CODE_METADATA_NAME: &str = "smpte:TimeCode";
+const KEY_CODE_METADATA_NAME: &str = "smpte:KeyCode";
+
+/// The default DWA compression level used by OpenEXR.
+const DEFAULT_DWA_COMPRESSION_LEVEL: f32 = 45.0;
+
+pub fn is_exr_path(path: &str) -> bool {
+    path.to_lowercase().ends_with(".exr")
+}
+
+/// Find the RGB(A) channels of a layer, in PixelBlock channel order.
+fn rgba_channels(channels: &AnyChannels<FlatSamples>) -> Result<Vec<&FlatSamples>, String> {
+    let mut samples = Vec::new();
+    for name in RGBA_CHANNEL_NAMES.iter() {
+        let channel = channels.list.iter().find(|c| c.name == **name);
+        match (channel, *name) {
+            (Some(channel), _) => samples.push(&channel.sample_data),
+            (None, "A") => (),
+            (None, _) => return Err(format!("image has no {:?} channel", name)),
+        }
+    }
+    Ok(samples)
+}
+
+/// Convert the EXR windows into data and display windows, the same
+/// way as 'oiio_allocate_image'.
+fn image_windows(
+    data_position: Vec2<i32>,
+    data_size: Vec2<usize>,
+    display_window: IntegerBounds,
+) -> (BBox2Di, BBox2Di) {
+    let display_min_x = display_window.position.x();
+    let display_min_y = display_window.position.y();
+    let data_window = BBox2Di::new(
+        data_position.x() + display_min_x,
+        data_position.y() + display_min_y,
+        data_position.x() + data_size.x() as i32 + display_min_x,
+        data_position.y() + data_size.y() as i32 + display_min_y,
+    );
+    let display_window = BBox2Di::new(
+        0,
+        0,
+        display_window.size.x() as i32,
+        display_window.size.y() as i32,
+    );
+    (data_window, display_window)
+}
+
+/// Find the named channels of a part, in the order given.
+fn named_channels<'a>(
+    channels: &'a AnyChannels<FlatSamples>,
+    channel_names: &[String],
+) -> Result<Vec<&'a FlatSamples>, String> {
+    let mut samples = Vec::new();
+    for name in channel_names {
+        match channels.list.iter().find(|c| c.name == *name.as_str()) {
+            Some(channel) => samples.push(&channel.sample_data),
+            None => return Err(format!("image has no {:?} channel", name)),
+        }
+    }
+    Ok(samples)
+}
+
+/// Interleave the planar channel 'samples' into a pixel block of
+/// 'data_type' (Half16 or Float32).
+///
+/// Half16 pixels are padded to 4 channels, the same as other image
+/// readers.
+fn interleave_samples(
+    samples: &[&FlatSamples],
+    width: i32,
+    height: i32,
+    data_type: DataType,
+) -> PixelBlock {
+    let num_channels = samples.len();
+    let padded_num_channels = stride_num_channels(num_channels as i32, data_type);
+    let pixel_count = (width * height) as usize;
+
+    let datablock = match data_type {
+        DataType::Half16 => {
+            let mut pixels = vec![f16::from_f32(1.0); pixel_count * padded_num_channels];
+            for (c, channel_samples) in samples.iter().enumerate() {
+                match channel_samples {
+                    FlatSamples::F16(values) => {
+                        for (i, value) in values.iter().enumerate() {
+                            // The "exr" crate uses a different version
+                            // of the "half" crate.
+                            pixels[i * padded_num_channels + c] = f16::from_bits(value.to_bits());
+                        }
+                    }
+                    _ => {
+                        for (i, value) in channel_samples.values_as_f32().enumerate() {
+                            pixels[i * padded_num_channels + c] = f16::from_f32(value);
+                        }
+                    }
+                }
+            }
+            DataBlock::from_slice_f16(&pixels)
+        }
+        _ => {
+            let mut pixels = vec![1.0; pixel_count * padded_num_channels];
+            for (c, channel_samples) in samples.iter().enumerate() {
+                for (i, value) in channel_samples.values_as_f32().enumerate() {
+                    pixels[i * padded_num_channels + c] = value;
+                }
+            }
+            DataBlock::from_slice_f32(&pixels)
+        }
+    };
+    let blocksize = BlockSize::new(width, height, padded_num_channels as i32);
+    PixelBlock::from_datablock(blocksize, datablock)
+}
+
+/// The first part of an EXR file, with all channels.
+type FirstPartImage = Image<Layer<AnyChannels<FlatSamples>>>;
+
+/// Decode the first part of an EXR file. Other parts are skipped.
+///
+/// Each block of an EXR file stores all of its channels together,
+/// so all channels are decoded; callers that need several sets of
+/// channels should pick them from a single decoded image.
+fn read_exr_image(path: &str) -> Result<FirstPartImage, String> {
+    exr::prelude::read()
+        .no_deep_data()
+        .largest_resolution_level()
+        .all_channels()
+        .first_valid_layer()
+        .all_attributes()
+        .from_file(path)
+        .map_err(|e| e.to_string())
+}
+
+/// Read the number of channels in the first layer of an EXR file,
+/// without reading any pixels.
+pub fn read_image_num_channels(path: &str) -> Result<i32, String> {
+    let channel_names = read_image_channel_names(path)?;
+    Ok(channel_names.len() as i32)
+}
+
+/// Read the names of all channels in the first part of an EXR file,
+/// without reading any pixels.
+pub fn read_image_channel_names(path: &str) -> Result<Vec<String>, String> {
+    let meta_data = MetaData::read_from_file(path, false).map_err(|e| e.to_string())?;
+    match meta_data.headers.first() {
+        Some(header) => Ok(header
+            .channels
+            .list
+            .iter()
+            .map(|c| c.name.to_string())
+            .collect()),
+        None => Err("image has no parts".to_string()),
+    }
+}
+
+/// Read the data and display windows of the first part of an EXR
+/// file, without reading any pixels. The pixel block is empty.
+pub fn read_image_header(path: &str) -> Result<ImageShared, String> {
+    let meta_data = MetaData::read_from_file(path, false).map_err(|e| e.to_string())?;
+    let header = match meta_data.headers.first() {
+        Some(value) => value,
+        None => return Err("image has no parts".to_string()),
+    };
+    let (data_window, display_window) = image_windows(
+        header.own_attributes.layer_position,
+        header.layer_size,
+        header.shared_attributes.display_window,
+    );
+    Ok(ImageShared {
+        pixel_block: Box::new(PixelBlock::empty(DataType::Float32)),
+        spec: ImageSpec::new(),
+        display_window,
+        data_window,
+    })
+}
+
+/// Convert the EXR header attributes into image metadata.
+///
+/// Custom attributes are sorted by name, so the metadata (and the
+/// image hash) is the same each time the file is read.
+fn read_metadata(
+    image_attributes: &ImageAttributes,
+    layer_attributes: &LayerAttributes,
+) -> Vec<ImageMetadata> {
+    let mut metadata = Vec::new();
+    let text_attributes = [
+        ("Copyright", &layer_attributes.owner),
+        ("ImageDescription", &layer_attributes.comments),
+        ("DateTime", &layer_attributes.capture_date),
+        ("Software", &layer_attributes.software_name),
+    ];
+    for (name, value) in text_attributes.iter() {
+        if let Some(value) = value {
+            metadata.push(ImageMetadata::new_string(name, &value.to_string()));
+        }
+    }
+    let matrix_attributes = [
+        ("worldtocamera", &layer_attributes.world_to_camera),
+        (
+            "worldtoscreen",
+            &layer_attributes.world_to_normalized_device,
+        ),
+    ];
+    for (name, value) in matrix_attributes.iter() {
+        if let Some(value) = value {
+            metadata.push(ImageMetadata::new_float(name, value.to_vec()));
+        }
+    }
+    if let Some(time_code) = image_attributes.time_code {
+        if let Ok(time) = time_code.pack_time_as_tv60_u32() {
+            let user_data = time_code.pack_user_data_as_u32();
+            metadata.push(ImageMetadata::new_int(
+                TIME_CODE_METADATA_NAME,
+                vec![time as i32, user_data as i32],
+            ));
+        }
+    }
+    if let Some(key_code) = layer_attributes.film_key_code {
+        metadata.push(ImageMetadata::new_int(
+            KEY_CODE_METADATA_NAME,
+            vec![
+                key_code.film_manufacturer_code,
+                key_code.film_type,
+                key_code.film_roll_prefix,
+                key_code.count,
+                key_code.perforation_offset,
+                key_code.perforations_per_frame,
+                key_code.perforations_per_count,
+            ],
+        ));
+    }
+
+    let mut other: Vec<(String, &AttributeValue)> = image_attributes
+        .other
+        .iter()
+        .chain(layer_attributes.other.iter())
+        .map(|(name, value)| (name.to_string(), value))
+        .filter(|(name, _)| !name.starts_with("oiio:"))
+        .collect();
+    other.sort_by(|a, b| a.0.cmp(&b.0));
+    for (name, value) in other {
+        let item = match value {
+            AttributeValue::I32(v) => ImageMetadata::new_int(&name, vec![*v]),
+            AttributeValue::IntVec2(v) => ImageMetadata::new_int(&name, vec![v.0, v.1]),
+            AttributeValue::IntVec3(v) => ImageMetadata::new_int(&name, vec![v.0, v.1, v.2]),
+            AttributeValue::F32(v) => ImageMetadata::new_float(&name, vec![*v]),
+            AttributeValue::F64(v) => ImageMetadata::new_float(&name, vec![*v as f32]),
+            AttributeValue::FloatVec2(v) => ImageMetadata::new_float(&name, vec![v.0, v.1]),
+            AttributeValue::FloatVec3(v) => ImageMetadata::new_float(&name, vec![v.0, v.1, v.2]),
+            AttributeValue::Matrix3x3(v) => ImageMetadata::new_float(&name, v.to_vec()),
+            AttributeValue::Matrix4x4(v) => ImageMetadata::new_float(&name, v.to_vec()),
+            AttributeValue::Text(v) => ImageMetadata::new_string(&name, &v.to_string()),
+            _ => {
+                debug!("Skipping EXR attribute: {:?}", name);
+                continue;
+            }
+        };
+        metadata.push(item);
+    }
+    metadata
+}
+
+/// Convert image metadata into EXR header attributes. Values that
+/// cannot be stored in an EXR header are skipped.
+fn write_metadata(
+    metadata: &[ImageMetadata],
+    image_attributes: &mut ImageAttributes,
+    layer_attributes: &mut LayerAttributes,
+) {
+    for item in metadata {
+        let name = item.name();
+        if name.is_empty() || name.starts_with("oiio:") {
+            continue;
+        }
+        let ints = item.int_values();
+        let floats = item.float_values();
+        let text = Text::new_or_none(item.string_value());
+        let value = match (name, item.data_type()) {
+            (TIME_CODE_METADATA_NAME, ImageMetadataType::Int) if ints.len() == 2 => {
+                let time_code = TimeCode::from_tv60_time(ints[0] as u32, ints[1] as u32);
+                image_attributes.time_code = Some(time_code);
+                continue;
+            }
+            (KEY_CODE_METADATA_NAME, ImageMetadataType::Int) if ints.len() == 7 => {
+                layer_attributes.film_key_code = Some(KeyCode {
+                    film_manufacturer_code: ints[0],
+                    film_type: ints[1],
+                    film_roll_prefix: ints[2],
+                    count: ints[3],
+                    perforation_offset: ints[4],
+                    perforations_per_frame: ints[5],
+                    perforations_per_count: ints[6],
+                });
+                continue;
+            }
+            ("Copyright", ImageMetadataType::String) => {
+                layer_attributes.owner = text;
+                continue;
+            }
+            ("ImageDescription", ImageMetadataType::String) => {
+                layer_attributes.comments = text;
+                continue;
+            }
+            ("DateTime", ImageMetadataType::String) => {
+                layer_attributes.capture_date = text;
+                continue;
+            }
+            ("Software", ImageMetadataType::String) => {
+                layer_attributes.software_name = text;
+                continue;
+            }
+            ("worldtocamera", ImageMetadataType::Float) if floats.len() == 16 => {
+                layer_attributes.world_to_camera = floats.try_into().ok();
+                continue;
+            }
+            ("worldtoscreen", ImageMetadataType::Float) if floats.len() == 16 => {
+                layer_attributes.world_to_normalized_device = floats.try_into().ok();
+                continue;
+            }
+            // Other standard attributes are written by the "exr"
+            // crate, and cannot be custom attributes.
+            _ if standard_names::ALL.contains(&name.as_bytes()) => None,
+            (_, ImageMetadataType::Int) => match ints.len() {
+                1 => Some(AttributeValue::I32(ints[0])),
+                2 => Some(AttributeValue::IntVec2(Vec2(ints[0], ints[1]))),
+                3 => Some(AttributeValue::IntVec3((ints[0], ints[1], ints[2]))),
+                _ => None,
+            },
+            (_, ImageMetadataType::Float) => match floats.len() {
+                1 => Some(AttributeValue::F32(floats[0])),
+                2 => Some(AttributeValue::FloatVec2(Vec2(floats[0], floats[1]))),
+                3 => Some(AttributeValue::FloatVec3((floats[0], floats[1], floats[2]))),
+                9 => floats.try_into().ok().map(AttributeValue::Matrix3x3),
+                16 => floats.try_into().ok().map(AttributeValue::Matrix4x4),
+                _ => None,
+            },
+            (_, ImageMetadataType::String) => text.map(AttributeValue::Text),
+            _ => None,
+        };
+        match (Text::new_or_none(name), value) {
+            (Some(name), Some(value)) => {
+                layer_attributes.other.insert(name, value);
+            }
+            _ => debug!("Skipping metadata for EXR: {:?}", item.name()),
+        }
+    }
+}
+
+/// Convert the RGB(A) channels of a decoded image into an image.
+///
+/// Half16 pixels are read directly into the pixel block, other
+/// sample types are converted to Float32.
+fn rgba_image(image: &FirstPartImage) -> Result<ImageShared, String> {
+    let layer = &image.layer_data;
+
+    let width = layer.size.x() as i32;
+    let height = layer.size.y() as i32;
+    let samples = rgba_channels(&layer.channel_data)?;
+    let all_half = samples.iter().all(|s| matches!(s, FlatSamples::F16(_)));
+    let data_type = match all_half {
+        true => DataType::Half16,
+        false => DataType::Float32,
+    };
+    let pixel_block = interleave_samples(&samples, width, height, data_type);
+    debug!(
+        "Read EXR {}x{} c={} data_type={:?}",
+        width,
+        height,
+        samples.len(),
+        pixel_block.data_type()
+    );
+
+    let (data_window, display_window) = image_windows(
+        layer.attributes.layer_position,
+        layer.size,
+        image.attributes.display_window,
+    );
+
+    let mut spec = ImageSpec::new();
+    spec.set_pixel_aspect(image.attributes.pixel_aspect);
+    let color_space_attr = Text::from(COLOR_SPACE_ATTR_NAME);
+    let color_space = layer
+        .attributes
+        .other
+        .get(&color_space_attr)
+        .or_else(|| image.attributes.other.get(&color_space_attr));
+    if let Some(AttributeValue::Text(value)) = color_space {
+        spec.set_color_space(value.to_string());
+    }
+    spec.set_metadata(read_metadata(&image.attributes, &layer.attributes));
+
+    Ok(ImageShared {
+        pixel_block: Box::new(pixel_block),
+        spec,
+        display_window,
+        data_window,
+    })
+}
+
+/// Convert the named channels of a decoded image, in the order
+/// given, into a Float32 image.
+fn channels_image(image: &FirstPartImage, channel_names: &[String]) -> Result<ImageShared, String> {
+    let layer = &image.layer_data;
+
+    let width = layer.size.x() as i32;
+    let height = layer.size.y() as i32;
+    let samples = named_channels(&layer.channel_data, channel_names)?;
+    let pixel_block = interleave_samples(&samples, width, height, DataType::Float32);
+    let (data_window, display_window) = image_windows(
+        layer.attributes.layer_position,
+        layer.size,
+        image.attributes.display_window,
+    );
+    Ok(ImageShared {
+        pixel_block: Box::new(pixel_block),
+        spec: ImageSpec::new(),
+        display_window,
+        data_window,
+    })
+}
+
+/// Read the first layer of an EXR file.
+pub fn read_image(path: &str) -> Result<ImageShared, String> {
+    let image = read_exr_image(path)?;
+    rgba_image(&image)
+}
+
+/// Read the named channels of the first part of an EXR file (such
+/// as "depth.Z"), in the order given, into a Float32 image.
+pub fn read_image_channels(path: &str, channel_names: &[String]) -> Result<ImageShared, String> {
+    let image = read_exr_image(path)?;
+    channels_image(&image, channel_names)
+}
+
+/// Read the RGB(A) image (if 'with_image' is true) and each list of
+/// named channels (for image layers) of an EXR file, decoding the
+/// file only once.
+///
+/// The image is None if it is not requested or the RGB channels
+/// cannot be found, and a layer is None if any of its channels
+/// cannot be found.
+pub fn read_image_layers(
+    path: &str,
+    with_image: bool,
+    layer_channel_names: &[Vec<String>],
+) -> Result<(Option<ImageShared>, Vec<Option<ImageShared>>), String> {
+    let image = read_exr_image(path)?;
+    let rgba = match with_image {
+        true => match rgba_image(&image) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                warn!("Reading EXR image failed: {:?} {}", path, e);
+                None
+            }
+        },
+        false => None,
+    };
+    let layers = layer_channel_names
+        .iter()
+        .map(
+            |channel_names| match channels_image(&image, channel_names) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    warn!("Reading EXR channels failed: {:?} {}", path, e);
+                    None
+                }
+            },
+        )
+        .collect();
+    Ok((rgba, layers))
+}
+
+fn exr_compression(compress: &ImageCompression) -> Compression {
+    let dwa_level = match compress.exr_dwa_compression_level {
+        level if level > 0 => level as f32,
+        _ => DEFAULT_DWA_COMPRESSION_LEVEL,
+    };
+    match compress.exr_compression {
+        ExrCompression::None => Compression::Uncompressed,
+        ExrCompression::Rle => Compression::RLE,
+        ExrCompression::ZipScanline => Compression::ZIP1,
+        ExrCompression::Piz => Compression::PIZ,
+        ExrCompression::Pxr24 => Compression::PXR24,
+        ExrCompression::B44 => Compression::B44,
+        ExrCompression::B44a => Compression::B44A,
+        ExrCompression::Dwaa => Compression::DWAA(Some(dwa_level)),
+        ExrCompression::Dwab => Compression::DWAB(Some(dwa_level)),
+        // ZIP is the default compression of OpenImageIO.
+        _ => Compression::ZIP16,
+    }
+}
+
+/// Write the pixels of 'image' to an EXR file, keeping the data
+/// window and display window.
+///
+/// Half16 and Float32 pixels are written with the same data type,
+/// UInt8 and UInt16 pixels are written as Half16.
+pub fn write_image(
+    path: &str,
+    image: &ImageShared,
+    compress: &ImageCompression,
+) -> Result<(), String> {
+    write_image_channels(path, image, &[], compress)
+}
+
+/// Write the pixels of 'image' to an EXR file, with a name for each
+/// channel. The default channel names (R, G, B and A) are used when
+/// 'channel_names' is empty.
+pub fn write_image_channels(
+    path: &str,
+    image: &ImageShared,
+    channel_names: &[String],
+    compress: &ImageCompression,
+) -> Result<(), String> {
+    let mut pixel_block = (*image.pixel_block).clone();
+    if (pixel_block.data_type() == DataType::UInt8) || (pixel_block.data_type() == DataType::UInt16)
+    {
+        pixel_block.convert_into_data_type(DataType::Half16);
+    }
+    let width = pixel_block.width() as usize;
+    let height = pixel_block.height() as usize;
+    let num_channels = pixel_block.num_channels() as usize;
+    let channel_names: Vec<String> = match channel_names.len() {
+        0 => RGBA_CHANNEL_NAMES
+            .iter()
+            .take(num_channels)
+            .map(|s| s.to_string())
+            .collect(),
+        _ => channel_names.to_vec(),
+    };
+    if (num_channels < 3) || (channel_names.len() != num_channels) {
+        return Err(format!(
+            "cannot write image with {} channel(s)",
+            num_channels
+        ));
+    }
+
+    let mut channels = Vec::new();
+    for (c, name) in channel_names.iter().enumerate() {
+        let sample_data = match pixel_block.data_type() {
+            DataType::Half16 => FlatSamples::F16(
+                pixel_block
+                    .as_slice_f16()
+                    .iter()
+                    .skip(c)
+                    .step_by(num_channels)
+                    .map(|v| exr::prelude::f16::from_bits(v.to_bits()))
+                    .collect(),
+            ),
+            _ => FlatSamples::F32(
+                pixel_block
+                    .as_slice_f32()
+                    .iter()
+                    .skip(c)
+                    .step_by(num_channels)
+                    .copied()
+                    .collect(),
+            ),
+        };
+        channels.push(AnyChannel::new(name.as_str(), sample_data));
+    }
+
+    let mut layer_attributes = LayerAttributes {
+        layer_position: Vec2(image.data_window.min_x, image.data_window.min_y),
+        ..Default::default()
+    };
+    layer_attributes.other.insert(
+        Text::from(COLOR_SPACE_ATTR_NAME),
+        AttributeValue::Text(Text::from(image.spec.color_space().as_str())),
+    );
+    let encoding = Encoding {
+        compression: exr_compression(compress),
+        blocks: Blocks::ScanLines,
+        line_order: LineOrder::Increasing,
+    };
+    let layer = Layer::new(
+        (width, height),
+        layer_attributes,
+        encoding,
+        AnyChannels::sort(channels.into()),
+    );
+
+    let mut exr_image = Image::from_layer(layer);
+    write_metadata(
+        image.spec.metadata(),
+        &mut exr_image.attributes,
+        &mut exr_image.layer_data.attributes,
+    );
+    let display_window = image.display_window;
+    exr_image.attributes.display_window = IntegerBounds::new(
+        (display_window.min_x, display_window.min_y),
+        (
+            (display_window.max_x - display_window.min_x).max(0) as usize,
+            (display_window.max_y - display_window.min_y).max(0) as usize,
+        ),
+    );
+    exr_image.attributes.pixel_aspect = image.spec.pixel_aspect();
+    exr_image.write().to_file(path).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cxxbridge::ffi::JpegChromaSubSampling;
+
+    fn test_image(pixel_block: PixelBlock) -> ImageShared {
+        let data_window = BBox2Di::new(-2, 3, pixel_block.width() - 2, pixel_block.height() + 3);
+        ImageShared {
+            pixel_block: Box::new(pixel_block),
+            spec: ImageSpec::new(),
+            display_window: BBox2Di::new(0, 0, 8, 6),
+            data_window,
+        }
+    }
+
+    fn test_compression(exr_compression: ExrCompression) -> ImageCompression {
+        ImageCompression {
+            exr_compression,
+            exr_dwa_compression_level: 45,
+            png_compression_level: 6,
+            jpeg_compression_level: 90,
+            jpeg_subsampling: JpegChromaSubSampling::Default,
+            jpeg_progressive: false,
+        }
+    }
+
+    fn round_trip(
+        file_name: &str,
+        image: &ImageShared,
+        exr_compression: ExrCompression,
+    ) -> ImageShared {
+        let path = std::env::temp_dir().join(file_name);
+        let path = path.to_str().unwrap();
+        write_image(path, image, &test_compression(exr_compression)).unwrap();
+        assert_eq!(read_image_num_channels(path).unwrap(), 4);
+        let image_read = read_image(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        image_read
+    }
+
+    #[test]
+    fn test_round_trip_f16() {
+        let values: Vec<f16> = (0..(4 * 3 * 4))
+            .map(|v| f16::from_f32(v as f32 * 0.25))
+            .collect();
+        let blocksize = BlockSize::new(4, 3, 4);
+        let pixel_block = PixelBlock::from_datablock(blocksize, DataBlock::from_slice_f16(&values));
+        let image = test_image(pixel_block);
+
+        let lossless = [
+            ExrCompression::None,
+            ExrCompression::Rle,
+            ExrCompression::Zip,
+            ExrCompression::ZipScanline,
+            ExrCompression::Piz,
+        ];
+        for exr_compression in lossless.iter() {
+            let image_read = round_trip("ocg_exrio_test_f16.exr", &image, *exr_compression);
+            assert_eq!(image_read.pixel_block.data_type(), DataType::Half16);
+            assert_eq!(image_read.pixel_block.as_slice_f16(), &values[..]);
+            assert_eq!(image_read.data_window, image.data_window);
+            assert_eq!(image_read.display_window, image.display_window);
+            assert_eq!(image_read.spec.color_space(), "Linear");
+        }
+    }
+
+    #[test]
+    fn test_round_trip_f32() {
+        let values: Vec<f32> = (0..(4 * 3 * 3)).map(|v| v as f32 * 0.1).collect();
+        let blocksize = BlockSize::new(4, 3, 3);
+        let pixel_block = PixelBlock::from_datablock(blocksize, DataBlock::from_slice_f32(&values));
+        let image = test_image(pixel_block);
+
+        let path = std::env::temp_dir().join("ocg_exrio_test_f32.exr");
+        let path = path.to_str().unwrap();
+        write_image(path, &image, &test_compression(ExrCompression::Zip)).unwrap();
+        assert_eq!(read_image_num_channels(path).unwrap(), 3);
+        let image_read = read_image(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(image_read.pixel_block.data_type(), DataType::Float32);
+        assert_eq!(image_read.pixel_block.num_channels(), 3);
+        assert_eq!(image_read.pixel_block.as_slice_f32(), &values[..]);
+        assert_eq!(image_read.data_window, image.data_window);
+        assert_eq!(image_read.display_window, image.display_window);
+    }
+
+    #[test]
+    fn test_round_trip_channels() {
+        let values: Vec<f32> = (0..(4 * 3 * 6)).map(|v| v as f32 * 0.5).collect();
+        let blocksize = BlockSize::new(4, 3, 6);
+        let pixel_block = PixelBlock::from_datablock(blocksize, DataBlock::from_slice_f32(&values));
+        let image = test_image(pixel_block);
+        let channel_names: Vec<String> = ["R", "G", "B", "A", "motion.u", "motion.v"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let path = std::env::temp_dir().join("ocg_exrio_test_channels.exr");
+        let path = path.to_str().unwrap();
+        let compress = test_compression(ExrCompression::Zip);
+        write_image_channels(path, &image, &channel_names, &compress).unwrap();
+        let mut file_channel_names = read_image_channel_names(path).unwrap();
+        file_channel_names.sort();
+        let mut expected_channel_names = channel_names.clone();
+        expected_channel_names.sort();
+        assert_eq!(file_channel_names, expected_channel_names);
+
+        // The RGBA channels are the image.
+        let image_read = read_image(path).unwrap();
+        assert_eq!(image_read.pixel_block.num_channels(), 4);
+        let rgba: Vec<f32> = values
+            .chunks_exact(6)
+            .flat_map(|pixel| pixel[..4].to_vec())
+            .collect();
+        assert_eq!(image_read.pixel_block.as_slice_f32(), &rgba[..]);
+
+        // The layer channels are read in the order given.
+        let layer_channel_names = vec!["motion.v".to_string(), "motion.u".to_string()];
+        let layer_read = read_image_channels(path, &layer_channel_names).unwrap();
+        let motion: Vec<f32> = values
+            .chunks_exact(6)
+            .flat_map(|pixel| vec![pixel[5], pixel[4]])
+            .collect();
+        assert_eq!(layer_read.pixel_block.data_type(), DataType::Float32);
+        assert_eq!(layer_read.pixel_block.as_slice_f32(), &motion[..]);
+        assert_eq!(layer_read.data_window, image.data_window);
+
+        // The image and all layers can be read together; layers with
+        // unknown channels are skipped.
+        let missing_channel_names = vec!["depth.Z".to_string()];
+        let layer_channel_names = [layer_channel_names, missing_channel_names];
+        let (image_read, layers_read) =
+            read_image_layers(path, true, &layer_channel_names).unwrap();
+        let image_read = image_read.unwrap();
+        assert_eq!(image_read.pixel_block.as_slice_f32(), &rgba[..]);
+        assert_eq!(layers_read.len(), 2);
+        let layer_read = layers_read[0].as_ref().unwrap();
+        assert_eq!(layer_read.pixel_block.as_slice_f32(), &motion[..]);
+        assert!(layers_read[1].is_none());
+
+        let (image_read, layers_read) =
+            read_image_layers(path, false, &layer_channel_names).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert!(image_read.is_none());
+        assert!(layers_read[0].is_some());
+    }
+
+    #[test]
+    fn test_round_trip_metadata() {
+        let values: Vec<f32> = (0..(4 * 3 * 4)).map(|v| v as f32).collect();
+        let blocksize = BlockSize::new(4, 3, 4);
+        let pixel_block = PixelBlock::from_datablock(blocksize, DataBlock::from_slice_f32(&values));
+        let mut image = test_image(pixel_block);
+        let time_code = TimeCode {
+            hours: 1,
+            minutes: 2,
+            seconds: 3,
+            frame: 4,
+            ..Default::default()
+        };
+        let time = time_code.pack_time_as_tv60_u32().unwrap() as i32;
+        let matrix: Vec<f32> = (0..16).map(|v| v as f32 * 0.5).collect();
+        image.spec.set_metadata(vec![
+            ImageMetadata::new_string("studio:shot", "sh010"),
+            ImageMetadata::new_string("ImageDescription", "plate"),
+            ImageMetadata::new_int(TIME_CODE_METADATA_NAME, vec![time, 0]),
+            ImageMetadata::new_int("studio:size", vec![1920, 1080]),
+            ImageMetadata::new_float("studio:focal", vec![35.0]),
+            ImageMetadata::new_float("worldtocamera", matrix.clone()),
+            // Cannot be stored in an EXR header.
+            ImageMetadata::new_int("studio:list", vec![1, 2, 3, 4]),
+            ImageMetadata::new_string("channels", "RGBA"),
+        ]);
+
+        let path = std::env::temp_dir().join("ocg_exrio_test_metadata.exr");
+        let path = path.to_str().unwrap();
+        write_image(path, &image, &test_compression(ExrCompression::Zip)).unwrap();
+        let image_read = read_image(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let spec = &image_read.spec;
+        let names: Vec<&str> = spec.metadata().iter().map(|m| m.name()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "ImageDescription",
+                "worldtocamera",
+                TIME_CODE_METADATA_NAME,
+                "studio:focal",
+                "studio:shot",
+                "studio:size",
+            ]
+        );
+        let find = |name| spec.find_metadata(name).unwrap();
+        assert_eq!(find("ImageDescription").string_value(), "plate");
+        assert_eq!(find(TIME_CODE_METADATA_NAME).int_values(), &[time, 0]);
+        assert_eq!(find("studio:focal").float_values(), &[35.0]);
+        assert_eq!(find("studio:shot").string_value(), "sh010");
+        assert_eq!(find("studio:size").int_values(), &[1920, 1080]);
+        assert_eq!(find("worldtocamera").float_values(), &matrix[..]);
+        assert_eq!(spec.color_space(), "Linear");
+    }
+
+    #[test]
+    fn test_read_errors() {
+        assert!(is_exr_path("/path/to/file.0001.EXR"));
+        assert!(read_image("/does/not/exist.exr").is_err());
+        assert!(read_image_num_channels("/does/not/exist.exr").is_err());
+    }
+}
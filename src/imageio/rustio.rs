@@ -0,0 +1,677 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+// Read and write images with pure-Rust decoders and encoders (the
+// "image" crate), without the OpenImageIO C++ library.
+//
+// PNG and TIFF images can be read and written with 8-bit and
+// 16-bit channels. JPEG and TGA images can be read. OpenEXR images
+// are read and written with the "exr" crate (see 'exrio').
+//
+// The EXIF orientation of JPEG and TIFF images is read into the image
+// spec, the same as the OpenImageIO "Orientation" attribute.
+
+use image;
+use image::codecs::jpeg::JpegDecoder;
+use image::codecs::png::CompressionType;
+use image::codecs::png::FilterType;
+use image::codecs::png::PngDecoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::tga::TgaDecoder;
+use image::codecs::tiff::TiffDecoder;
+use image::codecs::tiff::TiffEncoder;
+use image::ColorType;
+use image::DynamicImage;
+use image::GenericImageView;
+use image::ImageDecoder;
+use image::ImageEncoder;
+use image::ImageFormat;
+use log::debug;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Cursor;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::path::Path;
+
+use crate::cxxbridge::ffi::BBox2Di;
+use crate::cxxbridge::ffi::BlockSize;
+use crate::cxxbridge::ffi::DataType;
+use crate::cxxbridge::ffi::ImageCompression;
+use crate::cxxbridge::ffi::ImageOrientation;
+use crate::cxxbridge::ffi::ImageShared;
+use crate::cxxbridge::ffi::ImageSpec;
+use crate::imageio::exrio;
+use crate::imagelayer::RGBA_CHANNEL_NAMES;
+use crate::pixelblock::datablock::DataBlock;
+use crate::pixelblock::pixelblock::PixelBlock;
+use crate::pixelblock::utils::stride_num_channels;
+
+/// Copy 'pixels' with 'num_channels' into a buffer with
+/// 'padded_num_channels', filling the extra channels with
+/// 'fill_value'. This matches the channel padding used by the
+/// OpenImageIO reader.
+fn pad_channels<T: Copy>(
+    pixels: &[T],
+    num_channels: usize,
+    padded_num_channels: usize,
+    fill_value: T,
+) -> Vec<T> {
+    if num_channels == padded_num_channels {
+        return pixels.to_vec();
+    }
+    let pixel_count = pixels.len() / num_channels;
+    let mut padded_pixels = vec![fill_value; pixel_count * padded_num_channels];
+    for (src, dst) in pixels
+        .chunks_exact(num_channels)
+        .zip(padded_pixels.chunks_exact_mut(padded_num_channels))
+    {
+        dst[..num_channels].copy_from_slice(src);
+    }
+    padded_pixels
+}
+
+/// The number of channels of an image read with 'color_type'; gray
+/// images are expanded to RGB, and gray-alpha images to RGBA.
+fn expanded_num_channels(color_type: ColorType) -> i32 {
+    match color_type.has_alpha() {
+        true => 4,
+        false => 3,
+    }
+}
+
+/// The EXIF "Orientation" tag.
+const EXIF_ORIENTATION_TAG: u16 = 0x0112;
+
+/// The EXIF (TIFF) data type of 16-bit unsigned integers.
+const EXIF_SHORT_TYPE: u16 = 3;
+
+/// Read the "Orientation" tag (1 to 8) of the first IFD of TIFF
+/// structured data, as stored in TIFF files and JPEG EXIF blocks.
+fn tiff_orientation<R: Read + Seek>(reader: &mut R) -> Option<i32> {
+    let mut header = [0; 8];
+    reader.read_exact(&mut header).ok()?;
+    let little_endian = match &header[0..2] {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let read_u16 = |bytes: &[u8]| match little_endian {
+        true => u16::from_le_bytes([bytes[0], bytes[1]]),
+        false => u16::from_be_bytes([bytes[0], bytes[1]]),
+    };
+    let read_u32 = |bytes: &[u8]| match little_endian {
+        true => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        false => u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+    };
+    if read_u16(&header[2..4]) != 42 {
+        return None;
+    }
+
+    let ifd_offset = read_u32(&header[4..8]);
+    reader.seek(SeekFrom::Start(ifd_offset as u64)).ok()?;
+    let mut count = [0; 2];
+    reader.read_exact(&mut count).ok()?;
+    for _ in 0..read_u16(&count) {
+        let mut entry = [0; 12];
+        reader.read_exact(&mut entry).ok()?;
+        let tag = read_u16(&entry[0..2]);
+        let data_type = read_u16(&entry[2..4]);
+        if (tag == EXIF_ORIENTATION_TAG) && (data_type == EXIF_SHORT_TYPE) {
+            let value = read_u16(&entry[8..10]) as i32;
+            return match value {
+                1..=8 => Some(value),
+                _ => None,
+            };
+        }
+    }
+    None
+}
+
+/// Read the "Orientation" tag (1 to 8) of the EXIF block of a JPEG
+/// file.
+fn jpeg_orientation<R: Read>(reader: &mut R) -> Option<i32> {
+    let mut marker = [0; 2];
+    reader.read_exact(&mut marker).ok()?;
+    if marker != [0xFF, 0xD8] {
+        return None;
+    }
+    loop {
+        reader.read_exact(&mut marker).ok()?;
+        // The EXIF block is before the start of the image data
+        // (SOS) or the end of the image (EOI).
+        if (marker[0] != 0xFF) || (marker[1] == 0xDA) || (marker[1] == 0xD9) {
+            return None;
+        }
+        let mut length = [0; 2];
+        reader.read_exact(&mut length).ok()?;
+        let length = (u16::from_be_bytes(length) as usize).checked_sub(2)?;
+        let mut data = vec![0; length];
+        reader.read_exact(&mut data).ok()?;
+        if (marker[1] == 0xE1) && data.starts_with(b"Exif\0\0") {
+            return tiff_orientation(&mut Cursor::new(&data[6..]));
+        }
+    }
+}
+
+/// Read the EXIF orientation of a JPEG or TIFF file, or Normal if
+/// the file has no orientation.
+fn read_orientation(path: &str, format: ImageFormat) -> ImageOrientation {
+    let mut reader = match open_file(path) {
+        Ok(value) => value,
+        Err(_) => return ImageOrientation::Normal,
+    };
+    let orientation = match format {
+        ImageFormat::Jpeg => jpeg_orientation(&mut reader),
+        ImageFormat::Tiff => tiff_orientation(&mut reader),
+        _ => None,
+    };
+    match orientation {
+        // The ImageOrientation values are the EXIF values minus one.
+        Some(value) => ImageOrientation::from(value - 1),
+        None => ImageOrientation::Normal,
+    }
+}
+
+fn image_format(path: &str) -> Result<ImageFormat, String> {
+    ImageFormat::from_path(path).map_err(|e| format!("unknown image format: {}", e))
+}
+
+fn open_file(path: &str) -> Result<BufReader<File>, String> {
+    let file = File::open(path).map_err(|e| format!("failed to open file: {}", e))?;
+    Ok(BufReader::new(file))
+}
+
+/// Read the number of channels in an image file, without reading
+/// any pixels.
+pub fn read_image_num_channels(path: &str) -> Result<i32, String> {
+    if exrio::is_exr_path(path) {
+        return exrio::read_image_num_channels(path);
+    }
+    let reader = open_file(path)?;
+    let color_type = match image_format(path)? {
+        ImageFormat::Png => PngDecoder::new(reader).map(|d| d.color_type()),
+        ImageFormat::Tiff => TiffDecoder::new(reader).map(|d| d.color_type()),
+        ImageFormat::Jpeg => JpegDecoder::new(reader).map(|d| d.color_type()),
+        ImageFormat::Tga => TgaDecoder::new(reader).map(|d| d.color_type()),
+        format => return Err(format!("unsupported image format: {:?}", format)),
+    }
+    .map_err(|e| e.to_string())?;
+    Ok(expanded_num_channels(color_type))
+}
+
+/// Read the data and display windows and the orientation of an image
+/// file, without reading any pixels. The pixel block is empty.
+pub fn read_image_header(path: &str) -> Result<ImageShared, String> {
+    if exrio::is_exr_path(path) {
+        return exrio::read_image_header(path);
+    }
+    let format = image_format(path)?;
+    let reader = open_file(path)?;
+    let (width, height) = match format {
+        ImageFormat::Png => PngDecoder::new(reader).map(|d| d.dimensions()),
+        ImageFormat::Tiff => TiffDecoder::new(reader).map(|d| d.dimensions()),
+        ImageFormat::Jpeg => JpegDecoder::new(reader).map(|d| d.dimensions()),
+        ImageFormat::Tga => TgaDecoder::new(reader).map(|d| d.dimensions()),
+        format => return Err(format!("unsupported image format: {:?}", format)),
+    }
+    .map_err(|e| e.to_string())?;
+
+    let mut spec = ImageSpec::new();
+    spec.set_orientation(read_orientation(path, format));
+    let display_window = BBox2Di::new(0, 0, width as i32, height as i32);
+    Ok(ImageShared {
+        pixel_block: Box::new(PixelBlock::empty(DataType::Float32)),
+        spec,
+        display_window,
+        data_window: display_window,
+    })
+}
+
+/// Read the names of all channels in an image file, without reading
+/// any pixels.
+///
+/// Only EXR files have named channels, other files use R, G, B and A.
+pub fn read_image_channel_names(path: &str) -> Result<Vec<String>, String> {
+    if exrio::is_exr_path(path) {
+        return exrio::read_image_channel_names(path);
+    }
+    let num_channels = read_image_num_channels(path)?;
+    Ok(RGBA_CHANNEL_NAMES
+        .iter()
+        .take(num_channels as usize)
+        .map(|s| s.to_string())
+        .collect())
+}
+
+/// Read the named channels of an image file into a Float32 image.
+///
+/// Only EXR files have named channels.
+pub fn read_image_channels(path: &str, channel_names: &[String]) -> Result<ImageShared, String> {
+    match exrio::is_exr_path(path) {
+        true => exrio::read_image_channels(path, channel_names),
+        false => Err("only EXR images have named channels".to_string()),
+    }
+}
+
+/// Read an image file (if 'with_image' is true) and each list of
+/// named channels (for image layers), reading the file only once.
+///
+/// Only EXR files have named channels, so layers of other files are
+/// always None.
+pub fn read_image_layers(
+    path: &str,
+    with_image: bool,
+    layer_channel_names: &[Vec<String>],
+) -> Result<(Option<ImageShared>, Vec<Option<ImageShared>>), String> {
+    if exrio::is_exr_path(path) {
+        return exrio::read_image_layers(path, with_image, layer_channel_names);
+    }
+    let image = match with_image {
+        true => Some(read_image(path)?),
+        false => None,
+    };
+    Ok((image, layer_channel_names.iter().map(|_| None).collect()))
+}
+
+/// Read an image file into a new image.
+///
+/// RGB and RGBA images are read as they are, gray images are
+/// expanded to RGB, and gray-alpha images to RGBA. The pixels keep
+/// the data type of the file (UInt8 or UInt16), and the color space
+/// is "sRGB". Alpha is not premultiplied, so 'unassociated_alpha' is
+/// set for RGBA images.
+///
+/// The "image" crate only decodes 8-bit and 16-bit integer pixels;
+/// floating-point (EXR) images are read by 'exrio' as Half16 or
+/// Float32.
+pub fn read_image(path: &str) -> Result<ImageShared, String> {
+    if exrio::is_exr_path(path) {
+        return exrio::read_image(path);
+    }
+    let img = image::io::Reader::open(path)
+        .map_err(|e| format!("failed to open file: {}", e))?
+        .with_guessed_format()
+        .map_err(|e| format!("failed to read file: {}", e))?
+        .decode()
+        .map_err(|e| e.to_string())?;
+    let width = img.width() as i32;
+    let height = img.height() as i32;
+
+    let num_channels = expanded_num_channels(img.color());
+    let (data_type, pixels) = match img {
+        DynamicImage::ImageRgb8(buffer) => {
+            (DataType::UInt8, DataBlockPixels::UInt8(buffer.into_raw()))
+        }
+        DynamicImage::ImageRgba8(buffer) => {
+            (DataType::UInt8, DataBlockPixels::UInt8(buffer.into_raw()))
+        }
+        DynamicImage::ImageRgb16(buffer) => {
+            (DataType::UInt16, DataBlockPixels::UInt16(buffer.into_raw()))
+        }
+        DynamicImage::ImageRgba16(buffer) => {
+            (DataType::UInt16, DataBlockPixels::UInt16(buffer.into_raw()))
+        }
+        other @ DynamicImage::ImageLuma16(_) | other @ DynamicImage::ImageLumaA16(_) => {
+            match num_channels {
+                3 => (
+                    DataType::UInt16,
+                    DataBlockPixels::UInt16(other.into_rgb16().into_raw()),
+                ),
+                _ => (
+                    DataType::UInt16,
+                    DataBlockPixels::UInt16(other.into_rgba16().into_raw()),
+                ),
+            }
+        }
+        other => match num_channels {
+            3 => (
+                DataType::UInt8,
+                DataBlockPixels::UInt8(other.into_rgb8().into_raw()),
+            ),
+            _ => (
+                DataType::UInt8,
+                DataBlockPixels::UInt8(other.into_rgba8().into_raw()),
+            ),
+        },
+    };
+    debug!(
+        "Read {}x{} c={} data_type={:?}",
+        width, height, num_channels, data_type
+    );
+
+    let padded_num_channels = stride_num_channels(num_channels, data_type);
+    let datablock = match pixels {
+        DataBlockPixels::UInt8(values) => DataBlock::from_slice_u8(&pad_channels(
+            &values,
+            num_channels as usize,
+            padded_num_channels,
+            u8::MAX,
+        )),
+        DataBlockPixels::UInt16(values) => DataBlock::from_slice_u16(&pad_channels(
+            &values,
+            num_channels as usize,
+            padded_num_channels,
+            u16::MAX,
+        )),
+    };
+    let blocksize = BlockSize::new(width, height, padded_num_channels as i32);
+    let pixel_block = PixelBlock::from_datablock(blocksize, datablock);
+
+    let mut spec = ImageSpec::new();
+    spec.set_color_space("sRGB".to_string());
+    spec.set_unassociated_alpha(num_channels == 4);
+    if let Ok(format) = image_format(path) {
+        spec.set_orientation(read_orientation(path, format));
+    }
+
+    let display_window = BBox2Di::new(0, 0, width, height);
+    Ok(ImageShared {
+        pixel_block: Box::new(pixel_block),
+        spec,
+        display_window,
+        data_window: display_window,
+    })
+}
+
+/// The pixels of an image, in the data type of the file.
+enum DataBlockPixels {
+    UInt8(Vec<u8>),
+    UInt16(Vec<u16>),
+}
+
+fn png_compression_type(compression_level: i32) -> CompressionType {
+    // The same range as the OpenImageIO "png:compressionLevel"
+    // attribute; 0 to 9.
+    match compression_level {
+        i32::MIN..=2 => CompressionType::Fast,
+        7..=i32::MAX => CompressionType::Best,
+        _ => CompressionType::Default,
+    }
+}
+
+/// Write the pixels of 'image' to a PNG or TIFF file.
+///
+/// UInt8 pixels are written with 8-bit channels, and all other data
+/// types are written with 16-bit channels. Pixel values are not
+/// color converted. PNG and TIFF files have no data window, so only
+/// the pixels inside the data window are written.
+pub fn write_image(
+    path: &str,
+    image: &ImageShared,
+    compress: &ImageCompression,
+) -> Result<(), String> {
+    write_image_channels(path, image, &[], compress)
+}
+
+/// Write the pixels of 'image' to an image file, with a name for
+/// each channel.
+///
+/// Only EXR files can use 'channel_names', other files must use the
+/// default channel names (an empty list).
+pub fn write_image_channels(
+    path: &str,
+    image: &ImageShared,
+    channel_names: &[String],
+    compress: &ImageCompression,
+) -> Result<(), String> {
+    if exrio::is_exr_path(path) {
+        return exrio::write_image_channels(path, image, channel_names, compress);
+    }
+    if !channel_names.is_empty() {
+        return Err("only EXR images can have named channels".to_string());
+    }
+    let format = image_format(path)?;
+    let pixel_block = &image.pixel_block;
+    let width = pixel_block.width() as u32;
+    let height = pixel_block.height() as u32;
+    let num_channels = pixel_block.num_channels();
+
+    let mut pixel_block_out = (**pixel_block).clone();
+    let use_8_bit = pixel_block.data_type() == DataType::UInt8;
+    if !use_8_bit {
+        pixel_block_out.convert_into_data_type(DataType::UInt16);
+    }
+    let color_type = match (num_channels, use_8_bit) {
+        (3, true) => ColorType::Rgb8,
+        (4, true) => ColorType::Rgba8,
+        (3, false) => ColorType::Rgb16,
+        (4, false) => ColorType::Rgba16,
+        _ => {
+            return Err(format!(
+                "cannot write image with {} channel(s)",
+                num_channels
+            ))
+        }
+    };
+    let bytes: Vec<u8> = match use_8_bit {
+        true => pixel_block_out.as_slice_u8().to_vec(),
+        false => pixel_block_out
+            .as_slice_u16()
+            .iter()
+            .flat_map(|value| value.to_ne_bytes().to_vec())
+            .collect(),
+    };
+
+    let file =
+        File::create(Path::new(path)).map_err(|e| format!("failed to create file: {}", e))?;
+    let writer = BufWriter::new(file);
+    let result = match format {
+        ImageFormat::Png => PngEncoder::new_with_quality(
+            writer,
+            png_compression_type(compress.png_compression_level),
+            FilterType::Sub,
+        )
+        .write_image(&bytes, width, height, color_type),
+        ImageFormat::Tiff => {
+            TiffEncoder::new(writer).write_image(&bytes, width, height, color_type)
+        }
+        format => {
+            return Err(format!(
+                "unsupported image format for writing: {:?}",
+                format
+            ))
+        }
+    };
+    result.map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_image(pixel_block: PixelBlock) -> ImageShared {
+        let display_window = BBox2Di::new(0, 0, pixel_block.width(), pixel_block.height());
+        ImageShared {
+            pixel_block: Box::new(pixel_block),
+            spec: ImageSpec::new(),
+            display_window,
+            data_window: display_window,
+        }
+    }
+
+    fn test_compression() -> ImageCompression {
+        ImageCompression {
+            exr_compression: crate::cxxbridge::ffi::ExrCompression::Default,
+            exr_dwa_compression_level: 45,
+            png_compression_level: 6,
+            jpeg_compression_level: 90,
+            jpeg_subsampling: crate::cxxbridge::ffi::JpegChromaSubSampling::Default,
+            jpeg_progressive: false,
+        }
+    }
+
+    fn round_trip(file_name: &str, image: &ImageShared) -> ImageShared {
+        let path = std::env::temp_dir().join(file_name);
+        let path = path.to_str().unwrap();
+        write_image(path, image, &test_compression()).unwrap();
+        let image_read = read_image(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        image_read
+    }
+
+    #[test]
+    fn test_round_trip_u8() {
+        let values: Vec<u8> = (0..(4 * 3 * 4)).map(|v| (v * 5) as u8).collect();
+        let blocksize = BlockSize::new(4, 3, 4);
+        let pixel_block = PixelBlock::from_datablock(blocksize, DataBlock::from_slice_u8(&values));
+        let image = test_image(pixel_block);
+
+        for file_name in &["ocg_rustio_test_u8.png", "ocg_rustio_test_u8.tif"] {
+            let image_read = round_trip(file_name, &image);
+            assert_eq!(image_read.pixel_block.data_type(), DataType::UInt8);
+            assert_eq!(image_read.pixel_block.as_slice_u8(), &values[..]);
+            assert_eq!(image_read.data_window, image.data_window);
+            assert_eq!(image_read.display_window, image.display_window);
+            assert!(image_read.spec.unassociated_alpha());
+        }
+    }
+
+    #[test]
+    fn test_round_trip_u16() {
+        let values: Vec<u16> = (0..(4 * 3 * 4)).map(|v| (v * 1000) as u16).collect();
+        let blocksize = BlockSize::new(4, 3, 4);
+        let pixel_block = PixelBlock::from_datablock(blocksize, DataBlock::from_slice_u16(&values));
+        let image = test_image(pixel_block);
+
+        for file_name in &["ocg_rustio_test_u16.png", "ocg_rustio_test_u16.tif"] {
+            let image_read = round_trip(file_name, &image);
+            assert_eq!(image_read.pixel_block.data_type(), DataType::UInt16);
+            assert_eq!(image_read.pixel_block.as_slice_u16(), &values[..]);
+        }
+    }
+
+    #[test]
+    fn test_read_gray() {
+        // Gray images are expanded to RGB.
+        let values: Vec<u8> = (0..(4 * 3)).map(|v| (v * 20) as u8).collect();
+        let path = std::env::temp_dir().join("ocg_rustio_test_gray.png");
+        image::GrayImage::from_raw(4, 3, values.clone())
+            .unwrap()
+            .save(&path)
+            .unwrap();
+        let path = path.to_str().unwrap();
+        assert_eq!(read_image_num_channels(path).unwrap(), 3);
+        let image_read = read_image(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        // UInt8 pixels are padded to 4 channels.
+        let rgb: Vec<u8> = values.iter().flat_map(|v| vec![*v, *v, *v, 255]).collect();
+        assert_eq!(image_read.pixel_block.data_type(), DataType::UInt8);
+        assert_eq!(image_read.pixel_block.as_slice_u8(), &rgb[..]);
+        assert!(!image_read.spec.unassociated_alpha());
+
+        // Gray-alpha images are expanded to RGBA, keeping 16-bits.
+        let values: Vec<u16> = (0..(4 * 3 * 2)).map(|v| (v * 1000) as u16).collect();
+        let path = std::env::temp_dir().join("ocg_rustio_test_gray_alpha.png");
+        image::ImageBuffer::<image::LumaA<u16>, Vec<u16>>::from_raw(4, 3, values.clone())
+            .unwrap()
+            .save(&path)
+            .unwrap();
+        let path = path.to_str().unwrap();
+        assert_eq!(read_image_num_channels(path).unwrap(), 4);
+        let image_read = read_image(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        let rgba: Vec<u16> = values
+            .chunks_exact(2)
+            .flat_map(|v| vec![v[0], v[0], v[0], v[1]])
+            .collect();
+        assert_eq!(image_read.pixel_block.data_type(), DataType::UInt16);
+        assert_eq!(image_read.pixel_block.as_slice_u16(), &rgba[..]);
+        assert!(image_read.spec.unassociated_alpha());
+    }
+
+    /// TIFF structured data with a single "Orientation" entry.
+    fn tiff_data(little_endian: bool, orientation: u16) -> Vec<u8> {
+        let u16_bytes = |v: u16| match little_endian {
+            true => v.to_le_bytes(),
+            false => v.to_be_bytes(),
+        };
+        let u32_bytes = |v: u32| match little_endian {
+            true => v.to_le_bytes(),
+            false => v.to_be_bytes(),
+        };
+        let mut data = Vec::new();
+        data.extend_from_slice(match little_endian {
+            true => b"II",
+            false => b"MM",
+        });
+        data.extend_from_slice(&u16_bytes(42));
+        data.extend_from_slice(&u32_bytes(8));
+        data.extend_from_slice(&u16_bytes(1));
+        data.extend_from_slice(&u16_bytes(EXIF_ORIENTATION_TAG));
+        data.extend_from_slice(&u16_bytes(EXIF_SHORT_TYPE));
+        data.extend_from_slice(&u32_bytes(1));
+        data.extend_from_slice(&u16_bytes(orientation));
+        data.extend_from_slice(&[0, 0]);
+        data
+    }
+
+    #[test]
+    fn test_read_orientation() {
+        for little_endian in &[true, false] {
+            let data = tiff_data(*little_endian, 6);
+            assert_eq!(tiff_orientation(&mut Cursor::new(&data)), Some(6));
+            let data = tiff_data(*little_endian, 9);
+            assert_eq!(tiff_orientation(&mut Cursor::new(&data)), None);
+        }
+
+        // A JPEG file with a comment, then the EXIF block.
+        let exif = [b"Exif\0\0".to_vec(), tiff_data(false, 8)].concat();
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xFE, 0x00, 0x05, b'a', b'b', b'c'];
+        jpeg.extend_from_slice(&[0xFF, 0xE1]);
+        jpeg.extend_from_slice(&((exif.len() + 2) as u16).to_be_bytes());
+        jpeg.extend_from_slice(&exif);
+        jpeg.extend_from_slice(&[0xFF, 0xDA]);
+        assert_eq!(jpeg_orientation(&mut Cursor::new(&jpeg)), Some(8));
+        assert_eq!(
+            ImageOrientation::from(8 - 1),
+            ImageOrientation::Rotated90CounterClockwise
+        );
+
+        // No EXIF block.
+        let jpeg = vec![0xFF, 0xD8, 0xFF, 0xDA];
+        assert_eq!(jpeg_orientation(&mut Cursor::new(&jpeg)), None);
+    }
+
+    #[test]
+    fn test_read_image_header() {
+        let values: Vec<u8> = (0..(4 * 3)).map(|v| (v * 20) as u8).collect();
+        let path = std::env::temp_dir().join("ocg_rustio_test_header.png");
+        image::ImageBuffer::<image::Luma<u8>, Vec<u8>>::from_raw(4, 3, values)
+            .unwrap()
+            .save(&path)
+            .unwrap();
+        let path = path.to_str().unwrap();
+        let image_read = read_image_header(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(image_read.display_window, BBox2Di::new(0, 0, 4, 3));
+        assert_eq!(image_read.data_window, BBox2Di::new(0, 0, 4, 3));
+    }
+
+    #[test]
+    fn test_read_errors() {
+        assert!(read_image("/does/not/exist.png").is_err());
+        assert!(read_image_header("/does/not/exist.png").is_err());
+        assert!(read_image_num_channels("/does/not/exist.png").is_err());
+    }
+}
@@ -0,0 +1,292 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::cxxbridge::ffi::BBox2Di;
+use crate::cxxbridge::ffi::BlockSize;
+use crate::cxxbridge::ffi::DataType;
+use crate::pixelblock::pixelblock::PixelBlock;
+
+/// The channel names of the main image, in PixelBlock channel order.
+pub const RGBA_CHANNEL_NAMES: [&str; 4] = ["R", "G", "B", "A"];
+
+/// Select all layers of an image, when used as a layer name.
+pub const ALL_LAYERS: &str = "*";
+
+/// A named set of image channels carried alongside the RGBA pixels
+/// of a stream, such as "depth" (with the channel "depth.Z") or
+/// "motion" (with the channels "motion.u" and "motion.v").
+///
+/// The channel names are the full names used in the image file.
+/// Layers have their own data window, and the pixels are always
+/// Float32, without padding.
+#[derive(Debug, Clone)]
+pub struct ImageLayer {
+    name: String,
+    channel_names: Vec<String>,
+    data_window: BBox2Di,
+    pixel_block: Arc<PixelBlock>,
+}
+
+impl ImageLayer {
+    pub fn new(
+        name: String,
+        channel_names: Vec<String>,
+        data_window: BBox2Di,
+        pixel_block: Arc<PixelBlock>,
+    ) -> ImageLayer {
+        assert_eq!(channel_names.len() as i32, pixel_block.num_channels());
+        ImageLayer {
+            name,
+            channel_names,
+            data_window,
+            pixel_block,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn channel_names(&self) -> &Vec<String> {
+        &self.channel_names
+    }
+
+    pub fn data_window(&self) -> BBox2Di {
+        self.data_window
+    }
+
+    pub fn pixel_block(&self) -> &Arc<PixelBlock> {
+        &self.pixel_block
+    }
+
+    pub fn size_bytes(&self) -> usize {
+        self.pixel_block.size_bytes()
+    }
+}
+
+impl Hash for ImageLayer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.channel_names.hash(state);
+        self.data_window.hash(state);
+        self.pixel_block.hash(state);
+    }
+}
+
+/// The names of the channels in a layer of an image file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerChannels {
+    pub name: String,
+    pub channel_names: Vec<String>,
+}
+
+/// The layer name of a channel name, such as "motion" for
+/// "motion.u".
+///
+/// Channels without a layer prefix (other than R, G, B and A) are a
+/// layer of their own, so "Z" is the layer "Z".
+pub fn channel_layer_name(channel_name: &str) -> &str {
+    match channel_name.rfind('.') {
+        Some(index) => &channel_name[..index],
+        None => channel_name,
+    }
+}
+
+fn is_rgba_channel(channel_name: &str) -> bool {
+    RGBA_CHANNEL_NAMES.contains(&channel_name)
+}
+
+/// Group the channels of an image file into layers, in the order the
+/// layers first appear.
+///
+/// The R, G, B and A channels are the main image, and are not part
+/// of any layer.
+pub fn find_layers(channel_names: &[String]) -> Vec<LayerChannels> {
+    let mut layers: Vec<LayerChannels> = Vec::new();
+    for channel_name in channel_names {
+        if is_rgba_channel(channel_name) {
+            continue;
+        }
+        let layer_name = channel_layer_name(channel_name);
+        match layers.iter_mut().find(|l| l.name == layer_name) {
+            Some(layer) => layer.channel_names.push(channel_name.clone()),
+            None => layers.push(LayerChannels {
+                name: layer_name.to_string(),
+                channel_names: vec![channel_name.clone()],
+            }),
+        }
+    }
+    layers
+}
+
+/// Split a list of layer names, separated by spaces or commas.
+pub fn split_layer_names(layer_names: &str) -> Vec<&str> {
+    layer_names
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// The layers selected by 'layer_names', in the order of 'layers'.
+///
+/// 'layer_names' is a list of layer names separated by spaces or
+/// commas, or "*" to select all layers.
+pub fn select_layers(layers: Vec<LayerChannels>, layer_names: &str) -> Vec<LayerChannels> {
+    let names = split_layer_names(layer_names);
+    if names.contains(&ALL_LAYERS) {
+        return layers;
+    }
+    layers
+        .into_iter()
+        .filter(|l| names.contains(&l.name.as_str()))
+        .collect()
+}
+
+/// Combine the pixels of an image and its layers into a single
+/// pixel block, with the names of all channels.
+///
+/// The layers are copied into the data window of the image, and
+/// converted to the data type of the image. The image channels are
+/// named R, G, B and A, followed by the layer channels.
+pub fn interleave_layers(
+    pixel_block: &PixelBlock,
+    data_window: BBox2Di,
+    layers: &[ImageLayer],
+) -> (PixelBlock, Vec<String>) {
+    let width = pixel_block.width();
+    let height = pixel_block.height();
+    let data_type = pixel_block.data_type();
+    let image_num_channels = pixel_block.num_channels() as usize;
+
+    let mut channel_names: Vec<String> = RGBA_CHANNEL_NAMES
+        .iter()
+        .take(image_num_channels)
+        .map(|s| s.to_string())
+        .collect();
+    let mut layer_blocks = Vec::new();
+    for layer in layers {
+        let mut layer_block =
+            PixelBlock::from_pixel_block(&layer.pixel_block, layer.data_window, data_window);
+        layer_block.convert_into_data_type(data_type);
+        channel_names.extend(layer.channel_names.iter().cloned());
+        layer_blocks.push(layer_block);
+    }
+
+    let num_channels = channel_names.len();
+    let blocksize = BlockSize::new(width, height, num_channels as i32);
+    let mut out_block = PixelBlock::new(blocksize, data_type);
+    let pixel_count = (width * height) as usize;
+
+    // Copy the channels of each source block, one pixel at a time.
+    let mut channel_offset = 0;
+    let sources = std::iter::once(pixel_block).chain(layer_blocks.iter());
+    for src_block in sources {
+        let src_num_channels = src_block.num_channels() as usize;
+        for i in 0..pixel_count {
+            let src_start = i * src_num_channels;
+            let dst_start = (i * num_channels) + channel_offset;
+            match data_type {
+                DataType::Float32 => {
+                    let src = &src_block.as_slice_f32()[src_start..src_start + src_num_channels];
+                    out_block.as_mut_slice_f32()[dst_start..dst_start + src_num_channels]
+                        .copy_from_slice(src);
+                }
+                DataType::Half16 => {
+                    let src = &src_block.as_slice_f16()[src_start..src_start + src_num_channels];
+                    out_block.as_mut_slice_f16()[dst_start..dst_start + src_num_channels]
+                        .copy_from_slice(src);
+                }
+                DataType::UInt16 => {
+                    let src = &src_block.as_slice_u16()[src_start..src_start + src_num_channels];
+                    out_block.as_mut_slice_u16()[dst_start..dst_start + src_num_channels]
+                        .copy_from_slice(src);
+                }
+                DataType::UInt8 => {
+                    let src = &src_block.as_slice_u8()[src_start..src_start + src_num_channels];
+                    out_block.as_mut_slice_u8()[dst_start..dst_start + src_num_channels]
+                        .copy_from_slice(src);
+                }
+                _ => panic!("Invalid data type: {:?}", data_type),
+            }
+        }
+        channel_offset += src_num_channels;
+    }
+
+    (out_block, channel_names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pixelblock::datablock::DataBlock;
+
+    fn names(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find_layers() {
+        let channel_names = names(&["R", "G", "B", "A", "Z", "motion.u", "motion.v", "depth.Z"]);
+        let layers = find_layers(&channel_names);
+        assert_eq!(layers.len(), 3);
+        assert_eq!(layers[0].name, "Z");
+        assert_eq!(layers[0].channel_names, names(&["Z"]));
+        assert_eq!(layers[1].name, "motion");
+        assert_eq!(layers[1].channel_names, names(&["motion.u", "motion.v"]));
+        assert_eq!(layers[2].name, "depth");
+
+        let selected = select_layers(layers.clone(), "depth, motion");
+        assert_eq!(selected.len(), 2);
+        assert_eq!(selected[0].name, "motion");
+        assert_eq!(selected[1].name, "depth");
+        assert_eq!(select_layers(layers.clone(), "*"), layers);
+        assert!(select_layers(layers, "").is_empty());
+    }
+
+    #[test]
+    fn test_interleave_layers() {
+        let rgba = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let pixel_block =
+            PixelBlock::from_datablock(BlockSize::new(2, 1, 4), DataBlock::from_slice_f32(&rgba));
+        let data_window = BBox2Di::new(0, 0, 2, 1);
+
+        // The layer only covers the second pixel.
+        let depth =
+            PixelBlock::from_datablock(BlockSize::new(1, 1, 1), DataBlock::from_slice_f32(&[9.0]));
+        let layer = ImageLayer::new(
+            "depth".to_string(),
+            names(&["depth.Z"]),
+            BBox2Di::new(1, 0, 2, 1),
+            Arc::new(depth),
+        );
+
+        let (out_block, channel_names) = interleave_layers(&pixel_block, data_window, &[layer]);
+        assert_eq!(channel_names, names(&["R", "G", "B", "A", "depth.Z"]));
+        assert_eq!(out_block.num_channels(), 5);
+        assert_eq!(
+            out_block.as_slice_f32(),
+            &[1.0, 2.0, 3.0, 4.0, 0.0, 5.0, 6.0, 7.0, 8.0, 9.0]
+        );
+    }
+}
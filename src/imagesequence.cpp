@@ -0,0 +1,34 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+// Rust CXX
+#include <rust/cxx.h>
+#include <opencompgraph/_cxxbridge.h>
+
+#include <opencompgraph/imagesequence.h>
+
+namespace open_comp_graph {
+
+rust::Vec<ImageSequence> find_image_sequences(rust::Str directory) noexcept {
+    return internal::find_image_sequences(directory);
+}
+
+} // namespace open_comp_graph
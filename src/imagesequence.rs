@@ -0,0 +1,197 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+use log::{debug, warn};
+use rustc_hash::FxHashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::cxxbridge::ffi::ImageSequence;
+use crate::pathutils;
+
+/// The files of one sequence, before the padding is known.
+#[derive(Debug, Default)]
+struct SequenceFiles {
+    // The frame number and the number of digits in the file name.
+    frames: Vec<(i32, usize)>,
+}
+
+fn create_image_sequence(
+    directory: &Path,
+    prefix: &str,
+    suffix: &str,
+    padding_count: usize,
+    mut frames: Vec<i32>,
+) -> ImageSequence {
+    frames.sort_unstable();
+    frames.dedup();
+    let first_frame = frames[0];
+    let last_frame = frames[frames.len() - 1];
+
+    // Only the gaps between files are visited, so a sequence with a
+    // large gap does not list every missing frame.
+    let missing_frames_spec = frames
+        .windows(2)
+        .filter(|x| x[1] > x[0] + 1)
+        .map(|x| match (x[0] + 1, x[1] - 1) {
+            (start, end) if start == end => format!("{}", start),
+            (start, end) => format!("{}-{}", start, end),
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let file_name = format!("{}{}{}", prefix, "#".repeat(padding_count), suffix);
+    ImageSequence {
+        file_path: directory.join(file_name).to_string_lossy().to_string(),
+        first_frame,
+        last_frame,
+        padding: padding_count as i32,
+        frame_count: frames.len(),
+        missing_frames_spec,
+    }
+}
+
+/// Split the files of a sequence by padding.
+///
+/// Unpadded frame numbers, such as 998, 999 and 1000, are one
+/// sequence using the smallest padding. If the frame numbers cannot
+/// all be written with the same padding, such as "1" and "0001",
+/// each number of digits is a different sequence.
+fn split_by_padding(files: &SequenceFiles) -> Vec<(usize, Vec<i32>)> {
+    let min_padding = files.frames.iter().map(|v| v.1).min().unwrap_or(1);
+    let same_padding = files.frames.iter().all(|(frame, digits)| {
+        let frame_string = pathutils::format_frame(*frame, min_padding);
+        frame_string.trim_start_matches('-').len() == *digits
+    });
+    if same_padding {
+        let frames = files.frames.iter().map(|v| v.0).collect();
+        return vec![(min_padding, frames)];
+    }
+
+    let mut by_padding: FxHashMap<usize, Vec<i32>> = FxHashMap::default();
+    for (frame, digits) in &files.frames {
+        by_padding.entry(*digits).or_default().push(*frame);
+    }
+    by_padding.into_iter().collect()
+}
+
+/// Find the image sequences in 'directory'.
+///
+/// Files are grouped into sequences by the file name with the frame
+/// number removed (see 'pathutils::split_frame_number'). Files
+/// without a frame number are ignored. Sub-directories are not
+/// searched. The sequences are sorted by file path.
+pub fn find_image_sequences(directory: &str) -> Vec<ImageSequence> {
+    debug!("find_image_sequences: {}", directory);
+    let directory = Path::new(directory);
+    let read_dir = match fs::read_dir(directory) {
+        Ok(value) => value,
+        Err(e) => {
+            warn!("Could not read directory: {:?} {}", directory, e);
+            return Vec::new();
+        }
+    };
+
+    // Group by the file name before and after the frame number.
+    let mut groups: FxHashMap<(String, String), SequenceFiles> = FxHashMap::default();
+    for entry in read_dir.filter_map(|v| v.ok()) {
+        if !entry.path().is_file() {
+            continue;
+        }
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let frame_pattern = match pathutils::split_frame_number(&file_name) {
+            Some(value) => value,
+            None => continue,
+        };
+        let pattern = &frame_pattern.pattern;
+        let suffix_index = frame_pattern.frame_index + frame_pattern.padding_count;
+        let key = (
+            pattern[..frame_pattern.frame_index].to_string(),
+            pattern[suffix_index..].to_string(),
+        );
+        groups
+            .entry(key)
+            .or_default()
+            .frames
+            .push((frame_pattern.frame, frame_pattern.padding_count));
+    }
+
+    let mut sequences = Vec::new();
+    for ((prefix, suffix), files) in groups {
+        for (padding_count, frames) in split_by_padding(&files) {
+            sequences.push(create_image_sequence(
+                directory,
+                &prefix,
+                &suffix,
+                padding_count,
+                frames,
+            ));
+        }
+    }
+    sequences.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    sequences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_image_sequence() {
+        let directory = Path::new("images");
+        let frames = vec![1001, 1002, 1005, 1007, 1008, 2_000_000_000];
+        let sequence = create_image_sequence(directory, "file.", ".exr", 4, frames);
+        assert_eq!(sequence.first_frame, 1001);
+        assert_eq!(sequence.last_frame, 2_000_000_000);
+        assert_eq!(sequence.frame_count, 6);
+        assert_eq!(
+            sequence.missing_frames_spec,
+            "1003-1004,1006,1009-1999999999"
+        );
+
+        let frames = vec![3, 1, 2];
+        let sequence = create_image_sequence(directory, "file.", ".exr", 1, frames);
+        assert_eq!(sequence.missing_frames_spec, "");
+    }
+
+    #[test]
+    fn test_split_by_padding() {
+        // Unpadded frame numbers are one sequence.
+        let files = SequenceFiles {
+            frames: vec![(998, 3), (999, 3), (1000, 4)],
+        };
+        assert_eq!(split_by_padding(&files), vec![(3, vec![998, 999, 1000])]);
+
+        // Negative frames do not count the sign as padding.
+        let files = SequenceFiles {
+            frames: vec![(-1, 4), (0, 4), (1, 4)],
+        };
+        assert_eq!(split_by_padding(&files), vec![(4, vec![-1, 0, 1])]);
+
+        // "1" and "0001" cannot use the same padding.
+        let files = SequenceFiles {
+            frames: vec![(1, 1), (1, 4)],
+        };
+        let mut sequences = split_by_padding(&files);
+        sequences.sort();
+        assert_eq!(sequences, vec![(1, vec![1]), (4, vec![1])]);
+    }
+}
@@ -21,6 +21,8 @@
 
 use std::hash::{Hash, Hasher};
 
+use crate::cxxbridge::ffi::ImageMetadata;
+use crate::cxxbridge::ffi::ImageMetadataType;
 use crate::cxxbridge::ffi::ImageOrientation;
 use crate::cxxbridge::ffi::ImageSpec;
 use crate::hashutils;
@@ -34,6 +36,7 @@ impl ImageSpec {
             orientation: ImageOrientation::Normal,
             unassociated_alpha: false,
             dither: 0,
+            metadata: Vec::new(),
         }
     }
 
@@ -84,6 +87,136 @@ impl ImageSpec {
     pub fn set_dither(&mut self, value: i32) {
         self.dither = value;
     }
+
+    pub fn metadata(&self) -> &Vec<ImageMetadata> {
+        &self.metadata
+    }
+
+    pub fn set_metadata(&mut self, value: Vec<ImageMetadata>) {
+        self.metadata = value;
+    }
+
+    pub fn find_metadata(&self, name: &str) -> Option<&ImageMetadata> {
+        self.metadata.iter().find(|m| m.name == name)
+    }
+
+    /// Add the metadata value, replacing any existing value with the
+    /// same name (keeping the original order).
+    pub fn insert_metadata(&mut self, value: ImageMetadata) {
+        match self.metadata.iter_mut().find(|m| m.name == value.name) {
+            Some(existing) => *existing = value,
+            None => self.metadata.push(value),
+        }
+    }
+
+    /// Remove the named metadata value, returning true if it existed.
+    pub fn remove_metadata(&mut self, name: &str) -> bool {
+        let len = self.metadata.len();
+        self.metadata.retain(|m| m.name != name);
+        self.metadata.len() != len
+    }
+}
+
+impl Default for ImageSpec {
+    fn default() -> ImageSpec {
+        ImageSpec::new()
+    }
+}
+
+impl ImageMetadata {
+    pub fn new_int(name: &str, values: Vec<i32>) -> ImageMetadata {
+        ImageMetadata {
+            name: name.to_string(),
+            data_type: ImageMetadataType::Int,
+            int_values: values,
+            float_values: Vec::new(),
+            string_value: String::new(),
+        }
+    }
+
+    pub fn new_float(name: &str, values: Vec<f32>) -> ImageMetadata {
+        ImageMetadata {
+            name: name.to_string(),
+            data_type: ImageMetadataType::Float,
+            int_values: Vec::new(),
+            float_values: values,
+            string_value: String::new(),
+        }
+    }
+
+    pub fn new_string(name: &str, value: &str) -> ImageMetadata {
+        ImageMetadata {
+            name: name.to_string(),
+            data_type: ImageMetadataType::String,
+            int_values: Vec::new(),
+            float_values: Vec::new(),
+            string_value: value.to_string(),
+        }
+    }
+
+    /// Create a metadata value from text, such as a node attribute.
+    ///
+    /// Int and Float values are separated by whitespace or commas,
+    /// for example "1920 1080" or "0.5, 0.5".
+    pub fn from_text(
+        name: &str,
+        data_type: ImageMetadataType,
+        text: &str,
+    ) -> Result<ImageMetadata, String> {
+        let words = text
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|w| !w.is_empty());
+        match data_type {
+            ImageMetadataType::Int => {
+                let values: Result<Vec<i32>, _> = words.map(|w| w.parse::<i32>()).collect();
+                match values {
+                    Ok(values) if !values.is_empty() => Ok(ImageMetadata::new_int(name, values)),
+                    _ => Err(format!("invalid integer value: {:?}", text)),
+                }
+            }
+            ImageMetadataType::Float => {
+                let values: Result<Vec<f32>, _> = words.map(|w| w.parse::<f32>()).collect();
+                match values {
+                    Ok(values) if !values.is_empty() => Ok(ImageMetadata::new_float(name, values)),
+                    _ => Err(format!("invalid float value: {:?}", text)),
+                }
+            }
+            ImageMetadataType::String => Ok(ImageMetadata::new_string(name, text)),
+            _ => Err(format!("invalid metadata type: {:?}", data_type)),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn data_type(&self) -> ImageMetadataType {
+        self.data_type
+    }
+
+    pub fn int_values(&self) -> &[i32] {
+        &self.int_values
+    }
+
+    pub fn float_values(&self) -> &[f32] {
+        &self.float_values
+    }
+
+    pub fn string_value(&self) -> &str {
+        &self.string_value
+    }
+}
+
+impl Hash for ImageMetadata {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.data_type.hash(state);
+        self.int_values.hash(state);
+        for value in &self.float_values {
+            hashutils::HashableF32::new(*value).hash(state);
+        }
+        self.string_value.hash(state);
+    }
 }
 
 impl Hash for ImageSpec {
@@ -94,5 +227,44 @@ impl Hash for ImageSpec {
         self.orientation.hash(state);
         self.unassociated_alpha.hash(state);
         self.dither.hash(state);
+        // Only hashed when set, so images without metadata keep the
+        // same hash.
+        if !self.metadata.is_empty() {
+            self.metadata.hash(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_from_text() {
+        let value = ImageMetadata::from_text("size", ImageMetadataType::Int, "1920, 1080").unwrap();
+        assert_eq!(value.int_values(), &[1920, 1080]);
+        let value = ImageMetadata::from_text("focal", ImageMetadataType::Float, " 35.5 ").unwrap();
+        assert_eq!(value.float_values(), &[35.5]);
+        let value = ImageMetadata::from_text("note", ImageMetadataType::String, "a, b").unwrap();
+        assert_eq!(value.string_value(), "a, b");
+
+        assert!(ImageMetadata::from_text("size", ImageMetadataType::Int, "1.5").is_err());
+        assert!(ImageMetadata::from_text("size", ImageMetadataType::Int, "").is_err());
+        assert!(ImageMetadata::from_text("focal", ImageMetadataType::Float, "abc").is_err());
+    }
+
+    #[test]
+    fn test_spec_metadata() {
+        let mut spec = ImageSpec::new();
+        spec.insert_metadata(ImageMetadata::new_string("Artist", "a"));
+        spec.insert_metadata(ImageMetadata::new_int("fps", vec![24]));
+        spec.insert_metadata(ImageMetadata::new_string("Artist", "b"));
+        assert_eq!(spec.metadata().len(), 2);
+        assert_eq!(spec.metadata()[0].string_value(), "b");
+        assert_eq!(spec.find_metadata("fps").unwrap().int_values(), &[24]);
+
+        assert!(spec.remove_metadata("Artist"));
+        assert!(!spec.remove_metadata("Artist"));
+        assert!(spec.find_metadata("Artist").is_none());
     }
 }
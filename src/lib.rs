@@ -32,12 +32,17 @@ pub mod cxxbridge;
 pub mod data;
 pub mod deformer;
 pub mod deformutils;
+pub mod diskcache;
+pub mod frameset;
 pub mod geom;
 pub mod graph;
 pub mod graphiter;
+pub mod graphobserver;
 pub mod hashutils;
 pub mod imagebuffer;
 pub mod imageio;
+pub mod imagelayer;
+pub mod imagesequence;
 pub mod imagespec;
 pub mod logger;
 pub mod math;
@@ -46,4 +51,5 @@ pub mod ops;
 pub mod pathutils;
 pub mod pixel;
 pub mod pixelblock;
+pub mod prefetch;
 pub mod stream;
@@ -20,26 +20,36 @@
  */
 
 use log::{debug, warn};
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::time::Instant;
 
 use crate::attrblock::AttrBlock;
 use crate::cache::CacheImpl;
 use crate::cxxbridge::ffi::AttrState;
+use crate::cxxbridge::ffi::NodeErrorKind;
 use crate::cxxbridge::ffi::NodeStatus;
 use crate::cxxbridge::ffi::NodeType;
+use crate::cxxbridge::ffi::ProxyScale;
+use crate::cxxbridge::ffi::ValidationIssue;
+use crate::cxxbridge::ffi::ValidationIssueKind;
 use crate::data::FrameValue;
 use crate::data::HashValue;
 use crate::data::Identifier;
 use crate::data::NodeComputeMode;
+use crate::node::status::NodeStatusInfo;
 use crate::stream::StreamDataImpl;
 
 pub mod crop_image;
+pub mod frame_blend;
 pub mod grade;
 pub mod lens_distort;
 pub mod merge_image;
+pub mod metadata;
 pub mod null;
 pub mod read_image;
 pub mod resample_image;
+pub mod status;
 pub mod traits;
 pub mod transform;
 pub mod viewer;
@@ -49,7 +59,7 @@ pub mod write_image;
 pub struct NodeImpl {
     node_type: NodeType,
     id: Identifier,
-    status: NodeStatus,
+    status: NodeStatusInfo,
     attr_block: Box<dyn AttrBlock>,
     compute: Box<dyn traits::Operation>,
     validate: Box<dyn traits::Validate>,
@@ -71,26 +81,72 @@ impl NodeImpl {
     }
 
     pub fn get_status(&self) -> NodeStatus {
-        debug!("Node.get_status() -> {:?}", self.status);
-        self.status
+        debug!("Node.get_status() -> {:?}", self.status.status());
+        self.status.status()
     }
 
     pub fn get_status_id(&self) -> u8 {
-        debug!("Node.get_status_id() -> {}", self.status.repr);
-        self.status.repr
+        debug!("Node.get_status_id() -> {}", self.status.status().repr);
+        self.status.status().repr
+    }
+
+    pub fn get_error_kind(&self) -> NodeErrorKind {
+        debug!("Node.get_error_kind() -> {:?}", self.status.error_kind());
+        self.status.error_kind()
+    }
+
+    /// A human-readable message explaining why the node has a
+    /// Warning or Error status, or an empty string.
+    pub fn get_status_message(&self) -> &str {
+        self.status.message()
     }
 
     // This method is used to determine "has this node changed?
     // If I re-compute this Node, do I expect a different value?"
-    pub fn hash(&self, frame: FrameValue, inputs: &Vec<HashValue>) -> HashValue {
+    pub fn hash(
+        &self,
+        frame: FrameValue,
+        proxy_scale: ProxyScale,
+        inputs: &Vec<HashValue>,
+    ) -> HashValue {
         let node_type_id = self.get_node_type_id();
-        let value = self
-            .compute
-            .cache_hash(frame, node_type_id, &self.attr_block, inputs);
+        let value =
+            self.compute
+                .cache_hash(frame, proxy_scale, node_type_id, &self.attr_block, inputs);
         debug!("Node.hash(): id={} hash={}", self.id, value);
         value
     }
 
+    /// The frames the input must be evaluated at, to compute this
+    /// node at 'frame', see 'Operation::input_frames'.
+    pub fn input_frames(
+        &self,
+        frame: FrameValue,
+        hash_value: HashValue,
+        cache: &Box<CacheImpl>,
+    ) -> Option<Vec<FrameValue>> {
+        self.compute
+            .input_frames(frame, hash_value, &self.attr_block, cache)
+    }
+
+    /// The frames the input is hashed at, to calculate the hash of
+    /// this node at 'frame', see 'Operation::hash_input_frames'.
+    pub fn hash_input_frames(&self, frame: FrameValue) -> Option<Vec<FrameValue>> {
+        self.compute.hash_input_frames(frame, &self.attr_block)
+    }
+
+    /// The file this node reads at 'frame' (and the proxy scale it
+    /// is read at), if it can be read ahead of time, see
+    /// 'Operation::prefetch_path'.
+    pub fn prefetch_path(
+        &self,
+        frame: FrameValue,
+        proxy_scale: ProxyScale,
+    ) -> Option<(PathBuf, ProxyScale)> {
+        self.compute
+            .prefetch_path(frame, proxy_scale, &self.attr_block)
+    }
+
     pub fn validate_inputs(
         &self,
         node_compute_mode: NodeComputeMode,
@@ -107,20 +163,58 @@ impl NodeImpl {
         )
     }
 
+    /// Check the node for problems, without computing any pixels.
+    ///
+    /// Returns the number of channels the node is expected to
+    /// output, if known.
+    pub fn validate(
+        &self,
+        frames: &[FrameValue],
+        input_num_channels: &Vec<Option<i32>>,
+        issues: &mut Vec<ValidationIssue>,
+    ) -> Option<i32> {
+        let required_input_count = self.validate.required_input_count();
+        if input_num_channels.len() < required_input_count {
+            issues.push(ValidationIssue::new(
+                self.id,
+                ValidationIssueKind::MissingInput,
+                format!(
+                    "{:?} node needs {} input(s), but {} connected",
+                    self.node_type,
+                    required_input_count,
+                    input_num_channels.len()
+                ),
+            ));
+        }
+        self.validate.validate_attrs(
+            self.id,
+            &self.attr_block,
+            frames,
+            input_num_channels,
+            issues,
+        )
+    }
+
+    /// Compute the node at 'frame'.
+    ///
+    /// 'hash_value' is the hash of the node calculated by the graph,
+    /// used as the key to store the output in the cache.
+    #[allow(clippy::too_many_arguments)]
     pub fn compute(
         &mut self,
         frame: FrameValue,
+        proxy_scale: ProxyScale,
+        hash_value: HashValue,
         node_compute_mode: NodeComputeMode,
         inputs: &Vec<Rc<StreamDataImpl>>,
         output: &mut Rc<StreamDataImpl>,
-        cache: &mut Box<CacheImpl>,
+        cache: &Box<CacheImpl>,
     ) -> NodeStatus {
         let node_type_id = self.get_node_type_id();
-
-        let input_hash_values: Vec<HashValue> = inputs.iter().map(|v| v.hash()).collect();
-        let hash_value = self.hash(frame, &input_hash_values);
+        let start = Instant::now();
         let status = self.compute.compute(
             frame,
+            proxy_scale,
             node_type_id,
             &self.attr_block,
             hash_value,
@@ -129,8 +223,17 @@ impl NodeImpl {
             output,
             cache,
         );
+        cache.set_compute_seconds(hash_value, start.elapsed().as_secs_f32());
+        if !status.message().is_empty() {
+            warn!(
+                "Node id={} status={:?}: {}",
+                self.id,
+                status.status(),
+                status.message()
+            );
+        }
         self.status = status;
-        status
+        self.status.status()
     }
 
     pub fn attr_exists(&self, name: &str) -> AttrState {
@@ -212,6 +315,8 @@ pub fn create_node(node_type: NodeType, id: Identifier) -> NodeImpl {
         NodeType::ResampleImage => resample_image::new(id),
         NodeType::Transform => transform::new(id),
         NodeType::Viewer => viewer::new(id),
+        NodeType::FrameBlend => frame_blend::new(id),
+        NodeType::Metadata => metadata::new(id),
         _ => panic!("Invalid NodeType: {:?}", node_type),
     }
 }
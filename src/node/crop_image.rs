@@ -20,9 +20,9 @@
  */
 
 use log::{debug, error};
-use std::collections::hash_map::DefaultHasher;
 use std::hash::Hash;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::attrblock::AttrBlock;
 use crate::cache::CacheImpl;
@@ -32,25 +32,29 @@ use crate::cxxbridge::ffi::BBox2Di;
 use crate::cxxbridge::ffi::BakeOption;
 use crate::cxxbridge::ffi::DataType;
 use crate::cxxbridge::ffi::ImageShared;
-use crate::cxxbridge::ffi::NodeStatus;
+use crate::cxxbridge::ffi::NodeErrorKind;
 use crate::cxxbridge::ffi::NodeType;
+use crate::cxxbridge::ffi::ProxyScale;
 use crate::data::FrameValue;
 use crate::data::HashValue;
 use crate::data::Identifier;
 use crate::data::NodeComputeMode;
 use crate::data::COLOR_SPACE_NAME_LINEAR;
+use crate::hashutils::StableHasher;
+use crate::node::status::NodeStatusInfo;
 use crate::node::traits::Operation;
 use crate::node::traits::Validate;
 use crate::node::NodeImpl;
 use crate::ops::bake;
 use crate::ops::imagecrop;
+use crate::ops::imageproxy;
 use crate::stream::StreamDataImpl;
 
 pub fn new(id: Identifier) -> NodeImpl {
     NodeImpl {
         node_type: NodeType::CropImage,
         id,
-        status: NodeStatus::Uninitialized,
+        status: NodeStatusInfo::uninitialized(),
         compute: Box::new(CropImageOperation::new()),
         validate: Box::new(CropImageValidate::new()),
         attr_block: Box::new(CropImageAttrs::new()),
@@ -147,14 +151,15 @@ impl Operation for CropImageOperation {
     fn compute(
         &mut self,
         _frame: FrameValue,
+        _proxy_scale: ProxyScale,
         _node_type_id: u8,
         attr_block: &Box<dyn AttrBlock>,
         hash_value: HashValue,
         node_compute_mode: NodeComputeMode,
         inputs: &Vec<Rc<StreamDataImpl>>,
         output: &mut Rc<StreamDataImpl>,
-        cache: &mut Box<CacheImpl>,
-    ) -> NodeStatus {
+        cache: &Box<CacheImpl>,
+    ) -> NodeStatusInfo {
         debug!("CropImageOperation.compute()");
         debug!(
             "CropImageOperation NodeComputeMode={:#?}",
@@ -168,7 +173,10 @@ impl Operation for CropImageOperation {
             // No input given, return an empty default stream.
             let stream_data = StreamDataImpl::new();
             *output = std::rc::Rc::new(stream_data);
-            return NodeStatus::Warning;
+            return NodeStatusInfo::warning(
+                NodeErrorKind::MissingInput,
+                "no input connected".to_string(),
+            );
         }
 
         let input = &inputs[0].clone();
@@ -176,20 +184,25 @@ impl Operation for CropImageOperation {
         let enable = attr_block.get_attr_i32("enable");
         if enable != 1 {
             *output = std::rc::Rc::new(stream_data);
-            return NodeStatus::Valid;
+            return NodeStatusInfo::valid();
         }
 
         let window_min_x = attr_block.get_attr_i32("window_min_x");
         let window_min_y = attr_block.get_attr_i32("window_min_y");
         let window_max_x = attr_block.get_attr_i32("window_max_x");
         let window_max_y = attr_block.get_attr_i32("window_max_y");
-        let crop_window = BBox2Di::new(window_min_x, window_min_y, window_max_x, window_max_y);
+        // The crop window is given at full resolution, and is scaled
+        // to the (proxy) resolution of the input image.
+        let crop_window = imageproxy::proxy_window(
+            BBox2Di::new(window_min_x, window_min_y, window_max_x, window_max_y),
+            stream_data.proxy_scale(),
+        );
 
         let reformat = attr_block.get_attr_i32("reformat") == 1;
         let black_outside = attr_block.get_attr_i32("black_outside") == 1;
         let intersect = attr_block.get_attr_i32("intersect") == 1;
 
-        let mut status = NodeStatus::Valid;
+        let mut status = NodeStatusInfo::valid();
         let use_cache = attr_block.get_attr_i32("use_cache") != 0;
         let (pixel_block, data_window, display_window) = match use_cache {
             true => match cache.get(&hash_value) {
@@ -215,18 +228,26 @@ impl Operation for CropImageOperation {
                     );
                     if ok == false {
                         error!("CropImage failed!");
-                        status = NodeStatus::Error;
+                        status = NodeStatusInfo::error(
+                            NodeErrorKind::ComputeFailure,
+                            "failed to crop image".to_string(),
+                        );
                     }
 
-                    let pixel_block_rc = Rc::new(*img.pixel_block);
+                    let pixel_block_arc = Arc::new(*img.pixel_block);
                     let cached_img = CachedImage {
-                        pixel_block: pixel_block_rc.clone(),
+                        pixel_block: pixel_block_arc.clone(),
                         spec: img.spec,
                         data_window: img.data_window,
                         display_window: img.display_window,
+                        source_path: None,
+                        node_type: Some(NodeType::CropImage),
+                        compute_seconds: 0.0,
+                        cache_type: self.cache_type(),
+                        compressed: None,
                     };
                     cache.insert(hash_value, cached_img);
-                    (pixel_block_rc.clone(), img.data_window, img.display_window)
+                    (pixel_block_arc.clone(), img.data_window, img.display_window)
                 }
             },
             false => {
@@ -239,11 +260,14 @@ impl Operation for CropImageOperation {
                 );
                 if ok == false {
                     error!("CropImage failed!");
-                    status = NodeStatus::Error;
+                    status = NodeStatusInfo::error(
+                        NodeErrorKind::ComputeFailure,
+                        "failed to crop image".to_string(),
+                    );
                 }
 
-                let pixel_block_rc = Rc::new(*img.pixel_block);
-                (pixel_block_rc.clone(), img.data_window, img.display_window)
+                let pixel_block_arc = Arc::new(*img.pixel_block);
+                (pixel_block_arc.clone(), img.data_window, img.display_window)
             }
         };
 
@@ -258,7 +282,7 @@ impl Operation for CropImageOperation {
 }
 
 impl AttrBlock for CropImageAttrs {
-    fn attr_hash(&self, _frame: FrameValue, state: &mut DefaultHasher) {
+    fn attr_hash(&self, _frame: FrameValue, state: &mut StableHasher) {
         self.enable.hash(state);
         if self.enable == 0 {
             return;
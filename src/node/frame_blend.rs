@@ -0,0 +1,432 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+use log::debug;
+use std::hash::Hash;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::attrblock::AttrBlock;
+use crate::cache::CacheImpl;
+use crate::cache::CachedImage;
+use crate::cxxbridge::ffi::AttrState;
+use crate::cxxbridge::ffi::BBox2Di;
+use crate::cxxbridge::ffi::BakeOption;
+use crate::cxxbridge::ffi::DataType;
+use crate::cxxbridge::ffi::ImageShared;
+use crate::cxxbridge::ffi::ImageSpec;
+use crate::cxxbridge::ffi::NodeErrorKind;
+use crate::cxxbridge::ffi::NodeType;
+use crate::cxxbridge::ffi::ProxyScale;
+use crate::data::FrameValue;
+use crate::data::HashValue;
+use crate::data::Identifier;
+use crate::data::NodeComputeMode;
+use crate::data::COLOR_SPACE_NAME_LINEAR;
+use crate::hashutils::HashableF32;
+use crate::hashutils::StableHasher;
+use crate::node::status::NodeStatusInfo;
+use crate::node::traits::Operation;
+use crate::node::traits::Validate;
+use crate::node::NodeImpl;
+use crate::ops::bake;
+use crate::ops::imageblend;
+use crate::pixelblock::pixelblock::PixelBlock;
+use crate::stream::StreamDataImpl;
+
+pub fn new(id: Identifier) -> NodeImpl {
+    NodeImpl {
+        node_type: NodeType::FrameBlend,
+        id,
+        status: NodeStatusInfo::uninitialized(),
+        compute: Box::new(FrameBlendOperation::new()),
+        validate: Box::new(FrameBlendValidate::new()),
+        attr_block: Box::new(FrameBlendAttrs::new()),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FrameBlendOperation {}
+
+#[derive(Debug, Clone, Default)]
+pub struct FrameBlendAttrs {
+    pub enable: i32,
+    pub use_cache: i32,
+    pub samples: i32,
+    // The shutter interval, as frame offsets relative to the
+    // evaluated frame.
+    pub shutter_open: f32,
+    pub shutter_close: f32,
+}
+
+impl FrameBlendOperation {
+    pub fn new() -> FrameBlendOperation {
+        FrameBlendOperation {}
+    }
+}
+
+impl FrameBlendAttrs {
+    pub fn new() -> FrameBlendAttrs {
+        FrameBlendAttrs {
+            enable: 1,
+            use_cache: 1,
+            samples: 3,
+            shutter_open: -0.5,
+            shutter_close: 0.5,
+        }
+    }
+}
+
+/// Calculate the frames to sample, evenly spaced across the shutter
+/// interval (inclusive of the open and close times).
+///
+/// A single sample is taken from the middle of the shutter interval.
+pub fn sample_frames(
+    frame: FrameValue,
+    samples: i32,
+    shutter_open: f32,
+    shutter_close: f32,
+) -> Vec<FrameValue> {
+    let samples = std::cmp::max(samples, 1);
+    let shutter_open = shutter_open as FrameValue;
+    let shutter_close = shutter_close as FrameValue;
+    if samples == 1 {
+        return vec![frame + ((shutter_open + shutter_close) * 0.5)];
+    }
+    let shutter_length = shutter_close - shutter_open;
+    let last_sample = (samples - 1) as FrameValue;
+    (0..samples)
+        .map(|i| frame + shutter_open + (shutter_length * (i as FrameValue / last_sample)))
+        .collect()
+}
+
+fn do_image_process(inputs: &Vec<Rc<StreamDataImpl>>) -> ImageShared {
+    let to_color_space = COLOR_SPACE_NAME_LINEAR.to_string();
+
+    let mut images = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let mut stream_data = (**input).clone();
+        let mut pixel_block = stream_data.clone_pixel_block();
+        let mut image_spec = stream_data.clone_image_spec();
+        let from_color_space = image_spec.color_space();
+        let display_window = stream_data.display_window();
+        let mut data_window = stream_data.data_window();
+        bake::do_process(
+            BakeOption::All,
+            &mut pixel_block,
+            display_window,
+            &mut data_window,
+            &mut image_spec,
+            &mut stream_data,
+            &from_color_space,
+            &to_color_space,
+            DataType::Float32,
+        );
+        images.push(ImageShared {
+            pixel_block: Box::new(pixel_block),
+            display_window,
+            data_window,
+            spec: image_spec,
+        });
+    }
+
+    let pixel_block_out = PixelBlock::new_constant_pixel_rgba_f32(0.0, 0.0, 0.0, 1.0);
+    let mut image_out = ImageShared {
+        pixel_block: Box::new(pixel_block_out),
+        display_window: BBox2Di::new(0, 0, 1, 1),
+        data_window: BBox2Di::new(0, 0, 1, 1),
+        spec: ImageSpec::new(),
+    };
+    let ok = imageblend::blend_average(&images, &mut image_out);
+    debug!("frame_blend Success: {}", ok);
+
+    image_out
+}
+
+impl Operation for FrameBlendOperation {
+    fn input_frames(
+        &self,
+        frame: FrameValue,
+        hash_value: HashValue,
+        attr_block: &Box<dyn AttrBlock>,
+        cache: &Box<CacheImpl>,
+    ) -> Option<Vec<FrameValue>> {
+        let frames = self.hash_input_frames(frame, attr_block)?;
+        // The blended pixels are already cached, so only the first
+        // sample is evaluated, for the layers passed through.
+        let use_cache = attr_block.get_attr_i32("use_cache") != 0;
+        if use_cache && cache.contains(hash_value) {
+            return Some(frames.into_iter().take(1).collect());
+        }
+        Some(frames)
+    }
+
+    fn hash_input_frames(
+        &self,
+        frame: FrameValue,
+        attr_block: &Box<dyn AttrBlock>,
+    ) -> Option<Vec<FrameValue>> {
+        let enable = attr_block.get_attr_i32("enable");
+        if enable != 1 {
+            return None;
+        }
+        Some(sample_frames(
+            frame,
+            attr_block.get_attr_i32("samples"),
+            attr_block.get_attr_f32("shutter_open"),
+            attr_block.get_attr_f32("shutter_close"),
+        ))
+    }
+
+    fn compute(
+        &mut self,
+        frame: FrameValue,
+        _proxy_scale: ProxyScale,
+        _node_type_id: u8,
+        attr_block: &Box<dyn AttrBlock>,
+        hash_value: HashValue,
+        _node_compute_mode: NodeComputeMode,
+        inputs: &Vec<Rc<StreamDataImpl>>,
+        output: &mut Rc<StreamDataImpl>,
+        cache: &Box<CacheImpl>,
+    ) -> NodeStatusInfo {
+        debug!("FrameBlendOperation.compute()");
+        // debug!("AttrBlock: {:?}", attr_block);
+        // debug!("Inputs: {:?}", inputs);
+        // debug!("Output: {:?}", output);
+
+        if inputs.is_empty() {
+            let stream_data = StreamDataImpl::new();
+            *output = std::rc::Rc::new(stream_data);
+            return NodeStatusInfo::warning(
+                NodeErrorKind::MissingInput,
+                "no input connected".to_string(),
+            );
+        }
+
+        // When disabled the input is only evaluated at the current
+        // frame, and is passed through unchanged.
+        let enable = attr_block.get_attr_i32("enable");
+        if enable != 1 {
+            *output = inputs[0].clone();
+            return NodeStatusInfo::valid();
+        }
+
+        // Each input stream is the same input node, evaluated at a
+        // different sample frame.
+        debug!("Frame Blend Samples: {}", inputs.len());
+        let samples = sample_frames(
+            frame,
+            attr_block.get_attr_i32("samples"),
+            attr_block.get_attr_f32("shutter_open"),
+            attr_block.get_attr_f32("shutter_close"),
+        );
+        let mut status = NodeStatusInfo::valid();
+        let use_cache = attr_block.get_attr_i32("use_cache") != 0;
+        let (pixel_block, image_spec, data_window, display_window) = match use_cache {
+            true => match cache.get(&hash_value) {
+                Some(cached_img) => {
+                    debug!("Cache Hit");
+                    (
+                        cached_img.pixel_block.clone(),
+                        cached_img.spec.clone(),
+                        cached_img.data_window,
+                        cached_img.display_window,
+                    )
+                }
+                _ if inputs.len() < samples.len() => {
+                    // Only the first sample was evaluated because the
+                    // blended pixels were cached, but they have since
+                    // been evicted.
+                    let img = do_image_process(inputs);
+                    status = NodeStatusInfo::warning(
+                        NodeErrorKind::ComputeFailure,
+                        format!(
+                            "cached image was evicted, blended {} of {} samples",
+                            inputs.len(),
+                            samples.len()
+                        ),
+                    );
+                    (
+                        Arc::new(*img.pixel_block),
+                        img.spec,
+                        img.data_window,
+                        img.display_window,
+                    )
+                }
+                _ => {
+                    debug!("Cache Miss");
+                    let img = do_image_process(inputs);
+                    let pixel_block_arc = Arc::new(*img.pixel_block);
+                    let cached_img = CachedImage {
+                        pixel_block: pixel_block_arc.clone(),
+                        spec: img.spec.clone(),
+                        data_window: img.data_window,
+                        display_window: img.display_window,
+                        source_path: None,
+                        node_type: Some(NodeType::FrameBlend),
+                        compute_seconds: 0.0,
+                        cache_type: self.cache_type(),
+                        compressed: None,
+                    };
+                    cache.insert(hash_value, cached_img);
+                    (
+                        pixel_block_arc,
+                        img.spec,
+                        img.data_window,
+                        img.display_window,
+                    )
+                }
+            },
+            false => {
+                let img = do_image_process(inputs);
+                let pixel_block_arc = Arc::new(*img.pixel_block);
+                (
+                    pixel_block_arc,
+                    img.spec,
+                    img.data_window,
+                    img.display_window,
+                )
+            }
+        };
+
+        let mut stream_data = StreamDataImpl::new();
+        stream_data.set_data_window(data_window);
+        stream_data.set_display_window(display_window);
+        stream_data.set_hash(hash_value);
+        stream_data.set_pixel_block(pixel_block);
+        stream_data.set_image_spec(image_spec);
+        // Only the RGBA pixels are blended, the layers of the first
+        // sample are passed through.
+        stream_data.set_layers(inputs[0].layers().clone());
+        stream_data.set_proxy_scale(inputs[0].proxy_scale());
+
+        *output = std::rc::Rc::new(stream_data);
+        status
+    }
+}
+
+impl AttrBlock for FrameBlendAttrs {
+    fn attr_hash(&self, frame: FrameValue, state: &mut StableHasher) {
+        self.enable.hash(state);
+        if self.enable == 1 {
+            // The input is hashed at each sample frame (see
+            // 'hash_input_frames'); the frame is hashed as well so
+            // the blend of each evaluated frame has its own key.
+            frame.to_bits().hash(state);
+            self.samples.hash(state);
+            HashableF32::new(self.shutter_open).hash(state);
+            HashableF32::new(self.shutter_close).hash(state);
+        }
+    }
+
+    fn attr_exists(&self, name: &str) -> AttrState {
+        match name {
+            "enable" => AttrState::Exists,
+            "use_cache" => AttrState::Exists,
+            "samples" => AttrState::Exists,
+            "shutter_open" => AttrState::Exists,
+            "shutter_close" => AttrState::Exists,
+            _ => AttrState::Missing,
+        }
+    }
+
+    fn get_attr_str(&self, _name: &str) -> &str {
+        ""
+    }
+
+    fn set_attr_str(&mut self, _name: &str, _value: &str) {}
+
+    fn get_attr_i32(&self, name: &str) -> i32 {
+        match name {
+            "enable" => self.enable,
+            "use_cache" => self.use_cache,
+            "samples" => self.samples,
+            _ => 0,
+        }
+    }
+
+    fn set_attr_i32(&mut self, name: &str, value: i32) {
+        match name {
+            "enable" => self.enable = value,
+            "use_cache" => self.use_cache = value,
+            "samples" => self.samples = value,
+            _ => (),
+        };
+    }
+
+    fn get_attr_f32(&self, name: &str) -> f32 {
+        match name {
+            "shutter_open" => self.shutter_open,
+            "shutter_close" => self.shutter_close,
+            _ => 0.0,
+        }
+    }
+
+    fn set_attr_f32(&mut self, name: &str, value: f32) {
+        match name {
+            "shutter_open" => self.shutter_open = value,
+            "shutter_close" => self.shutter_close = value,
+            _ => (),
+        };
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FrameBlendValidate {}
+
+impl FrameBlendValidate {
+    pub fn new() -> FrameBlendValidate {
+        FrameBlendValidate {}
+    }
+}
+
+impl Validate for FrameBlendValidate {
+    fn validate_inputs(
+        &self,
+        _node_type_id: u8,
+        attr_block: &Box<dyn AttrBlock>,
+        hash_value: HashValue,
+        node_compute_mode: NodeComputeMode,
+        input_nodes: &Vec<&Box<NodeImpl>>,
+    ) -> Vec<NodeComputeMode> {
+        debug!(
+            "FrameBlendValidate::validate_inputs(): NodeComputeMode={:#?} HashValue={:#?}",
+            node_compute_mode, hash_value
+        );
+        // When enabled, the input is evaluated separately for each
+        // sample frame, so nothing is needed from the input at the
+        // current frame.
+        let enable = attr_block.get_attr_i32("enable");
+        let input_compute_mode = match enable {
+            1 => NodeComputeMode::NONE,
+            _ => NodeComputeMode::ALL,
+        };
+        let mut node_compute_modes = Vec::new();
+        if !input_nodes.is_empty() {
+            node_compute_modes.push(node_compute_mode & input_compute_mode);
+            for _ in input_nodes.iter().skip(1) {
+                node_compute_modes.push(node_compute_mode & NodeComputeMode::NONE);
+            }
+        }
+        node_compute_modes
+    }
+}
@@ -20,7 +20,6 @@
  */
 
 use log::debug;
-use std::collections::hash_map::DefaultHasher;
 use std::hash;
 use std::hash::Hash;
 use std::rc::Rc;
@@ -29,13 +28,17 @@ use crate::attrblock::AttrBlock;
 use crate::cache::CacheImpl;
 use crate::colorop::colorgrade::ColorOpGrade;
 use crate::cxxbridge::ffi::AttrState;
-use crate::cxxbridge::ffi::NodeStatus;
+use crate::cxxbridge::ffi::NodeErrorKind;
 use crate::cxxbridge::ffi::NodeType;
+use crate::cxxbridge::ffi::ProxyScale;
 use crate::data::FrameValue;
 use crate::data::HashValue;
 use crate::data::Identifier;
 use crate::data::NodeComputeMode;
+use crate::data::OperationCacheType;
 use crate::hashutils::HashableF32;
+use crate::hashutils::StableHasher;
+use crate::node::status::NodeStatusInfo;
 use crate::node::traits::Operation;
 use crate::node::traits::Validate;
 use crate::node::NodeImpl;
@@ -45,7 +48,7 @@ pub fn new(id: Identifier) -> NodeImpl {
     NodeImpl {
         node_type: NodeType::Grade,
         id,
-        status: NodeStatus::Uninitialized,
+        status: NodeStatusInfo::uninitialized(),
         compute: Box::new(GradeOperation::new()),
         validate: Box::new(GradeValidate::new()),
         attr_block: Box::new(GradeAttrs::new()),
@@ -232,28 +235,36 @@ impl GradeAttrs {
 }
 
 impl Operation for GradeOperation {
+    fn cache_type(&self) -> OperationCacheType {
+        OperationCacheType::Trivial
+    }
+
     fn compute(
         &mut self,
         _frame: FrameValue,
+        _proxy_scale: ProxyScale,
         _node_type_id: u8,
         attr_block: &Box<dyn AttrBlock>,
         hash_value: HashValue,
         node_compute_mode: NodeComputeMode,
         inputs: &Vec<Rc<StreamDataImpl>>,
         output: &mut Rc<StreamDataImpl>,
-        _cache: &mut Box<CacheImpl>,
-    ) -> NodeStatus {
+        _cache: &Box<CacheImpl>,
+    ) -> NodeStatusInfo {
         debug!("GradeOperation.compute()");
         debug!("GradeOperation NodeComputeMode={:#?}", node_compute_mode);
         // debug!("AttrBlock: {:?}", attr_block);
         // debug!("Inputs: {:?}", inputs);
         // debug!("Output: {:?}", output);
 
-        let mut status = NodeStatus::Valid;
+        let mut status = NodeStatusInfo::valid();
         let mut stream_data = match inputs.len() {
             0 => {
                 // No input given, return an empty default stream.
-                status = NodeStatus::Warning;
+                status = NodeStatusInfo::warning(
+                    NodeErrorKind::MissingInput,
+                    "no input connected".to_string(),
+                );
                 StreamDataImpl::new()
             }
             _ => {
@@ -370,7 +381,7 @@ impl Operation for GradeOperation {
 }
 
 impl AttrBlock for GradeAttrs {
-    fn attr_hash(&self, _frame: FrameValue, state: &mut DefaultHasher) {
+    fn attr_hash(&self, _frame: FrameValue, state: &mut StableHasher) {
         self.hash(state)
     }
 
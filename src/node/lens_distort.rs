@@ -20,7 +20,6 @@
  */
 
 use log::debug;
-use std::collections::hash_map::DefaultHasher;
 use std::hash;
 use std::hash::Hash;
 use std::rc::Rc;
@@ -28,15 +27,21 @@ use std::rc::Rc;
 use crate::attrblock::AttrBlock;
 use crate::cache::CacheImpl;
 use crate::cxxbridge::ffi::AttrState;
-use crate::cxxbridge::ffi::NodeStatus;
+use crate::cxxbridge::ffi::LensDistortDirection;
+use crate::cxxbridge::ffi::NodeErrorKind;
 use crate::cxxbridge::ffi::NodeType;
+use crate::cxxbridge::ffi::ProxyScale;
+use crate::cxxbridge::ffi::ValidationIssue;
 use crate::data::FrameValue;
 use crate::data::HashValue;
 use crate::data::Identifier;
 use crate::data::NodeComputeMode;
+use crate::data::OperationCacheType;
 use crate::deformer::tde4_classic::DeformerTde4Classic;
 use crate::deformer::Deformer;
 use crate::hashutils::HashableF32;
+use crate::hashutils::StableHasher;
+use crate::node::status::NodeStatusInfo;
 use crate::node::traits::Operation;
 use crate::node::traits::Validate;
 use crate::node::NodeImpl;
@@ -46,7 +51,7 @@ pub fn new(id: Identifier) -> NodeImpl {
     NodeImpl {
         node_type: NodeType::LensDistort,
         id,
-        status: NodeStatus::Uninitialized,
+        status: NodeStatusInfo::uninitialized(),
         compute: Box::new(LensDistortOperation::new()),
         validate: Box::new(LensDistortValidate::new()),
         attr_block: Box::new(LensDistortAttrs::new()),
@@ -108,17 +113,22 @@ impl LensDistortAttrs {
 }
 
 impl Operation for LensDistortOperation {
+    fn cache_type(&self) -> OperationCacheType {
+        OperationCacheType::Trivial
+    }
+
     fn compute(
         &mut self,
         _frame: FrameValue,
+        _proxy_scale: ProxyScale,
         _node_type_id: u8,
         attr_block: &Box<dyn AttrBlock>,
         hash_value: HashValue,
         node_compute_mode: NodeComputeMode,
         inputs: &Vec<Rc<StreamDataImpl>>,
         output: &mut Rc<StreamDataImpl>,
-        _cache: &mut Box<CacheImpl>,
-    ) -> NodeStatus {
+        _cache: &Box<CacheImpl>,
+    ) -> NodeStatusInfo {
         debug!("LensDistortOperation.compute()");
         debug!(
             "LensDistortOperation NodeComputeMode={:#?}",
@@ -128,11 +138,14 @@ impl Operation for LensDistortOperation {
         // debug!("Inputs: {:?}", inputs);
         // debug!("Output: {:?}", output);
 
-        let mut status = NodeStatus::Valid;
+        let mut status = NodeStatusInfo::valid();
         let mut stream_data = match inputs.len() {
             0 => {
                 // No input given, return an empty default stream.
-                status = NodeStatus::Warning;
+                status = NodeStatusInfo::warning(
+                    NodeErrorKind::MissingInput,
+                    "no input connected".to_string(),
+                );
                 StreamDataImpl::new()
             }
             _ => {
@@ -173,7 +186,7 @@ impl Operation for LensDistortOperation {
 }
 
 impl AttrBlock for LensDistortAttrs {
-    fn attr_hash(&self, _frame: FrameValue, state: &mut DefaultHasher) {
+    fn attr_hash(&self, _frame: FrameValue, state: &mut StableHasher) {
         self.hash(state)
     }
 
@@ -274,4 +287,23 @@ impl Validate for LensDistortValidate {
         }
         node_compute_modes
     }
+
+    fn validate_attrs(
+        &self,
+        node_id: Identifier,
+        attr_block: &Box<dyn AttrBlock>,
+        _frames: &[FrameValue],
+        input_num_channels: &Vec<Option<i32>>,
+        issues: &mut Vec<ValidationIssue>,
+    ) -> Option<i32> {
+        let direction = attr_block.get_attr_i32("direction");
+        if LensDistortDirection::from(direction) == LensDistortDirection::Uninitialized {
+            issues.push(ValidationIssue::invalid_enum_value(
+                node_id,
+                "direction",
+                direction,
+            ));
+        }
+        input_num_channels.first().copied().flatten()
+    }
 }
@@ -20,9 +20,9 @@
  */
 
 use log::debug;
-use std::collections::hash_map::DefaultHasher;
 use std::hash::Hash;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::attrblock::AttrBlock;
 use crate::cache::CacheImpl;
@@ -34,14 +34,19 @@ use crate::cxxbridge::ffi::DataType;
 use crate::cxxbridge::ffi::ImageShared;
 use crate::cxxbridge::ffi::ImageSpec;
 use crate::cxxbridge::ffi::MergeImageMode;
-use crate::cxxbridge::ffi::NodeStatus;
+use crate::cxxbridge::ffi::NodeErrorKind;
 use crate::cxxbridge::ffi::NodeType;
+use crate::cxxbridge::ffi::ProxyScale;
+use crate::cxxbridge::ffi::ValidationIssue;
+use crate::cxxbridge::ffi::ValidationIssueKind;
 use crate::data::FrameValue;
 use crate::data::HashValue;
 use crate::data::Identifier;
 use crate::data::NodeComputeMode;
 use crate::data::COLOR_SPACE_NAME_LINEAR;
 use crate::hashutils::HashableF32;
+use crate::hashutils::StableHasher;
+use crate::node::status::NodeStatusInfo;
 use crate::node::traits::Operation;
 use crate::node::traits::Validate;
 use crate::node::NodeImpl;
@@ -54,7 +59,7 @@ pub fn new(id: Identifier) -> NodeImpl {
     NodeImpl {
         node_type: NodeType::MergeImage,
         id,
-        status: NodeStatus::Uninitialized,
+        status: NodeStatusInfo::uninitialized(),
         compute: Box::new(MergeImageOperation::new()),
         validate: Box::new(MergeImageValidate::new()),
         attr_block: Box::new(MergeImageAttrs::new()),
@@ -184,14 +189,15 @@ impl Operation for MergeImageOperation {
     fn compute(
         &mut self,
         _frame: FrameValue,
+        _proxy_scale: ProxyScale,
         _node_type_id: u8,
         attr_block: &Box<dyn AttrBlock>,
         hash_value: HashValue,
         _node_compute_mode: NodeComputeMode,
         inputs: &Vec<Rc<StreamDataImpl>>,
         output: &mut Rc<StreamDataImpl>,
-        cache: &mut Box<CacheImpl>,
-    ) -> NodeStatus {
+        cache: &Box<CacheImpl>,
+    ) -> NodeStatusInfo {
         debug!("MergeImageOperation.compute()");
         // debug!("AttrBlock: {:?}", attr_block);
         // debug!("Inputs: {:?}", inputs);
@@ -201,14 +207,20 @@ impl Operation for MergeImageOperation {
         if enable != 1 {
             let stream_data = StreamDataImpl::new();
             *output = std::rc::Rc::new(stream_data);
-            return NodeStatus::Warning;
+            return NodeStatusInfo::warning(
+                NodeErrorKind::Disabled,
+                "node is disabled".to_string(),
+            );
         }
 
-        let mut status = NodeStatus::Valid;
+        let mut status = NodeStatusInfo::valid();
         if inputs.len() == 0 {
             let stream_data = StreamDataImpl::new();
             *output = std::rc::Rc::new(stream_data);
-            return NodeStatus::Warning;
+            return NodeStatusInfo::warning(
+                NodeErrorKind::MissingInput,
+                "no input connected to A or B".to_string(),
+            );
         }
 
         let input_a = &inputs[0].clone();
@@ -221,7 +233,10 @@ impl Operation for MergeImageOperation {
             1 => {
                 // No input given for second, return an empty default
                 // stream.
-                status = NodeStatus::Warning;
+                status = NodeStatusInfo::warning(
+                    NodeErrorKind::MissingInput,
+                    "no input connected to B".to_string(),
+                );
                 StreamDataImpl::new()
             }
             _ => {
@@ -236,12 +251,13 @@ impl Operation for MergeImageOperation {
         // Cache the results of the merge. If the input values do not
         // change we can easily look up the pixels again.
         let use_cache = attr_block.get_attr_i32("use_cache") != 0;
-        let (pixel_block, data_window, display_window) = match use_cache {
+        let (pixel_block, image_spec, data_window, display_window) = match use_cache {
             true => match cache.get(&hash_value) {
                 Some(cached_img) => {
                     debug!("Cache Hit");
                     (
                         cached_img.pixel_block.clone(),
+                        cached_img.spec.clone(),
                         cached_img.data_window,
                         cached_img.display_window,
                     )
@@ -251,21 +267,36 @@ impl Operation for MergeImageOperation {
                     let img =
                         do_image_process(&mut stream_data_a, &mut stream_data_b, merge_mode, mix);
 
-                    let pixel_block_rc = Rc::new(*img.pixel_block);
+                    let pixel_block_arc = Arc::new(*img.pixel_block);
                     let cached_img = CachedImage {
-                        pixel_block: pixel_block_rc.clone(),
-                        spec: img.spec,
+                        pixel_block: pixel_block_arc.clone(),
+                        spec: img.spec.clone(),
                         data_window: img.data_window,
                         display_window: img.display_window,
+                        source_path: None,
+                        node_type: Some(NodeType::MergeImage),
+                        compute_seconds: 0.0,
+                        cache_type: self.cache_type(),
+                        compressed: None,
                     };
                     cache.insert(hash_value, cached_img);
-                    (pixel_block_rc.clone(), img.data_window, img.display_window)
+                    (
+                        pixel_block_arc.clone(),
+                        img.spec,
+                        img.data_window,
+                        img.display_window,
+                    )
                 }
             },
             false => {
                 let img = do_image_process(&mut stream_data_a, &mut stream_data_b, merge_mode, mix);
-                let pixel_block_rc = Rc::new(*img.pixel_block);
-                (pixel_block_rc.clone(), img.data_window, img.display_window)
+                let pixel_block_arc = Arc::new(*img.pixel_block);
+                (
+                    pixel_block_arc.clone(),
+                    img.spec,
+                    img.data_window,
+                    img.display_window,
+                )
             }
         };
 
@@ -301,6 +332,11 @@ impl Operation for MergeImageOperation {
         stream_data.set_display_window(display_window);
         stream_data.set_hash(hash_value);
         stream_data.set_pixel_block(pixel_block);
+        stream_data.set_image_spec(image_spec);
+        // Only the RGBA pixels are merged, the layers of input A
+        // are passed through.
+        stream_data.set_layers(stream_data_a.layers().clone());
+        stream_data.set_proxy_scale(stream_data_a.proxy_scale());
 
         *output = std::rc::Rc::new(stream_data);
         status
@@ -308,7 +344,7 @@ impl Operation for MergeImageOperation {
 }
 
 impl AttrBlock for MergeImageAttrs {
-    fn attr_hash(&self, _frame: FrameValue, state: &mut DefaultHasher) {
+    fn attr_hash(&self, _frame: FrameValue, state: &mut StableHasher) {
         self.enable.hash(state);
         if self.enable == 1 {
             self.mode.hash(state);
@@ -401,4 +437,40 @@ impl Validate for MergeImageValidate {
         }
         node_compute_modes
     }
+
+    fn required_input_count(&self) -> usize {
+        2
+    }
+
+    fn validate_attrs(
+        &self,
+        node_id: Identifier,
+        attr_block: &Box<dyn AttrBlock>,
+        _frames: &[FrameValue],
+        input_num_channels: &Vec<Option<i32>>,
+        issues: &mut Vec<ValidationIssue>,
+    ) -> Option<i32> {
+        let mode = attr_block.get_attr_i32("mode");
+        if MergeImageMode::from(mode) == MergeImageMode::Uninitialized {
+            issues.push(ValidationIssue::invalid_enum_value(node_id, "mode", mode));
+        }
+
+        // The merged image uses the channels of the B input, so an
+        // alpha channel on the A input would be lost.
+        let num_channels_a = input_num_channels.first().copied().flatten();
+        let num_channels_b = input_num_channels.get(1).copied().flatten();
+        if let (Some(a), Some(b)) = (num_channels_a, num_channels_b) {
+            if a != b {
+                issues.push(ValidationIssue::new(
+                    node_id,
+                    ValidationIssueKind::ChannelCountMismatch,
+                    format!(
+                        "input A has {} channel(s) but input B has {} channel(s)",
+                        a, b
+                    ),
+                ));
+            }
+        }
+        num_channels_b
+    }
 }
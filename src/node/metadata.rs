@@ -0,0 +1,318 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+use log::debug;
+use std::hash::Hash;
+use std::rc::Rc;
+
+use crate::attrblock::AttrBlock;
+use crate::cache::CacheImpl;
+use crate::cxxbridge::ffi::AttrState;
+use crate::cxxbridge::ffi::ImageMetadata;
+use crate::cxxbridge::ffi::ImageMetadataType;
+use crate::cxxbridge::ffi::MetadataMode;
+use crate::cxxbridge::ffi::NodeErrorKind;
+use crate::cxxbridge::ffi::NodeType;
+use crate::cxxbridge::ffi::ProxyScale;
+use crate::cxxbridge::ffi::ValidationIssue;
+use crate::cxxbridge::ffi::ValidationIssueKind;
+use crate::data::FrameValue;
+use crate::data::HashValue;
+use crate::data::Identifier;
+use crate::data::NodeComputeMode;
+use crate::data::OperationCacheType;
+use crate::hashutils::StableHasher;
+use crate::node::status::NodeStatusInfo;
+use crate::node::traits::Operation;
+use crate::node::traits::Validate;
+use crate::node::NodeImpl;
+use crate::stream::StreamDataImpl;
+
+pub fn new(id: Identifier) -> NodeImpl {
+    NodeImpl {
+        node_type: NodeType::Metadata,
+        id,
+        status: NodeStatusInfo::uninitialized(),
+        compute: Box::new(MetadataOperation::new()),
+        validate: Box::new(MetadataValidate::new()),
+        attr_block: Box::new(MetadataAttrs::new()),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MetadataOperation {}
+
+#[derive(Debug, Clone, Default)]
+pub struct MetadataAttrs {
+    pub enable: i32,
+    pub mode: i32,
+    pub name: String,
+    pub value_type: i32,
+    pub value: String,
+}
+
+impl MetadataOperation {
+    pub fn new() -> MetadataOperation {
+        MetadataOperation {}
+    }
+}
+
+impl MetadataAttrs {
+    pub fn new() -> MetadataAttrs {
+        MetadataAttrs {
+            enable: 1,
+            mode: 0, // 0 = MetadataMode::Set
+            name: "".to_string(),
+            value_type: 2, // 2 = ImageMetadataType::String
+            value: "".to_string(),
+        }
+    }
+}
+
+/// Check the metadata attributes for 'mode' can be used.
+fn check_attrs(
+    mode: MetadataMode,
+    name: &str,
+    value_type: i32,
+    value: &str,
+) -> Result<Option<ImageMetadata>, String> {
+    match mode {
+        MetadataMode::Set | MetadataMode::Remove if name.is_empty() => {
+            Err("metadata name is empty".to_string())
+        }
+        MetadataMode::Set => {
+            let data_type = ImageMetadataType::from(value_type);
+            ImageMetadata::from_text(name, data_type, value).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+impl Operation for MetadataOperation {
+    fn cache_type(&self) -> OperationCacheType {
+        OperationCacheType::Trivial
+    }
+
+    fn compute(
+        &mut self,
+        _frame: FrameValue,
+        _proxy_scale: ProxyScale,
+        _node_type_id: u8,
+        attr_block: &Box<dyn AttrBlock>,
+        hash_value: HashValue,
+        _node_compute_mode: NodeComputeMode,
+        inputs: &Vec<Rc<StreamDataImpl>>,
+        output: &mut Rc<StreamDataImpl>,
+        _cache: &Box<CacheImpl>,
+    ) -> NodeStatusInfo {
+        debug!("MetadataOperation.compute()");
+        // debug!("AttrBlock: {:?}", attr_block);
+        // debug!("Inputs: {:?}", inputs);
+        // debug!("Output: {:?}", output);
+
+        if inputs.is_empty() {
+            // No input given, return an empty default stream.
+            let stream_data = StreamDataImpl::new();
+            *output = std::rc::Rc::new(stream_data);
+            return NodeStatusInfo::warning(
+                NodeErrorKind::MissingInput,
+                "no input connected".to_string(),
+            );
+        }
+
+        let enable = attr_block.get_attr_i32("enable");
+        if enable != 1 {
+            *output = inputs[0].clone();
+            return NodeStatusInfo::valid();
+        }
+
+        let mode = MetadataMode::from(attr_block.get_attr_i32("mode"));
+        let name = attr_block.get_attr_str("name");
+        let value_type = attr_block.get_attr_i32("value_type");
+        let value = attr_block.get_attr_str("value");
+        let metadata = match check_attrs(mode, name, value_type, value) {
+            Ok(metadata) => metadata,
+            Err(message) => {
+                // The image is passed through unchanged.
+                *output = inputs[0].clone();
+                return NodeStatusInfo::warning(NodeErrorKind::InvalidAttribute, message);
+            }
+        };
+
+        let mut stream_data = (*inputs[0]).clone();
+        let mut image_spec = stream_data.clone_image_spec();
+        match mode {
+            MetadataMode::Set => {
+                if let Some(metadata) = metadata {
+                    image_spec.insert_metadata(metadata);
+                }
+            }
+            MetadataMode::Remove => {
+                image_spec.remove_metadata(name);
+            }
+            MetadataMode::RemoveAll => image_spec.set_metadata(Vec::new()),
+            _ => (),
+        }
+        stream_data.set_image_spec(image_spec);
+
+        // Set Output data
+        stream_data.set_hash(hash_value);
+        *output = Rc::new(stream_data);
+        NodeStatusInfo::valid()
+    }
+}
+
+impl AttrBlock for MetadataAttrs {
+    fn attr_hash(&self, _frame: FrameValue, state: &mut StableHasher) {
+        self.enable.hash(state);
+        if self.enable == 1 {
+            self.mode.hash(state);
+            match MetadataMode::from(self.mode) {
+                MetadataMode::Set => {
+                    self.name.hash(state);
+                    self.value_type.hash(state);
+                    self.value.hash(state);
+                }
+                MetadataMode::Remove => self.name.hash(state),
+                _ => (),
+            }
+        }
+    }
+
+    fn attr_exists(&self, name: &str) -> AttrState {
+        match name {
+            "enable" => AttrState::Exists,
+            "mode" => AttrState::Exists,
+            "name" => AttrState::Exists,
+            "value_type" => AttrState::Exists,
+            "value" => AttrState::Exists,
+            _ => AttrState::Missing,
+        }
+    }
+
+    fn get_attr_str(&self, name: &str) -> &str {
+        match name {
+            "name" => &self.name,
+            "value" => &self.value,
+            _ => "",
+        }
+    }
+
+    fn set_attr_str(&mut self, name: &str, value: &str) {
+        match name {
+            "name" => self.name = value.to_string(),
+            "value" => self.value = value.to_string(),
+            _ => (),
+        };
+    }
+
+    fn get_attr_i32(&self, name: &str) -> i32 {
+        match name {
+            "enable" => self.enable,
+            "mode" => self.mode,
+            "value_type" => self.value_type,
+            _ => 0,
+        }
+    }
+
+    fn set_attr_i32(&mut self, name: &str, value: i32) {
+        match name {
+            "enable" => self.enable = value,
+            "mode" => self.mode = value,
+            "value_type" => self.value_type = value,
+            _ => (),
+        };
+    }
+
+    fn get_attr_f32(&self, _name: &str) -> f32 {
+        0.0
+    }
+
+    fn set_attr_f32(&mut self, _name: &str, _value: f32) {}
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MetadataValidate {}
+
+impl MetadataValidate {
+    pub fn new() -> MetadataValidate {
+        MetadataValidate {}
+    }
+}
+
+impl Validate for MetadataValidate {
+    fn validate_inputs(
+        &self,
+        _node_type_id: u8,
+        _attr_block: &Box<dyn AttrBlock>,
+        hash_value: HashValue,
+        node_compute_mode: NodeComputeMode,
+        input_nodes: &Vec<&Box<NodeImpl>>,
+    ) -> Vec<NodeComputeMode> {
+        debug!(
+            "MetadataValidate::validate_inputs(): NodeComputeMode={:#?} HashValue={:#?}",
+            node_compute_mode, hash_value
+        );
+        let mut node_compute_modes = Vec::new();
+        if !input_nodes.is_empty() {
+            node_compute_modes.push(node_compute_mode & NodeComputeMode::ALL);
+            for _ in input_nodes.iter().skip(1) {
+                node_compute_modes.push(node_compute_mode & NodeComputeMode::NONE);
+            }
+        }
+        node_compute_modes
+    }
+
+    fn validate_attrs(
+        &self,
+        node_id: Identifier,
+        attr_block: &Box<dyn AttrBlock>,
+        _frames: &[FrameValue],
+        input_num_channels: &Vec<Option<i32>>,
+        issues: &mut Vec<ValidationIssue>,
+    ) -> Option<i32> {
+        if attr_block.get_attr_i32("enable") == 1 {
+            let mode = attr_block.get_attr_i32("mode");
+            let value_type = attr_block.get_attr_i32("value_type");
+            if MetadataMode::from(mode) == MetadataMode::Uninitialized {
+                issues.push(ValidationIssue::invalid_enum_value(node_id, "mode", mode));
+            } else if ImageMetadataType::from(value_type) == ImageMetadataType::Uninitialized {
+                issues.push(ValidationIssue::invalid_enum_value(
+                    node_id,
+                    "value_type",
+                    value_type,
+                ));
+            } else if let Err(message) = check_attrs(
+                MetadataMode::from(mode),
+                attr_block.get_attr_str("name"),
+                value_type,
+                attr_block.get_attr_str("value"),
+            ) {
+                issues.push(ValidationIssue::new(
+                    node_id,
+                    ValidationIssueKind::InvalidAttribute,
+                    message,
+                ));
+            }
+        }
+        input_num_channels.first().copied().flatten()
+    }
+}
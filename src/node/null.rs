@@ -20,19 +20,22 @@
  */
 
 use log::debug;
-use std::collections::hash_map::DefaultHasher;
 use std::hash::Hash;
 use std::rc::Rc;
 
 use crate::attrblock::AttrBlock;
 use crate::cache::CacheImpl;
 use crate::cxxbridge::ffi::AttrState;
-use crate::cxxbridge::ffi::NodeStatus;
+use crate::cxxbridge::ffi::NodeErrorKind;
 use crate::cxxbridge::ffi::NodeType;
+use crate::cxxbridge::ffi::ProxyScale;
 use crate::data::FrameValue;
 use crate::data::HashValue;
 use crate::data::Identifier;
 use crate::data::NodeComputeMode;
+use crate::data::OperationCacheType;
+use crate::hashutils::StableHasher;
+use crate::node::status::NodeStatusInfo;
 use crate::node::traits::Operation;
 use crate::node::traits::Validate;
 use crate::node::NodeImpl;
@@ -42,7 +45,7 @@ pub fn new(id: Identifier) -> NodeImpl {
     NodeImpl {
         node_type: NodeType::Null,
         id,
-        status: NodeStatus::Uninitialized,
+        status: NodeStatusInfo::uninitialized(),
         compute: Box::new(NullOperation::new()),
         validate: Box::new(NullValidate::new()),
         attr_block: Box::new(NullAttrs::new()),
@@ -68,17 +71,22 @@ impl NullAttrs {
 }
 
 impl Operation for NullOperation {
+    fn cache_type(&self) -> OperationCacheType {
+        OperationCacheType::Trivial
+    }
+
     fn compute(
         &mut self,
         _frame: FrameValue,
+        _proxy_scale: ProxyScale,
         _node_type_id: u8,
         _attr_block: &Box<dyn AttrBlock>,
         _hash_value: HashValue,
         _compute_mode: NodeComputeMode,
         inputs: &Vec<Rc<StreamDataImpl>>,
         output: &mut Rc<StreamDataImpl>,
-        _cache: &mut Box<CacheImpl>,
-    ) -> NodeStatus {
+        _cache: &Box<CacheImpl>,
+    ) -> NodeStatusInfo {
         debug!("NullOperation.compute()");
         // debug!("AttrBlock: {:?}", _attr_block);
         // debug!("Inputs: {:?}", inputs);
@@ -89,18 +97,21 @@ impl Operation for NullOperation {
                 // No input given, return an empty default stream.
                 let stream_data = StreamDataImpl::new();
                 *output = std::rc::Rc::new(stream_data);
-                NodeStatus::Warning
+                NodeStatusInfo::warning(
+                    NodeErrorKind::MissingInput,
+                    "no input connected".to_string(),
+                )
             }
             _ => {
                 *output = inputs[0].clone();
-                NodeStatus::Valid
+                NodeStatusInfo::valid()
             }
         }
     }
 }
 
 impl AttrBlock for NullAttrs {
-    fn attr_hash(&self, _frame: FrameValue, state: &mut DefaultHasher) {
+    fn attr_hash(&self, _frame: FrameValue, state: &mut StableHasher) {
         self.hash(state)
     }
 
@@ -19,35 +19,74 @@
  *
  */
 
-use log::debug;
-use std::collections::hash_map::DefaultHasher;
+use log::{debug, warn};
+use rustc_hash::FxHashMap;
+use std::fs;
 use std::hash::Hash;
+use std::hash::Hasher;
+use std::io::Read;
 use std::path::Path;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::string::String;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Instant;
+use std::time::SystemTime;
 
 use crate::attrblock::AttrBlock;
 use crate::cache::CacheImpl;
 use crate::cache::CachedImage;
+use crate::colorspace::color_space_exists;
 use crate::cxxbridge::ffi::AttrState;
-use crate::cxxbridge::ffi::NodeStatus;
+use crate::cxxbridge::ffi::BBox2Di;
+use crate::cxxbridge::ffi::BlockSize;
+use crate::cxxbridge::ffi::DataType;
+use crate::cxxbridge::ffi::ImageIOBackend;
+use crate::cxxbridge::ffi::ImageOrientation;
+use crate::cxxbridge::ffi::ImageShared;
+use crate::cxxbridge::ffi::ImageSpec;
+use crate::cxxbridge::ffi::NodeErrorKind;
 use crate::cxxbridge::ffi::NodeType;
+use crate::cxxbridge::ffi::ProxyScale;
+use crate::cxxbridge::ffi::ReadImageAlphaMode;
+use crate::cxxbridge::ffi::ReadImageFrameRangeMode;
+use crate::cxxbridge::ffi::ReadImageHashMode;
+use crate::cxxbridge::ffi::ReadImageMissingFrameMode;
+use crate::cxxbridge::ffi::ValidationIssue;
+use crate::cxxbridge::ffi::ValidationIssueKind;
 use crate::data::FrameValue;
 use crate::data::HashValue;
 use crate::data::Identifier;
 use crate::data::NodeComputeMode;
+use crate::data::OperationCacheType;
+use crate::data::COLOR_BARS_HEIGHT;
+use crate::data::COLOR_BARS_WIDTH;
+use crate::hashutils::calculate_hash;
+use crate::hashutils::StableHasher;
 use crate::imageio;
+use crate::imagelayer;
+use crate::imagelayer::ImageLayer;
+use crate::imagelayer::LayerChannels;
+use crate::node::status::NodeStatusInfo;
 use crate::node::traits::Operation;
 use crate::node::traits::Validate;
 use crate::node::NodeImpl;
+use crate::ops::imageorient;
+use crate::ops::imageproxy;
 use crate::pathutils;
+use crate::pixelblock::pixelblock::PixelBlock;
 use crate::stream::StreamDataImpl;
 
+/// How many frames either side of a missing frame are searched for
+/// an existing file, when there is no frame range.
+const MISSING_FRAME_SEARCH_DISTANCE: i32 = 100;
+
 pub fn new(id: Identifier) -> NodeImpl {
     NodeImpl {
         node_type: NodeType::ReadImage,
         id,
-        status: NodeStatus::Uninitialized,
+        status: NodeStatusInfo::uninitialized(),
         compute: Box::new(ReadImageOperation::new()),
         validate: Box::new(ReadImageValidate::new()),
         attr_block: Box::new(ReadImageAttrs::new()),
@@ -62,16 +101,29 @@ pub struct ReadImageAttrs {
     pub enable: i32,
     pub use_cache: i32,
     pub file_path: String,
-    // TODO: Add more attributes:
-    //
-    // - Start frame - The first frame number to use.
-    // - End frame - the last frame number to use.
-    // - Missing frame behavior - What to do when an image is not found.
-    //
-    // - Pre-multiply
-    // - Un-premultiply
-    // - Input Color Space
-    // - Output channels.
+    pub hash_mode: i32, // index for ReadImageHashMode.
+    pub use_frame_range: i32,
+    pub first_frame: i32,
+    pub last_frame: i32,
+    pub before_mode: i32,        // index for ReadImageFrameRangeMode.
+    pub after_mode: i32,         // index for ReadImageFrameRangeMode.
+    pub missing_frame_mode: i32, // index for ReadImageMissingFrameMode.
+    pub color_space: String,     // empty uses the file's color space.
+    pub alpha_mode: i32,         // index for ReadImageAlphaMode.
+    pub layers: String,          // layer names, or "*" for all layers.
+    pub apply_orientation: i32,  // make the image Normal orientation.
+    pub proxy_scale: i32,        // index for ProxyScale.
+}
+
+/// Where the pixels of a ReadImage node come from, at a frame.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameSource {
+    /// An existing file.
+    File(String),
+    /// The file does not exist, and no other file could be used.
+    Missing(String),
+    /// A black image, for frames outside the frame range.
+    Black,
 }
 
 impl ReadImageOperation {
@@ -86,22 +138,664 @@ impl ReadImageAttrs {
             enable: 1,
             use_cache: 1,
             file_path: "".to_string(),
+            hash_mode: 0, // 0 = ReadImageHashMode::Path
+            use_frame_range: 0,
+            first_frame: 1,
+            last_frame: 1,
+            before_mode: 0,        // 0 = ReadImageFrameRangeMode::Hold
+            after_mode: 0,         // 0 = ReadImageFrameRangeMode::Hold
+            missing_frame_mode: 3, // 3 = ReadImageMissingFrameMode::ColorBars
+            color_space: "".to_string(),
+            alpha_mode: 0, // 0 = ReadImageAlphaMode::Auto
+            layers: "".to_string(),
+            apply_orientation: 1,
+            proxy_scale: 0, // 0 = ProxyScale::Full
+        }
+    }
+
+    pub fn from_attr_block(attr_block: &Box<dyn AttrBlock>) -> ReadImageAttrs {
+        ReadImageAttrs {
+            enable: attr_block.get_attr_i32("enable"),
+            use_cache: attr_block.get_attr_i32("use_cache"),
+            file_path: attr_block.get_attr_str("file_path").to_string(),
+            hash_mode: attr_block.get_attr_i32("hash_mode"),
+            use_frame_range: attr_block.get_attr_i32("use_frame_range"),
+            first_frame: attr_block.get_attr_i32("first_frame"),
+            last_frame: attr_block.get_attr_i32("last_frame"),
+            before_mode: attr_block.get_attr_i32("before_mode"),
+            after_mode: attr_block.get_attr_i32("after_mode"),
+            missing_frame_mode: attr_block.get_attr_i32("missing_frame_mode"),
+            color_space: attr_block.get_attr_str("color_space").to_string(),
+            alpha_mode: attr_block.get_attr_i32("alpha_mode"),
+            layers: attr_block.get_attr_str("layers").to_string(),
+            apply_orientation: attr_block.get_attr_i32("apply_orientation"),
+            proxy_scale: attr_block.get_attr_i32("proxy_scale"),
+        }
+    }
+
+    fn frame_path(&self, frame_num: i32) -> String {
+        pathutils::expand_string(self.file_path.to_string(), frame_num)
+    }
+
+    /// The frame number of the file to read at 'frame', or None if
+    /// the frame is black.
+    fn file_frame(&self, frame: FrameValue) -> Option<i32> {
+        let frame_num = frame.round().trunc() as i32;
+        if self.use_frame_range == 0 {
+            return Some(frame_num);
+        }
+        let first = self.first_frame.min(self.last_frame);
+        let last = self.first_frame.max(self.last_frame);
+        let mode = if frame_num < first {
+            ReadImageFrameRangeMode::from(self.before_mode)
+        } else if frame_num > last {
+            ReadImageFrameRangeMode::from(self.after_mode)
+        } else {
+            return Some(frame_num);
+        };
+        remap_frame(frame_num, first, last, mode)
+    }
+
+    /// The nearest frame to 'frame_num' with an existing file.
+    fn nearest_existing_path(&self, frame_num: i32) -> Option<String> {
+        let (min_frame, max_frame) = match self.use_frame_range {
+            0 => (
+                frame_num - MISSING_FRAME_SEARCH_DISTANCE,
+                frame_num + MISSING_FRAME_SEARCH_DISTANCE,
+            ),
+            _ => (
+                self.first_frame.min(self.last_frame),
+                self.first_frame.max(self.last_frame),
+            ),
+        };
+        let max_distance = (frame_num - min_frame).max(max_frame - frame_num);
+        for distance in 1..=max_distance {
+            // Earlier frames are preferred over later frames.
+            for candidate in &[frame_num - distance, frame_num + distance] {
+                if (*candidate < min_frame) || (*candidate > max_frame) {
+                    continue;
+                }
+                let path = self.frame_path(*candidate);
+                if Path::new(&path).is_file() {
+                    return Some(path);
+                }
+            }
+        }
+        None
+    }
+
+    /// The existing file nearest to 'frame' (inside the frame
+    /// range), used for the size of black frames.
+    fn nearest_frame_path(&self, frame: FrameValue) -> Option<String> {
+        let frame_num = frame.round().trunc() as i32;
+        let frame_num = match self.use_frame_range {
+            0 => frame_num,
+            _ => frame_num
+                .max(self.first_frame.min(self.last_frame))
+                .min(self.first_frame.max(self.last_frame)),
+        };
+        let path = self.frame_path(frame_num);
+        if Path::new(&path).is_file() {
+            return Some(path);
+        }
+        self.nearest_existing_path(frame_num)
+    }
+
+    /// Where the pixels come from at 'frame', after the frame range
+    /// and missing frame modes are applied.
+    pub fn frame_source(&self, frame: FrameValue) -> FrameSource {
+        let frame_num = match self.file_frame(frame) {
+            Some(value) => value,
+            None => return FrameSource::Black,
+        };
+        let path = self.frame_path(frame_num);
+        if Path::new(&path).is_file() {
+            return FrameSource::File(path);
+        }
+        match ReadImageMissingFrameMode::from(self.missing_frame_mode) {
+            ReadImageMissingFrameMode::Nearest => match self.nearest_existing_path(frame_num) {
+                Some(nearest_path) => FrameSource::File(nearest_path),
+                None => FrameSource::Missing(path),
+            },
+            _ => FrameSource::Missing(path),
+        }
+    }
+}
+
+/// Map a frame outside of the frame range 'first' to 'last' back
+/// into the frame range, or None for a black frame.
+fn remap_frame(
+    frame_num: i32,
+    first: i32,
+    last: i32,
+    mode: ReadImageFrameRangeMode,
+) -> Option<i32> {
+    let length = last - first + 1;
+    match mode {
+        ReadImageFrameRangeMode::Loop => Some(first + (frame_num - first).rem_euclid(length)),
+        ReadImageFrameRangeMode::Bounce => {
+            if length == 1 {
+                return Some(first);
+            }
+            let period = 2 * (length - 1);
+            let offset = (frame_num - first).rem_euclid(period);
+            match offset < length {
+                true => Some(first + offset),
+                false => Some(last - (offset - (length - 1))),
+            }
+        }
+        ReadImageFrameRangeMode::Black => None,
+        _ => Some(frame_num.max(first).min(last)),
+    }
+}
+
+/// Set the output for a frame without a file, using the missing
+/// frame mode.
+fn missing_frame_output(
+    attrs: &ReadImageAttrs,
+    frame: FrameValue,
+    path: &str,
+    hash_value: HashValue,
+    proxy_scale: ProxyScale,
+    output: &mut Rc<StreamDataImpl>,
+) -> NodeStatusInfo {
+    let message = format!("file not found: {}", path);
+    let (stream_data, status) = match ReadImageMissingFrameMode::from(attrs.missing_frame_mode) {
+        ReadImageMissingFrameMode::Error => (
+            color_bars_stream_data(proxy_scale),
+            NodeStatusInfo::error(NodeErrorKind::FileNotFound, message),
+        ),
+        ReadImageMissingFrameMode::Black => (
+            black_stream_data(attrs, frame, hash_value, proxy_scale),
+            NodeStatusInfo::warning(NodeErrorKind::FileNotFound, message),
+        ),
+        _ => (
+            color_bars_stream_data(proxy_scale),
+            NodeStatusInfo::warning(NodeErrorKind::FileNotFound, message),
+        ),
+    };
+    *output = std::rc::Rc::new(stream_data);
+    status
+}
+
+/// The proxy scale the image is read at; the lowest resolution of
+/// the 'proxy_scale' attribute and the graph's proxy scale.
+fn read_proxy_scale(proxy_scale: i32, graph_proxy_scale: ProxyScale) -> ProxyScale {
+    let proxy_scale = match ProxyScale::from(proxy_scale) {
+        ProxyScale::Uninitialized => ProxyScale::Full,
+        value => value,
+    };
+    imageproxy::combine_proxy_scale(proxy_scale, graph_proxy_scale)
+}
+
+/// The output of a ReadImage node for black frames, at the proxy
+/// scale.
+///
+/// Black frames are the size of the nearest existing frame, so a
+/// black frame in a sequence does not change the image size. If no
+/// frame exists the size of the color bars is used.
+fn black_stream_data(
+    attrs: &ReadImageAttrs,
+    frame: FrameValue,
+    hash_value: HashValue,
+    proxy_scale: ProxyScale,
+) -> StreamDataImpl {
+    let header = attrs
+        .nearest_frame_path(frame)
+        .and_then(|path| imageio::read_image_header(&path));
+    let (display_window, data_window, orientation) = match header {
+        Some(image) => (
+            image.display_window,
+            image.data_window,
+            image.spec.orientation(),
+        ),
+        None => {
+            let window = BBox2Di::new(0, 0, COLOR_BARS_WIDTH, COLOR_BARS_HEIGHT);
+            (window, window, ImageOrientation::Normal)
+        }
+    };
+    let mut display_window = imageproxy::proxy_window(display_window, proxy_scale);
+    let mut data_window = imageproxy::proxy_window(data_window, proxy_scale);
+    if (attrs.apply_orientation != 0)
+        && (orientation != ImageOrientation::Normal)
+        && (orientation != ImageOrientation::Uninitialized)
+    {
+        data_window = imageorient::orient_data_window(data_window, display_window, orientation);
+        display_window = imageorient::orient_display_window(display_window, orientation);
+    }
+    let num_channels = 4;
+    let blocksize = BlockSize::new(data_window.width(), data_window.height(), num_channels);
+
+    let mut stream_data = StreamDataImpl::new();
+    stream_data.set_hash(hash_value);
+    stream_data.set_display_window(display_window);
+    stream_data.set_data_window(data_window);
+    stream_data.set_pixel_block(Arc::new(PixelBlock::new(blocksize, DataType::Float32)));
+    stream_data.set_proxy_scale(proxy_scale);
+    stream_data
+}
+
+/// The default (color bars) output of a ReadImage node, at the proxy
+/// scale.
+fn color_bars_stream_data(proxy_scale: ProxyScale) -> StreamDataImpl {
+    let mut stream_data = StreamDataImpl::new();
+    if proxy_scale == ProxyScale::Full {
+        return stream_data;
+    }
+    let display_window = stream_data.display_window();
+    let data_window = stream_data.data_window();
+    let pixel_block =
+        imageproxy::proxy_pixel_block(&stream_data.pixel_block(), data_window, proxy_scale);
+    stream_data.set_display_window(imageproxy::proxy_window(display_window, proxy_scale));
+    stream_data.set_data_window(imageproxy::proxy_window(data_window, proxy_scale));
+    stream_data.set_pixel_block(Arc::new(pixel_block));
+    stream_data.set_proxy_scale(proxy_scale);
+    stream_data
+}
+
+/// Change the pixels and image spec read from disk, using the color
+/// space and alpha mode attributes.
+///
+/// The cache holds the pixels as they are read from disk, so images
+/// read ahead of time can be used, and the attributes are applied
+/// afterwards.
+fn apply_read_overrides(
+    attrs: &ReadImageAttrs,
+    pixel_block: Arc<PixelBlock>,
+    mut image_spec: ImageSpec,
+) -> (Arc<PixelBlock>, ImageSpec) {
+    if !attrs.color_space.is_empty() {
+        image_spec.set_color_space(attrs.color_space.clone());
+    }
+
+    let alpha_mode = ReadImageAlphaMode::from(attrs.alpha_mode);
+    let unpremultiply = match alpha_mode {
+        ReadImageAlphaMode::Premultiply => false,
+        ReadImageAlphaMode::Unpremultiply => true,
+        _ => return (pixel_block, image_spec),
+    };
+    if image_spec.unassociated_alpha() == unpremultiply {
+        // The pixels are already in the requested state.
+        return (pixel_block, image_spec);
+    }
+    image_spec.set_unassociated_alpha(unpremultiply);
+    if pixel_block.num_channels() != 4 {
+        // There is no alpha channel to change the pixels by.
+        return (pixel_block, image_spec);
+    }
+
+    let data_type = pixel_block.data_type();
+    let mut new_pixel_block = (*pixel_block).clone();
+    new_pixel_block.convert_into_data_type(DataType::Float32);
+    match unpremultiply {
+        true => unpremultiply_rgba_inplace(new_pixel_block.as_mut_slice_f32()),
+        false => premultiply_rgba_inplace(new_pixel_block.as_mut_slice_f32()),
+    }
+    new_pixel_block.convert_into_data_type(data_type);
+    (Arc::new(new_pixel_block), image_spec)
+}
+
+/// Flip, rotate or transpose the image and layers read from disk to
+/// the Normal orientation, when 'apply_orientation' is enabled.
+///
+/// Like 'apply_read_overrides', this is applied after the cache, so
+/// the cache holds the pixels as they are stored in the file.
+fn apply_read_orientation(attrs: &ReadImageAttrs, stream_data: &mut StreamDataImpl) {
+    let mut image_spec = stream_data.clone_image_spec();
+    let orientation = image_spec.orientation();
+    if (attrs.apply_orientation == 0)
+        || (orientation == ImageOrientation::Normal)
+        || (orientation == ImageOrientation::Uninitialized)
+    {
+        return;
+    }
+
+    let display_window = stream_data.display_window();
+    let data_window = stream_data.data_window();
+    let pixel_block = imageorient::orient_pixel_block(&stream_data.pixel_block(), orientation);
+    let layers: Vec<ImageLayer> = stream_data
+        .layers()
+        .iter()
+        .map(|layer| {
+            ImageLayer::new(
+                layer.name().to_string(),
+                layer.channel_names().to_vec(),
+                imageorient::orient_data_window(layer.data_window(), display_window, orientation),
+                Arc::new(imageorient::orient_pixel_block(
+                    layer.pixel_block(),
+                    orientation,
+                )),
+            )
+        })
+        .collect();
+    imageorient::orient_image_spec(&mut image_spec);
+
+    stream_data.set_data_window(imageorient::orient_data_window(
+        data_window,
+        display_window,
+        orientation,
+    ));
+    stream_data.set_display_window(imageorient::orient_display_window(
+        display_window,
+        orientation,
+    ));
+    stream_data.set_pixel_block(Arc::new(pixel_block));
+    stream_data.set_image_spec(image_spec);
+    stream_data.set_layers(layers);
+}
+
+/// Multiply the RGB channels by the alpha channel.
+fn premultiply_rgba_inplace(pixels: &mut [f32]) {
+    for pixel in pixels.chunks_exact_mut(4) {
+        let alpha = pixel[3];
+        pixel[0] *= alpha;
+        pixel[1] *= alpha;
+        pixel[2] *= alpha;
+    }
+}
+
+/// Divide the RGB channels by the alpha channel. Pixels with zero
+/// alpha are left unchanged.
+fn unpremultiply_rgba_inplace(pixels: &mut [f32]) {
+    for pixel in pixels.chunks_exact_mut(4) {
+        let alpha = pixel[3];
+        if alpha > 0.0 {
+            pixel[0] /= alpha;
+            pixel[1] /= alpha;
+            pixel[2] /= alpha;
+        }
+    }
+}
+
+// The hash of the content of each file, with the size and modified
+// time of the file when it was hashed.
+type ContentHashes = FxHashMap<String, (u64, SystemTime, HashValue)>;
+static CONTENT_HASHES: Mutex<Option<ContentHashes>> = Mutex::new(None);
+
+/// Hash the full content of the file at 'path'.
+///
+/// The hash is remembered until the size or modified time of the
+/// file changes, so the file is only read again after it changes.
+fn file_content_hash(path: &str) -> Option<HashValue> {
+    let metadata = fs::metadata(path).ok()?;
+    let size = metadata.len();
+    let modified = metadata.modified().ok()?;
+    {
+        let content_hashes = CONTENT_HASHES.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((hash_size, hash_modified, content_hash)) =
+            content_hashes.as_ref().and_then(|x| x.get(path))
+        {
+            if (*hash_size == size) && (*hash_modified == modified) {
+                return Some(*content_hash);
+            }
+        }
+    }
+
+    // The file is read without the lock held, so other files can be
+    // hashed at the same time.
+    let mut file = fs::File::open(path).ok()?;
+    let mut state = StableHasher::new();
+    let mut buffer = vec![0; 1 << 16];
+    loop {
+        match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(num_bytes) => state.write(&buffer[..num_bytes]),
+            Err(e) => {
+                warn!("Could not read file to hash: {} {}", path, e);
+                return None;
+            }
+        }
+    }
+    let content_hash = state.finish();
+
+    let mut content_hashes = CONTENT_HASHES.lock().unwrap_or_else(|e| e.into_inner());
+    content_hashes
+        .get_or_insert_with(FxHashMap::default)
+        .insert(path.to_string(), (size, modified, content_hash));
+    Some(content_hash)
+}
+
+/// Add the state of the file on disk to the hash, so that changes to
+/// the file are detected.
+fn hash_file_state(path: &str, hash_mode: ReadImageHashMode, state: &mut StableHasher) {
+    match hash_mode {
+        ReadImageHashMode::ModifiedTime => {
+            let metadata = match fs::metadata(path) {
+                Ok(value) => value,
+                Err(_) => return,
+            };
+            metadata.len().hash(state);
+            if let Ok(modified) = metadata.modified() {
+                if let Ok(duration) = modified.duration_since(std::time::UNIX_EPOCH) {
+                    duration.as_secs().hash(state);
+                    duration.subsec_nanos().hash(state);
+                }
+            }
+        }
+        ReadImageHashMode::Content => {
+            if let Some(content_hash) = file_content_hash(path) {
+                content_hash.hash(state);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Convert an image read from 'path' into an image at the proxy
+/// scale, ready to be stored in the cache.
+fn to_cached_image(
+    mut img: ImageShared,
+    path: &Path,
+    proxy_scale: ProxyScale,
+    start: Instant,
+) -> CachedImage {
+    imageproxy::proxy_image_in_place(&mut img, proxy_scale);
+    CachedImage {
+        pixel_block: Arc::new(*img.pixel_block),
+        spec: img.spec,
+        data_window: img.data_window,
+        display_window: img.display_window,
+        source_path: Some(path.to_path_buf()),
+        node_type: Some(NodeType::ReadImage),
+        compute_seconds: start.elapsed().as_secs_f32(),
+        cache_type: OperationCacheType::BoundByIO,
+        compressed: None,
+    }
+}
+
+/// Read the image at 'path' (at the proxy scale), ready to be
+/// stored in the cache.
+///
+/// This is used when the ReadImage node is computed, and to read
+/// images ahead of time, so both must create the same image.
+pub fn read_cached_image(
+    path: &Path,
+    proxy_scale: ProxyScale,
+    num_threads: i32,
+) -> Result<CachedImage, String> {
+    let start = Instant::now();
+    let path_string = path.to_string_lossy().to_string();
+    let img = imageio::read_image(&path_string, num_threads)?;
+    Ok(to_cached_image(img, path, proxy_scale, start))
+}
+
+/// The layers selected by the 'layers' attribute, found in the image
+/// at 'path'.
+fn selected_layers(attrs: &ReadImageAttrs, path: &Path) -> Vec<LayerChannels> {
+    if attrs.layers.is_empty() {
+        return Vec::new();
+    }
+    let path_string = path.to_string_lossy().to_string();
+    let channel_names = match imageio::read_image_channel_names(&path_string) {
+        Some(value) => value,
+        None => return Vec::new(),
+    };
+    let file_layers = imagelayer::find_layers(&channel_names);
+    let selected_layers = imagelayer::select_layers(file_layers, &attrs.layers);
+    for layer_name in imagelayer::split_layer_names(&attrs.layers) {
+        let found = selected_layers.iter().any(|l| l.name == layer_name);
+        if (layer_name != imagelayer::ALL_LAYERS) && !found {
+            warn!("Layer {:?} not found in image: {:?}", layer_name, path);
         }
     }
+    selected_layers
+}
+
+/// Read the image at 'path' and the layers selected by the 'layers'
+/// attribute, at the proxy scale.
+///
+/// Each layer is stored in the cache separately from the RGBA
+/// pixels, using the hash of the node and the layer name, and is
+/// pinned together with the node hash. The file is read at most once,
+/// for the image and all layers that are not found in the cache.
+///
+/// An error is returned if the image is not in the cache and the
+/// file cannot be read.
+fn read_image_and_layers(
+    attrs: &ReadImageAttrs,
+    path: &Path,
+    proxy_scale: ProxyScale,
+    hash_value: HashValue,
+    cache: &Box<CacheImpl>,
+) -> Result<(CachedImage, Vec<ImageLayer>), String> {
+    let use_cache = attrs.use_cache != 0;
+    let cache_get = |key: &HashValue| match use_cache {
+        true => cache.get(key),
+        false => None,
+    };
+
+    let layer_channels = selected_layers(attrs, path);
+    let layer_hashes: Vec<HashValue> = layer_channels
+        .iter()
+        .map(|layer| calculate_hash(&(hash_value, &layer.name)))
+        .collect();
+    let mut cached_layers: Vec<Option<CachedImage>> = layer_hashes.iter().map(cache_get).collect();
+    let cached_img = cache_get(&hash_value);
+    match cached_img {
+        Some(_) => debug!("Cache Hit"),
+        None => debug!("Cache Miss"),
+    };
+
+    let missing_layers: Vec<usize> = (0..cached_layers.len())
+        .filter(|index| cached_layers[*index].is_none())
+        .collect();
+    let cached_img = match (cached_img, missing_layers.is_empty()) {
+        (Some(value), true) => value,
+        (cached_img, _) => {
+            let start = Instant::now();
+            let num_threads = 0;
+            let path_string = path.to_string_lossy().to_string();
+            let channel_names: Vec<Vec<String>> = missing_layers
+                .iter()
+                .map(|index| layer_channels[*index].channel_names.clone())
+                .collect();
+            let (img, layer_imgs) = imageio::read_image_layers(
+                &path_string,
+                cached_img.is_none(),
+                &channel_names,
+                num_threads,
+            )?;
+            for (index, layer_img) in missing_layers.into_iter().zip(layer_imgs) {
+                if let Some(layer_img) = layer_img {
+                    let value = to_cached_image(layer_img, path, proxy_scale, start);
+                    if use_cache {
+                        cache.insert_child(hash_value, layer_hashes[index], value.clone());
+                    }
+                    cached_layers[index] = Some(value);
+                }
+            }
+            match (cached_img, img) {
+                (Some(value), _) => value,
+                (None, img) => {
+                    let img = img.unwrap_or_else(imageio::empty_image);
+                    let value = to_cached_image(img, path, proxy_scale, start);
+                    if use_cache {
+                        cache.insert(hash_value, value.clone());
+                    }
+                    value
+                }
+            }
+        }
+    };
+
+    let layers = layer_channels
+        .into_iter()
+        .zip(cached_layers)
+        .filter_map(|(layer, cached_layer)| {
+            let cached_layer = cached_layer?;
+            Some(ImageLayer::new(
+                layer.name,
+                layer.channel_names,
+                cached_layer.data_window,
+                cached_layer.pixel_block,
+            ))
+        })
+        .collect();
+    Ok((cached_img, layers))
 }
 
 impl Operation for ReadImageOperation {
+    fn cache_type(&self) -> OperationCacheType {
+        OperationCacheType::BoundByIO
+    }
+
+    fn cache_hash(
+        &self,
+        frame: FrameValue,
+        proxy_scale: ProxyScale,
+        node_type_id: u8,
+        attr_block: &Box<dyn AttrBlock>,
+        inputs_hash: &Vec<HashValue>,
+    ) -> HashValue {
+        let mut state = StableHasher::new();
+        node_type_id.hash(&mut state);
+        attr_block.attr_hash(frame, &mut state);
+        for input_hash in inputs_hash {
+            input_hash.hash(&mut state);
+        }
+        // Images read at full resolution keep the same hash values
+        // as before proxy scales existed.
+        let enable = attr_block.get_attr_i32("enable") == 1;
+        let proxy_scale = read_proxy_scale(attr_block.get_attr_i32("proxy_scale"), proxy_scale);
+        if enable && (proxy_scale != ProxyScale::Full) {
+            proxy_scale.hash(&mut state);
+        }
+        state.finish()
+    }
+
+    fn prefetch_path(
+        &self,
+        frame: FrameValue,
+        proxy_scale: ProxyScale,
+        attr_block: &Box<dyn AttrBlock>,
+    ) -> Option<(PathBuf, ProxyScale)> {
+        let attrs = ReadImageAttrs::from_attr_block(attr_block);
+        if (attrs.enable == 0) || (attrs.use_cache == 0) {
+            return None;
+        }
+        let path_expanded = match attrs.frame_source(frame) {
+            FrameSource::File(value) => value,
+            _ => return None,
+        };
+        let path = Path::new(&path_expanded).canonicalize().ok()?;
+        match path.is_file() {
+            true => Some((path, read_proxy_scale(attrs.proxy_scale, proxy_scale))),
+            false => None,
+        }
+    }
+
     fn compute(
         &mut self,
         frame: FrameValue,
+        proxy_scale: ProxyScale,
         _node_type_id: u8,
         attr_block: &Box<dyn AttrBlock>,
         hash_value: HashValue,
         node_compute_mode: NodeComputeMode,
         _inputs: &Vec<Rc<StreamDataImpl>>,
         output: &mut Rc<StreamDataImpl>,
-        cache: &mut Box<CacheImpl>,
-    ) -> NodeStatus {
+        cache: &Box<CacheImpl>,
+    ) -> NodeStatusInfo {
         debug!("ReadImageOperation.compute()");
         debug!(
             "ReadImageOperation NodeComputeMode={:#?}",
@@ -116,20 +810,45 @@ impl Operation for ReadImageOperation {
             if enable == false {
                 let stream_data = StreamDataImpl::new();
                 *output = std::rc::Rc::new(stream_data);
-                return NodeStatus::Warning;
+                return NodeStatusInfo::warning(
+                    NodeErrorKind::Disabled,
+                    "node is disabled".to_string(),
+                );
             }
-            let file_path = attr_block.get_attr_str("file_path");
-            let frame_num = frame.round().trunc() as i32;
-            let path_expanded = pathutils::expand_string(file_path.to_string(), frame_num);
+            let attrs = ReadImageAttrs::from_attr_block(attr_block);
+            let proxy_scale = read_proxy_scale(attrs.proxy_scale, proxy_scale);
+            let path_expanded = match attrs.frame_source(frame) {
+                FrameSource::File(value) => value,
+                FrameSource::Black => {
+                    let stream_data = black_stream_data(&attrs, frame, hash_value, proxy_scale);
+                    *output = std::rc::Rc::new(stream_data);
+                    return NodeStatusInfo::valid();
+                }
+                FrameSource::Missing(value) => {
+                    return missing_frame_output(
+                        &attrs,
+                        frame,
+                        &value,
+                        hash_value,
+                        proxy_scale,
+                        output,
+                    );
+                }
+            };
 
             let path = match Path::new(&path_expanded).canonicalize() {
                 Ok(full_path) => full_path,
                 Err(_) => {
                     // The path could not be canonicalised, probably
                     // meaning the path does not exist.
-                    let stream_data = StreamDataImpl::new();
-                    *output = std::rc::Rc::new(stream_data);
-                    return NodeStatus::Warning;
+                    return missing_frame_output(
+                        &attrs,
+                        frame,
+                        &path_expanded,
+                        hash_value,
+                        proxy_scale,
+                        output,
+                    );
                 }
             };
 
@@ -137,51 +856,21 @@ impl Operation for ReadImageOperation {
             if path.is_file() == true {
                 let mut stream_data = StreamDataImpl::new();
 
-                let use_cache = attr_block.get_attr_i32("use_cache") != 0;
-                let (pixel_block, image_spec, data_window, display_window) = match use_cache {
-                    true => match cache.get(&hash_value) {
-                        Some(cached_img) => {
-                            debug!("Cache Hit");
-                            (
-                                cached_img.pixel_block.clone(),
-                                cached_img.spec.clone(),
-                                cached_img.data_window,
-                                cached_img.display_window,
-                            )
-                        }
-                        _ => {
-                            debug!("Cache Miss");
-                            let num_threads = 0;
-                            let img = imageio::read_image(&path_expanded, num_threads);
-                            let pixel_block_rc = Rc::new(*img.pixel_block);
-                            let cached_img = CachedImage {
-                                pixel_block: pixel_block_rc.clone(),
-                                spec: img.spec.clone(),
-                                data_window: img.data_window,
-                                display_window: img.display_window,
-                            };
-                            cache.insert(hash_value, cached_img);
-                            (
-                                pixel_block_rc.clone(),
-                                img.spec.clone(),
-                                img.data_window,
-                                img.display_window,
-                            )
+                let (cached_img, layers) =
+                    match read_image_and_layers(&attrs, &path, proxy_scale, hash_value, cache) {
+                        Ok(value) => value,
+                        Err(e) => {
+                            let stream_data = StreamDataImpl::new();
+                            *output = std::rc::Rc::new(stream_data);
+                            let message =
+                                format!("could not read image: {}: {}", path.display(), e);
+                            return NodeStatusInfo::error(NodeErrorKind::ComputeFailure, message);
                         }
-                    },
-                    false => {
-                        debug!("From Disk");
-                        let num_threads = 0;
-                        let img = imageio::read_image(&path_expanded, num_threads);
-                        let pixel_block_rc = Rc::new(*img.pixel_block);
-                        (
-                            pixel_block_rc.clone(),
-                            img.spec.clone(),
-                            img.data_window,
-                            img.display_window,
-                        )
-                    }
-                };
+                    };
+                let data_window = cached_img.data_window;
+                let display_window = cached_img.display_window;
+                let (pixel_block, image_spec) =
+                    apply_read_overrides(&attrs, cached_img.pixel_block, cached_img.spec);
 
                 // debug!(
                 //     "pixel_block: {:?} x {:?} x {:?}",
@@ -217,29 +906,79 @@ impl Operation for ReadImageOperation {
                 stream_data.set_hash(hash_value);
                 stream_data.set_pixel_block(pixel_block);
                 stream_data.set_image_spec(image_spec);
+                stream_data.set_layers(layers);
+                stream_data.set_proxy_scale(proxy_scale);
+                apply_read_orientation(&attrs, &mut stream_data);
 
                 *output = std::rc::Rc::new(stream_data);
             } else {
-                let enable = attr_block.get_attr_i32("enable");
-                if enable != 1 {
-                    let mut stream_data = StreamDataImpl::new();
-                    stream_data.set_hash(hash_value);
-                    *output = std::rc::Rc::new(stream_data);
-                    return NodeStatus::Valid;
-                }
+                // The path exists, but it is a directory or some
+                // other non-file.
+                let mut stream_data = StreamDataImpl::new();
+                stream_data.set_hash(hash_value);
+                *output = std::rc::Rc::new(stream_data);
+                return NodeStatusInfo::warning(
+                    NodeErrorKind::FileNotFound,
+                    format!("path is not a file: {}", path_expanded),
+                );
             }
         }
-        NodeStatus::Valid
+        NodeStatusInfo::valid()
     }
 }
 
 impl AttrBlock for ReadImageAttrs {
-    fn attr_hash(&self, frame: FrameValue, state: &mut DefaultHasher) {
+    fn attr_hash(&self, frame: FrameValue, state: &mut StableHasher) {
         self.enable.hash(state);
         if self.enable == 1 {
-            let frame_num = frame.round().trunc() as i32;
-            let path_expanded = pathutils::expand_string(self.file_path.to_string(), frame_num);
+            // Frames that read the same file have the same hash, so
+            // held frames share the same cached image. The default
+            // modes do not add anything, so existing hash values are
+            // unchanged.
+            let path_expanded = match self.frame_source(frame) {
+                FrameSource::File(value) => value,
+                FrameSource::Missing(value) => {
+                    let missing_frame_mode =
+                        ReadImageMissingFrameMode::from(self.missing_frame_mode);
+                    if missing_frame_mode != ReadImageMissingFrameMode::ColorBars {
+                        self.missing_frame_mode.hash(state);
+                    }
+                    if missing_frame_mode == ReadImageMissingFrameMode::Black {
+                        self.nearest_frame_path(frame).hash(state);
+                    }
+                    value
+                }
+                FrameSource::Black => {
+                    // Black frames are the size of the nearest frame.
+                    state.write(b"Black");
+                    self.nearest_frame_path(frame).hash(state);
+                    return;
+                }
+            };
             path_expanded.hash(state);
+            let hash_mode = ReadImageHashMode::from(self.hash_mode);
+            if hash_mode != ReadImageHashMode::Path {
+                self.hash_mode.hash(state);
+                hash_file_state(&path_expanded, hash_mode, state);
+            }
+            if !self.color_space.is_empty() {
+                self.color_space.hash(state);
+            }
+            if ReadImageAlphaMode::from(self.alpha_mode) != ReadImageAlphaMode::Auto {
+                self.alpha_mode.hash(state);
+            }
+            if !self.layers.is_empty() {
+                self.layers.hash(state);
+            }
+            if self.apply_orientation == 0 {
+                self.apply_orientation.hash(state);
+            }
+            // Each backend may read a file differently, so images
+            // read by other backends are not shared in the cache.
+            let backend = imageio::image_io_backend();
+            if backend != ImageIOBackend::OpenImageIO {
+                backend.hash(state);
+            }
         }
     }
 
@@ -248,6 +987,18 @@ impl AttrBlock for ReadImageAttrs {
             "enable" => AttrState::Exists,
             "use_cache" => AttrState::Exists,
             "file_path" => AttrState::Exists,
+            "hash_mode" => AttrState::Exists,
+            "use_frame_range" => AttrState::Exists,
+            "first_frame" => AttrState::Exists,
+            "last_frame" => AttrState::Exists,
+            "before_mode" => AttrState::Exists,
+            "after_mode" => AttrState::Exists,
+            "missing_frame_mode" => AttrState::Exists,
+            "color_space" => AttrState::Exists,
+            "alpha_mode" => AttrState::Exists,
+            "layers" => AttrState::Exists,
+            "apply_orientation" => AttrState::Exists,
+            "proxy_scale" => AttrState::Exists,
             _ => AttrState::Missing,
         }
     }
@@ -255,6 +1006,8 @@ impl AttrBlock for ReadImageAttrs {
     fn get_attr_str(&self, name: &str) -> &str {
         match name {
             "file_path" => &self.file_path,
+            "color_space" => &self.color_space,
+            "layers" => &self.layers,
             _ => "",
         }
     }
@@ -262,6 +1015,8 @@ impl AttrBlock for ReadImageAttrs {
     fn set_attr_str(&mut self, name: &str, value: &str) {
         match name {
             "file_path" => self.file_path = value.to_string(),
+            "color_space" => self.color_space = value.to_string(),
+            "layers" => self.layers = value.to_string(),
             _ => (),
         };
     }
@@ -270,6 +1025,16 @@ impl AttrBlock for ReadImageAttrs {
         match name {
             "enable" => self.enable,
             "use_cache" => self.use_cache,
+            "hash_mode" => self.hash_mode,
+            "use_frame_range" => self.use_frame_range,
+            "first_frame" => self.first_frame,
+            "last_frame" => self.last_frame,
+            "before_mode" => self.before_mode,
+            "after_mode" => self.after_mode,
+            "missing_frame_mode" => self.missing_frame_mode,
+            "alpha_mode" => self.alpha_mode,
+            "apply_orientation" => self.apply_orientation,
+            "proxy_scale" => self.proxy_scale,
             _ => 0,
         }
     }
@@ -278,6 +1043,16 @@ impl AttrBlock for ReadImageAttrs {
         match name {
             "enable" => self.enable = value,
             "use_cache" => self.use_cache = value,
+            "hash_mode" => self.hash_mode = value,
+            "use_frame_range" => self.use_frame_range = value,
+            "first_frame" => self.first_frame = value,
+            "last_frame" => self.last_frame = value,
+            "before_mode" => self.before_mode = value,
+            "after_mode" => self.after_mode = value,
+            "missing_frame_mode" => self.missing_frame_mode = value,
+            "alpha_mode" => self.alpha_mode = value,
+            "apply_orientation" => self.apply_orientation = value,
+            "proxy_scale" => self.proxy_scale = value,
             _ => (),
         };
     }
@@ -322,4 +1097,134 @@ impl Validate for ReadImageValidate {
         }
         node_compute_modes
     }
+
+    fn required_input_count(&self) -> usize {
+        0
+    }
+
+    fn validate_attrs(
+        &self,
+        node_id: Identifier,
+        attr_block: &Box<dyn AttrBlock>,
+        frames: &[FrameValue],
+        _input_num_channels: &Vec<Option<i32>>,
+        issues: &mut Vec<ValidationIssue>,
+    ) -> Option<i32> {
+        let enable = attr_block.get_attr_i32("enable") != 0;
+        if enable == false {
+            return None;
+        }
+
+        let hash_mode = attr_block.get_attr_i32("hash_mode");
+        if ReadImageHashMode::from(hash_mode) == ReadImageHashMode::Uninitialized {
+            issues.push(ValidationIssue::invalid_enum_value(
+                node_id,
+                "hash_mode",
+                hash_mode,
+            ));
+        }
+
+        for attr_name in &["before_mode", "after_mode"] {
+            let value = attr_block.get_attr_i32(attr_name);
+            if ReadImageFrameRangeMode::from(value) == ReadImageFrameRangeMode::Uninitialized {
+                issues.push(ValidationIssue::invalid_enum_value(
+                    node_id, attr_name, value,
+                ));
+            }
+        }
+
+        let missing_frame_mode = attr_block.get_attr_i32("missing_frame_mode");
+        let missing_frame_mode_enum = ReadImageMissingFrameMode::from(missing_frame_mode);
+        if missing_frame_mode_enum == ReadImageMissingFrameMode::Uninitialized {
+            issues.push(ValidationIssue::invalid_enum_value(
+                node_id,
+                "missing_frame_mode",
+                missing_frame_mode,
+            ));
+        }
+
+        let alpha_mode = attr_block.get_attr_i32("alpha_mode");
+        if ReadImageAlphaMode::from(alpha_mode) == ReadImageAlphaMode::Uninitialized {
+            issues.push(ValidationIssue::invalid_enum_value(
+                node_id,
+                "alpha_mode",
+                alpha_mode,
+            ));
+        }
+
+        let proxy_scale = attr_block.get_attr_i32("proxy_scale");
+        if ProxyScale::from(proxy_scale) == ProxyScale::Uninitialized {
+            issues.push(ValidationIssue::invalid_enum_value(
+                node_id,
+                "proxy_scale",
+                proxy_scale,
+            ));
+        }
+
+        let color_space = attr_block.get_attr_str("color_space");
+        if !color_space.is_empty() && !color_space_exists(color_space) {
+            issues.push(ValidationIssue::new(
+                node_id,
+                ValidationIssueKind::UnknownColorSpace,
+                format!("unknown color space: {:?}", color_space),
+            ));
+        }
+
+        let attrs = ReadImageAttrs::from_attr_block(attr_block);
+        let mut first_existing_path = None;
+        for frame in frames {
+            match attrs.frame_source(*frame) {
+                FrameSource::File(path_expanded) => {
+                    if first_existing_path.is_none() {
+                        first_existing_path = Some(path_expanded);
+                    }
+                }
+                // Missing frames are expected to be black.
+                FrameSource::Missing(_)
+                    if missing_frame_mode_enum == ReadImageMissingFrameMode::Black => {}
+                FrameSource::Missing(path_expanded) => {
+                    issues.push(ValidationIssue::with_frame(
+                        node_id,
+                        ValidationIssueKind::FileNotFound,
+                        *frame,
+                        format!("file not found: {}", path_expanded),
+                    ));
+                }
+                FrameSource::Black => (),
+            }
+        }
+
+        // Only the first image is checked, images in a sequence are
+        // expected to have the same channels.
+        let path = first_existing_path?;
+        let channel_names = imageio::read_image_channel_names(&path)?;
+        let file_layers = imagelayer::find_layers(&channel_names);
+        for layer_name in imagelayer::split_layer_names(&attrs.layers) {
+            let found = file_layers.iter().any(|l| l.name == layer_name);
+            if (layer_name != imagelayer::ALL_LAYERS) && !found {
+                issues.push(ValidationIssue::new(
+                    node_id,
+                    ValidationIssueKind::LayerNotFound,
+                    format!("layer {:?} not found in image: {}", layer_name, path),
+                ));
+            }
+        }
+
+        // Channels after RGBA are read as layers.
+        let num_channels = channel_names
+            .iter()
+            .filter(|name| imagelayer::RGBA_CHANNEL_NAMES.contains(&name.as_str()))
+            .count() as i32;
+        if num_channels < 3 {
+            issues.push(ValidationIssue::new(
+                node_id,
+                ValidationIssueKind::ChannelCountMismatch,
+                format!(
+                    "image has {} channel(s), at least 3 (RGB) are needed: {}",
+                    num_channels, path
+                ),
+            ));
+        }
+        Some(num_channels)
+    }
 }
@@ -20,9 +20,9 @@
  */
 
 use log::{debug, error};
-use std::collections::hash_map::DefaultHasher;
 use std::hash::Hash;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::attrblock::AttrBlock;
 use crate::cache::CacheImpl;
@@ -32,12 +32,18 @@ use crate::cxxbridge::ffi::BBox2Di;
 use crate::cxxbridge::ffi::DataType;
 use crate::cxxbridge::ffi::ImageShared;
 use crate::cxxbridge::ffi::ImageSpec;
-use crate::cxxbridge::ffi::NodeStatus;
+use crate::cxxbridge::ffi::NodeErrorKind;
 use crate::cxxbridge::ffi::NodeType;
+use crate::cxxbridge::ffi::ProxyScale;
 use crate::data::FrameValue;
 use crate::data::HashValue;
 use crate::data::Identifier;
 use crate::data::NodeComputeMode;
+use crate::data::OperationCacheType;
+use crate::hashutils::calculate_hash;
+use crate::hashutils::StableHasher;
+use crate::imagelayer::ImageLayer;
+use crate::node::status::NodeStatusInfo;
 use crate::node::traits::Operation;
 use crate::node::traits::Validate;
 use crate::node::NodeImpl;
@@ -49,7 +55,7 @@ pub fn new(id: Identifier) -> NodeImpl {
     NodeImpl {
         node_type: NodeType::ResampleImage,
         id,
-        status: NodeStatus::Uninitialized,
+        status: NodeStatusInfo::uninitialized(),
         compute: Box::new(ResampleImageOperation::new()),
         validate: Box::new(ResampleImageValidate::new()),
         attr_block: Box::new(ResampleImageAttrs::new()),
@@ -84,6 +90,20 @@ impl ResampleImageAttrs {
     }
 }
 
+fn resample(mut src_img: ImageShared, factor: i32, interpolate: bool) -> (bool, ImageShared) {
+    // Destination image.
+    let mut dst_img = ImageShared {
+        pixel_block: Box::new(PixelBlock::empty(DataType::Float32)),
+        display_window: BBox2Di::new(0, 0, 0, 0),
+        data_window: BBox2Di::new(0, 0, 0, 0),
+        spec: ImageSpec::new(),
+    };
+
+    // Do work and use destination image.
+    let ok = imageresample::image_resample(&mut src_img, &mut dst_img, factor, interpolate);
+    (ok, dst_img)
+}
+
 fn do_image_process(
     stream_data: &StreamDataImpl,
     factor: i32,
@@ -92,38 +112,91 @@ fn do_image_process(
     // Source image.
     let src_pixel_block = stream_data.clone_pixel_block();
     let src_image_spec = stream_data.clone_image_spec();
-    let mut src_img = ImageShared {
+    let src_img = ImageShared {
         pixel_block: Box::new(src_pixel_block),
         display_window: stream_data.display_window(),
         data_window: stream_data.data_window(),
         spec: src_image_spec,
     };
+    resample(src_img, factor, interpolate)
+}
 
-    // Destination image.
-    let mut dst_img = ImageShared {
-        pixel_block: Box::new(PixelBlock::empty(DataType::Float32)),
-        display_window: BBox2Di::new(0, 0, 0, 0),
-        data_window: BBox2Di::new(0, 0, 0, 0),
-        spec: ImageSpec::new(),
-    };
-
-    // Do work and use destination image.
-    let ok = imageresample::image_resample(&mut src_img, &mut dst_img, factor, interpolate);
-    (ok, dst_img)
+/// Resample the layers of 'stream_data' the same as the image, so
+/// each layer keeps matching the resolution of the image.
+///
+/// Each layer is stored in the cache separately from the RGBA
+/// pixels, using the hash of the node and the layer name, and is
+/// pinned together with the node hash.
+fn resample_layers(
+    stream_data: &StreamDataImpl,
+    factor: i32,
+    interpolate: bool,
+    hash_value: HashValue,
+    use_cache: bool,
+    cache: &Box<CacheImpl>,
+) -> Vec<ImageLayer> {
+    let mut layers = Vec::new();
+    for layer in stream_data.layers() {
+        let layer_hash = calculate_hash(&(hash_value, layer.name()));
+        let cached_value = match use_cache {
+            true => cache.get(&layer_hash),
+            false => None,
+        };
+        let (pixel_block, data_window) = match cached_value {
+            Some(cached_img) => (cached_img.pixel_block, cached_img.data_window),
+            None => {
+                let src_img = ImageShared {
+                    pixel_block: Box::new((**layer.pixel_block()).clone()),
+                    display_window: stream_data.display_window(),
+                    data_window: layer.data_window(),
+                    spec: ImageSpec::new(),
+                };
+                let (ok, img) = resample(src_img, factor, interpolate);
+                if !ok {
+                    error!("ResampleImage failed on layer: {:?}", layer.name());
+                    continue;
+                }
+                let pixel_block_arc = Arc::new(*img.pixel_block);
+                if use_cache {
+                    let cached_img = CachedImage {
+                        pixel_block: pixel_block_arc.clone(),
+                        spec: img.spec,
+                        data_window: img.data_window,
+                        display_window: img.display_window,
+                        source_path: None,
+                        node_type: Some(NodeType::ResampleImage),
+                        compute_seconds: 0.0,
+                        cache_type: OperationCacheType::BoundByCPU,
+                        compressed: None,
+                    };
+                    cache.insert_child(hash_value, layer_hash, cached_img);
+                }
+                (pixel_block_arc, img.data_window)
+            }
+        };
+        layers.push(ImageLayer::new(
+            layer.name().to_string(),
+            layer.channel_names().to_vec(),
+            data_window,
+            pixel_block,
+        ));
+    }
+    layers
 }
 
 impl Operation for ResampleImageOperation {
     fn compute(
         &mut self,
         _frame: FrameValue,
+        _proxy_scale: ProxyScale,
         _node_type_id: u8,
         attr_block: &Box<dyn AttrBlock>,
         hash_value: HashValue,
         node_compute_mode: NodeComputeMode,
         inputs: &Vec<Rc<StreamDataImpl>>,
         output: &mut Rc<StreamDataImpl>,
-        cache: &mut Box<CacheImpl>,
-    ) -> NodeStatus {
+        cache: &Box<CacheImpl>,
+    ) -> NodeStatusInfo {
         debug!("ResampleImageOperation.compute()");
         debug!(
             "ResampleImageOperation NodeComputeMode={:#?}",
@@ -137,7 +210,10 @@ impl Operation for ResampleImageOperation {
             // No input given, return an empty default stream.
             let stream_data = StreamDataImpl::new();
             *output = std::rc::Rc::new(stream_data);
-            return NodeStatus::Warning;
+            return NodeStatusInfo::warning(
+                NodeErrorKind::MissingInput,
+                "no input connected".to_string(),
+            );
         }
 
         let enable = attr_block.get_attr_i32("enable");
@@ -145,14 +221,14 @@ impl Operation for ResampleImageOperation {
             let input = &inputs[0].clone();
             let stream_data = (**input).clone();
             *output = std::rc::Rc::new(stream_data);
-            return NodeStatus::Valid;
+            return NodeStatusInfo::valid();
         }
 
         debug_assert!(inputs.len() == 1);
         let input = &inputs[0].clone();
         let mut stream_data = (**input).clone();
 
-        let mut status = NodeStatus::Valid;
+        let mut status = NodeStatusInfo::valid();
         let factor = attr_block.get_attr_i32("factor");
         if factor != 0 {
             let interpolate = attr_block.get_attr_i32("interpolate") != 0;
@@ -173,35 +249,56 @@ impl Operation for ResampleImageOperation {
                         let (ok, img) = do_image_process(&mut stream_data, factor, interpolate);
                         if ok == false {
                             error!("ResampleImage failed!");
-                            status = NodeStatus::Error;
+                            status = NodeStatusInfo::error(
+                                NodeErrorKind::ComputeFailure,
+                                format!("failed to resample image: factor={}", factor),
+                            );
                         }
 
-                        let pixel_block_rc = Rc::new(*img.pixel_block);
+                        let pixel_block_arc = Arc::new(*img.pixel_block);
                         let cached_img = CachedImage {
-                            pixel_block: pixel_block_rc.clone(),
+                            pixel_block: pixel_block_arc.clone(),
                             spec: img.spec,
                             data_window: img.data_window,
                             display_window: img.display_window,
+                            source_path: None,
+                            node_type: Some(NodeType::ResampleImage),
+                            compute_seconds: 0.0,
+                            cache_type: self.cache_type(),
+                            compressed: None,
                         };
                         cache.insert(hash_value, cached_img);
-                        (pixel_block_rc.clone(), img.data_window, img.display_window)
+                        (pixel_block_arc.clone(), img.data_window, img.display_window)
                     }
                 },
                 false => {
                     let (ok, img) = do_image_process(&mut stream_data, factor, interpolate);
                     if ok == false {
                         error!("ResampleImage failed!");
-                        status = NodeStatus::Error;
+                        status = NodeStatusInfo::error(
+                            NodeErrorKind::ComputeFailure,
+                            format!("failed to resample image: factor={}", factor),
+                        );
                     }
 
-                    let pixel_block_rc = Rc::new(*img.pixel_block);
-                    (pixel_block_rc.clone(), img.data_window, img.display_window)
+                    let pixel_block_arc = Arc::new(*img.pixel_block);
+                    (pixel_block_arc.clone(), img.data_window, img.display_window)
                 }
             };
 
+            let layers = resample_layers(
+                &stream_data,
+                factor,
+                interpolate,
+                hash_value,
+                use_cache,
+                cache,
+            );
+
             stream_data.set_data_window(data_window);
             stream_data.set_display_window(display_window);
             stream_data.set_pixel_block(pixel_block);
+            stream_data.set_layers(layers);
             stream_data.set_hash(hash_value);
         } else {
             // Use source image.
@@ -215,7 +312,7 @@ impl Operation for ResampleImageOperation {
 }
 
 impl AttrBlock for ResampleImageAttrs {
-    fn attr_hash(&self, _frame: FrameValue, state: &mut DefaultHasher) {
+    fn attr_hash(&self, _frame: FrameValue, state: &mut StableHasher) {
         self.enable.hash(state);
         if self.enable == 1 {
             if self.factor != 0 {
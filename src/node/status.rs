@@ -0,0 +1,138 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+use crate::cxxbridge::ffi::NodeErrorKind;
+use crate::cxxbridge::ffi::NodeStatus;
+use crate::cxxbridge::ffi::ValidationIssue;
+use crate::cxxbridge::ffi::ValidationIssueKind;
+use crate::data::FrameValue;
+use crate::data::Identifier;
+
+/// The result of computing a node.
+///
+/// A Warning or Error status should always come with an error kind
+/// and a message the user can read, such as "file not found:
+/// /path/plate.1001.exr", so the host application can tell the
+/// reasons apart.
+#[derive(Debug, Clone)]
+pub struct NodeStatusInfo {
+    status: NodeStatus,
+    error_kind: NodeErrorKind,
+    message: String,
+}
+
+impl NodeStatusInfo {
+    pub fn new(status: NodeStatus, error_kind: NodeErrorKind, message: String) -> NodeStatusInfo {
+        NodeStatusInfo {
+            status,
+            error_kind,
+            message,
+        }
+    }
+
+    /// The status of a node that has not been computed yet.
+    pub fn uninitialized() -> NodeStatusInfo {
+        NodeStatusInfo::new(
+            NodeStatus::Uninitialized,
+            NodeErrorKind::None,
+            "".to_string(),
+        )
+    }
+
+    /// The node computed without any problems.
+    pub fn valid() -> NodeStatusInfo {
+        NodeStatusInfo::new(NodeStatus::Valid, NodeErrorKind::None, "".to_string())
+    }
+
+    /// The node computed, but the result may not be what the user
+    /// expects.
+    pub fn warning(error_kind: NodeErrorKind, message: String) -> NodeStatusInfo {
+        NodeStatusInfo::new(NodeStatus::Warning, error_kind, message)
+    }
+
+    /// The node failed to compute.
+    pub fn error(error_kind: NodeErrorKind, message: String) -> NodeStatusInfo {
+        NodeStatusInfo::new(NodeStatus::Error, error_kind, message)
+    }
+
+    pub fn status(&self) -> NodeStatus {
+        self.status
+    }
+
+    pub fn error_kind(&self) -> NodeErrorKind {
+        self.error_kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Default for NodeStatusInfo {
+    fn default() -> Self {
+        NodeStatusInfo::uninitialized()
+    }
+}
+
+impl ValidationIssue {
+    pub fn new(node_id: Identifier, kind: ValidationIssueKind, message: String) -> ValidationIssue {
+        ValidationIssue::with_frame(node_id, kind, 0.0, message)
+    }
+
+    pub fn with_frame(
+        node_id: Identifier,
+        kind: ValidationIssueKind,
+        frame: FrameValue,
+        message: String,
+    ) -> ValidationIssue {
+        ValidationIssue {
+            node_id,
+            kind,
+            frame,
+            message,
+        }
+    }
+
+    /// An attribute holds an index that is not a valid enum value.
+    pub fn invalid_enum_value(node_id: Identifier, attr_name: &str, value: i32) -> ValidationIssue {
+        ValidationIssue::new(
+            node_id,
+            ValidationIssueKind::InvalidEnumValue,
+            format!("invalid value for attribute \"{}\": {}", attr_name, value),
+        )
+    }
+
+    pub fn node_id(&self) -> Identifier {
+        self.node_id
+    }
+
+    pub fn kind(&self) -> ValidationIssueKind {
+        self.kind
+    }
+
+    pub fn frame(&self) -> FrameValue {
+        self.frame
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
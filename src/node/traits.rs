@@ -19,17 +19,22 @@
  *
  */
 
-use std::collections::hash_map::DefaultHasher;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 use crate::attrblock::AttrBlock;
 use crate::cache::CacheImpl;
-use crate::cxxbridge::ffi::NodeStatus;
+use crate::cxxbridge::ffi::ProxyScale;
+use crate::cxxbridge::ffi::ValidationIssue;
 use crate::data::FrameValue;
 use crate::data::HashValue;
+use crate::data::Identifier;
 use crate::data::NodeComputeMode;
+use crate::data::OperationCacheType;
+use crate::hashutils::StableHasher;
+use crate::node::status::NodeStatusInfo;
 use crate::node::NodeImpl;
 use crate::stream::StreamDataImpl;
 
@@ -42,32 +47,57 @@ pub trait Validate: std::fmt::Debug {
         node_compute_mode: NodeComputeMode,
         input_nodes: &Vec<&Box<NodeImpl>>,
     ) -> Vec<NodeComputeMode>;
+
+    /// The number of inputs that must be connected for the node to
+    /// compute correctly.
+    fn required_input_count(&self) -> usize {
+        1
+    }
+
+    /// Check the node's attributes for problems, without computing
+    /// any pixels. Problems found are added to 'issues'.
+    ///
+    /// 'input_num_channels' has the number of channels expected from
+    /// each connected input, if known. Returns the number of channels
+    /// the node is expected to output, if known.
+    fn validate_attrs(
+        &self,
+        _node_id: Identifier,
+        _attr_block: &Box<dyn AttrBlock>,
+        _frames: &[FrameValue],
+        input_num_channels: &Vec<Option<i32>>,
+        _issues: &mut Vec<ValidationIssue>,
+    ) -> Option<i32> {
+        input_num_channels.first().copied().flatten()
+    }
 }
 
 pub trait Operation: std::fmt::Debug {
-    // TODO: Add a "OperationCacheType". This will allow us to
-    // categorize the Operation in terms of cache.
-    //
-    // For example we could have a "Trivial" OperationCacheType, which
-    // would indicate that the operation is trivial to compute (ie. do
-    // not store this in the cache because we can easily re-compute
-    // it).
-    //
-    // Another example type might be "BoundByIO", which would indicate
-    // that the operation can be asynconously computed.
-    //
-    // Another example type might be "BoundByCPU", which would
-    // indicate that the operation should be multi-threaded to be
-    // computed in the shortest time possible.
+    /// Categorize the Operation in terms of the cache.
+    ///
+    /// "Trivial" operations are easy to re-compute, so their cached
+    /// images are evicted first and are not written to the disk
+    /// cache. "BoundByIO" operations spend most of their time reading
+    /// or writing files (and could be computed asynchronously).
+    /// "BoundByCPU" operations spend their time computing pixels.
+    fn cache_type(&self) -> OperationCacheType {
+        OperationCacheType::BoundByCPU
+    }
 
+    /// The hash of the node's output.
+    ///
+    /// 'proxy_scale' is the graph's proxy scale. Only nodes that
+    /// read images need to add it to the hash, other nodes get the
+    /// proxy scale from their inputs (and the input hash values).
     fn cache_hash(
         &self,
         frame: FrameValue,
+        _proxy_scale: ProxyScale,
         node_type_id: u8,
         attr_block: &Box<dyn AttrBlock>,
         inputs_hash: &Vec<HashValue>,
     ) -> HashValue {
-        let mut state = DefaultHasher::new();
+        let mut state = StableHasher::new();
         node_type_id.hash(&mut state);
         attr_block.attr_hash(frame, &mut state);
         for input_hash in inputs_hash {
@@ -76,6 +106,54 @@ pub trait Operation: std::fmt::Debug {
         state.finish()
     }
 
+    /// The frames the (first) input must be evaluated at to compute
+    /// this node at 'frame'.
+    ///
+    /// Most nodes only need the inputs at the same frame, and return
+    /// None. If frames are returned, the input is evaluated once for
+    /// each frame (even if the only frame is 'frame'), and 'compute'
+    /// is given one input stream per frame. 'hash_value' is the hash
+    /// of this node at 'frame', so nodes can avoid evaluating the
+    /// input when their output is already in 'cache'.
+    fn input_frames(
+        &self,
+        _frame: FrameValue,
+        _hash_value: HashValue,
+        _attr_block: &Box<dyn AttrBlock>,
+        _cache: &Box<CacheImpl>,
+    ) -> Option<Vec<FrameValue>> {
+        None
+    }
+
+    /// The frames the (first) input is hashed at, to calculate the
+    /// hash of this node at 'frame'.
+    ///
+    /// These must be the frames 'input_frames' returns when the
+    /// output of this node is not cached, so the hash of the node
+    /// depends on the input at every frame that is computed.
+    fn hash_input_frames(
+        &self,
+        _frame: FrameValue,
+        _attr_block: &Box<dyn AttrBlock>,
+    ) -> Option<Vec<FrameValue>> {
+        None
+    }
+
+    /// The file this node will read at 'frame' (and the proxy scale
+    /// it is read at), if the file can be read ahead of time and
+    /// stored in the cache.
+    ///
+    /// The image stored in the cache must be the same as the image
+    /// 'compute' would store in the cache.
+    fn prefetch_path(
+        &self,
+        _frame: FrameValue,
+        _proxy_scale: ProxyScale,
+        _attr_block: &Box<dyn AttrBlock>,
+    ) -> Option<(PathBuf, ProxyScale)> {
+        None
+    }
+
     // TODO: Operations should have a method to initialise and check
     // the operation has all required data before doing the main
     // "compute" function.
@@ -83,15 +161,22 @@ pub trait Operation: std::fmt::Debug {
     // TOOD: Create a method that must be overridden by implementations.
     // that are responsible for returning the bounding box.
 
+    /// Compute the node's output stream.
+    ///
+    /// Any Warning or Error status returned should explain the
+    /// problem with a NodeErrorKind and message.
+    ///
+    /// 'proxy_scale' is the graph's proxy scale, see 'cache_hash'.
     fn compute(
         &mut self,
         frame: FrameValue,
+        proxy_scale: ProxyScale,
         node_type_id: u8,
         attr_block: &Box<dyn AttrBlock>,
         hash_value: HashValue,
         node_compute_mode: NodeComputeMode,
         inputs: &Vec<Rc<StreamDataImpl>>,
         output: &mut Rc<StreamDataImpl>,
-        cache: &mut Box<CacheImpl>,
-    ) -> NodeStatus;
+        cache: &Box<CacheImpl>,
+    ) -> NodeStatusInfo;
 }
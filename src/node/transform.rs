@@ -20,7 +20,6 @@
  */
 
 use log::debug;
-use std::collections::hash_map::DefaultHasher;
 use std::hash;
 use std::hash::Hash;
 use std::rc::Rc;
@@ -28,25 +27,30 @@ use std::rc::Rc;
 use crate::attrblock::AttrBlock;
 use crate::cache::CacheImpl;
 use crate::cxxbridge::ffi::AttrState;
-use crate::cxxbridge::ffi::NodeStatus;
+use crate::cxxbridge::ffi::NodeErrorKind;
 use crate::cxxbridge::ffi::NodeType;
+use crate::cxxbridge::ffi::ProxyScale;
 use crate::data::FrameValue;
 use crate::data::HashValue;
 use crate::data::Identifier;
 use crate::data::NodeComputeMode;
+use crate::data::OperationCacheType;
 use crate::deformer::transform::DeformerTransform;
 use crate::deformer::Deformer;
 use crate::hashutils::HashableF32;
+use crate::hashutils::StableHasher;
+use crate::node::status::NodeStatusInfo;
 use crate::node::traits::Operation;
 use crate::node::traits::Validate;
 use crate::node::NodeImpl;
+use crate::ops::imageproxy;
 use crate::stream::StreamDataImpl;
 
 pub fn new(id: Identifier) -> NodeImpl {
     NodeImpl {
         node_type: NodeType::Transform,
         id,
-        status: NodeStatus::Uninitialized,
+        status: NodeStatusInfo::uninitialized(),
         compute: Box::new(TransformOperation::new()),
         validate: Box::new(TransformValidate::new()),
         attr_block: Box::new(TransformAttrs::new()),
@@ -116,17 +120,22 @@ impl TransformAttrs {
 }
 
 impl Operation for TransformOperation {
+    fn cache_type(&self) -> OperationCacheType {
+        OperationCacheType::Trivial
+    }
+
     fn compute(
         &mut self,
         _frame: FrameValue,
+        _proxy_scale: ProxyScale,
         _node_type_id: u8,
         attr_block: &Box<dyn AttrBlock>,
         hash_value: HashValue,
         node_compute_mode: NodeComputeMode,
         inputs: &Vec<Rc<StreamDataImpl>>,
         output: &mut Rc<StreamDataImpl>,
-        _cache: &mut Box<CacheImpl>,
-    ) -> NodeStatus {
+        _cache: &Box<CacheImpl>,
+    ) -> NodeStatusInfo {
         debug!("TransformOperation.compute()");
         debug!(
             "TransformOperation NodeComputeMode={:#?}",
@@ -136,11 +145,14 @@ impl Operation for TransformOperation {
         // debug!("Inputs: {:?}", inputs);
         // debug!("Output: {:?}", output);
 
-        let mut status = NodeStatus::Valid;
+        let mut status = NodeStatusInfo::valid();
         let mut stream_data = match inputs.len() {
             0 => {
                 // No input given, return an empty default stream.
-                status = NodeStatus::Warning;
+                status = NodeStatusInfo::warning(
+                    NodeErrorKind::MissingInput,
+                    "no input connected".to_string(),
+                );
                 StreamDataImpl::new()
             }
             _ => {
@@ -154,8 +166,19 @@ impl Operation for TransformOperation {
             let mut deformer = DeformerTransform::default();
 
             deformer.set_attr_i32("invert", attr_block.get_attr_i32("invert"));
-            deformer.set_attr_f32("translate_x", attr_block.get_attr_f32("translate_x"));
-            deformer.set_attr_f32("translate_y", attr_block.get_attr_f32("translate_y"));
+            // Translate values are in pixels at full resolution, and
+            // are scaled to the (proxy) resolution of the input image.
+            let proxy_scale = stream_data.proxy_scale();
+            let translate_x = attr_block.get_attr_f32("translate_x");
+            let translate_y = attr_block.get_attr_f32("translate_y");
+            deformer.set_attr_f32(
+                "translate_x",
+                imageproxy::proxy_value(translate_x, proxy_scale),
+            );
+            deformer.set_attr_f32(
+                "translate_y",
+                imageproxy::proxy_value(translate_y, proxy_scale),
+            );
             deformer.set_attr_f32("rotate", attr_block.get_attr_f32("rotate"));
             deformer.set_attr_f32(
                 "rotate_center_x",
@@ -182,7 +205,7 @@ impl Operation for TransformOperation {
 }
 
 impl AttrBlock for TransformAttrs {
-    fn attr_hash(&self, _frame: FrameValue, state: &mut DefaultHasher) {
+    fn attr_hash(&self, _frame: FrameValue, state: &mut StableHasher) {
         self.hash(state)
     }
 
@@ -21,27 +21,33 @@
  */
 
 use log::debug;
-use std::collections::hash_map::DefaultHasher;
 use std::hash::Hash;
 use std::rc::Rc;
 use std::string::String;
+use std::sync::Arc;
 
 use crate::attrblock::AttrBlock;
 use crate::cache::CacheImpl;
 use crate::cache::CachedImage;
+use crate::colorspace::color_space_exists;
 use crate::cxxbridge::ffi::AttrState;
 use crate::cxxbridge::ffi::BBox2Di;
 use crate::cxxbridge::ffi::BakeOption;
 use crate::cxxbridge::ffi::DataType;
 use crate::cxxbridge::ffi::ImageShared;
 use crate::cxxbridge::ffi::ImageSpec;
-use crate::cxxbridge::ffi::NodeStatus;
+use crate::cxxbridge::ffi::NodeErrorKind;
 use crate::cxxbridge::ffi::NodeType;
+use crate::cxxbridge::ffi::ProxyScale;
+use crate::cxxbridge::ffi::ValidationIssue;
+use crate::cxxbridge::ffi::ValidationIssueKind;
 use crate::data::FrameValue;
 use crate::data::HashValue;
 use crate::data::Identifier;
 use crate::data::NodeComputeMode;
 use crate::data::COLOR_SPACE_NAME_LINEAR;
+use crate::hashutils::StableHasher;
+use crate::node::status::NodeStatusInfo;
 use crate::node::traits::Operation;
 use crate::node::traits::Validate;
 use crate::node::NodeImpl;
@@ -54,7 +60,7 @@ pub fn new(id: Identifier) -> NodeImpl {
     NodeImpl {
         node_type: NodeType::Viewer,
         id,
-        status: NodeStatus::Uninitialized,
+        status: NodeStatusInfo::uninitialized(),
         compute: Box::new(ViewerOperation::new()),
         validate: Box::new(ViewerValidate::new()),
         attr_block: Box::new(ViewerAttrs::new()),
@@ -99,7 +105,7 @@ fn do_viewer_bake(
     bake_pixel_data_type: DataType,
     crop_to_format: bool,
     to_color_space: &str,
-) -> (Rc<PixelBlock>, ImageSpec, BBox2Di, BBox2Di) {
+) -> (Arc<PixelBlock>, ImageSpec, BBox2Di, BBox2Di) {
     debug!("ViewerOperation::do_viewer_bake(bake_option={:#?}, bake_pixel_data_type={:#?}, to_color_space={:#?})", bake_option, bake_pixel_data_type, to_color_space);
 
     // Stream input data
@@ -146,9 +152,9 @@ fn do_viewer_bake(
         image_spec = img.spec;
     }
 
-    let pixel_block_rc = Rc::new(pixel_block);
+    let pixel_block_arc = Arc::new(pixel_block);
     (
-        pixel_block_rc.clone(),
+        pixel_block_arc.clone(),
         image_spec,
         data_window,
         display_window,
@@ -159,14 +165,15 @@ impl Operation for ViewerOperation {
     fn compute(
         &mut self,
         _frame: FrameValue,
+        _proxy_scale: ProxyScale,
         _node_type_id: u8,
         attr_block: &Box<dyn AttrBlock>,
         hash_value: HashValue,
         node_compute_mode: NodeComputeMode,
         inputs: &Vec<Rc<StreamDataImpl>>,
         output: &mut Rc<StreamDataImpl>,
-        cache: &mut Box<CacheImpl>,
-    ) -> NodeStatus {
+        cache: &Box<CacheImpl>,
+    ) -> NodeStatusInfo {
         debug!("ViewerOperation.compute()");
         debug!("ViewerOperation NodeComputeMode={:#?}", node_compute_mode);
         // debug!("AttrBlock: {:?}", attr_block);
@@ -175,12 +182,17 @@ impl Operation for ViewerOperation {
 
         let enable = attr_block.get_attr_i32("enable");
         if enable != 1 {
-            return NodeStatus::Valid;
+            return NodeStatusInfo::valid();
         }
 
+        let mut status = NodeStatusInfo::valid();
         let mut stream_data = match inputs.len() {
             0 => {
                 // No input given, return an empty default stream.
+                status = NodeStatusInfo::warning(
+                    NodeErrorKind::MissingInput,
+                    "no input connected".to_string(),
+                );
                 StreamDataImpl::new()
             }
             _ => {
@@ -224,7 +236,7 @@ impl Operation for ViewerOperation {
                         debug!("Cache Miss");
                         // Bake the image data down and add to
                         // the image cache.
-                        let (pixel_block_rc, image_spec, data_window, display_window) =
+                        let (pixel_block_arc, image_spec, data_window, display_window) =
                             do_viewer_bake(
                                 &mut stream_data,
                                 bake_option,
@@ -233,17 +245,22 @@ impl Operation for ViewerOperation {
                                 &bake_color_space,
                             );
                         let cached_img = CachedImage {
-                            pixel_block: pixel_block_rc.clone(),
+                            pixel_block: pixel_block_arc.clone(),
                             spec: image_spec.clone(),
                             data_window: data_window,
                             display_window: display_window,
+                            source_path: None,
+                            node_type: Some(NodeType::Viewer),
+                            compute_seconds: 0.0,
+                            cache_type: self.cache_type(),
+                            compressed: None,
                         };
                         cache.insert(hash_value, cached_img);
-                        (pixel_block_rc, image_spec, data_window, display_window)
+                        (pixel_block_arc, image_spec, data_window, display_window)
                     }
                 },
                 false => {
-                    let (pixel_block_rc, image_spec, data_window, display_window) = do_viewer_bake(
+                    let (pixel_block_arc, image_spec, data_window, display_window) = do_viewer_bake(
                         &mut stream_data,
                         bake_option,
                         bake_pixel_data_type,
@@ -251,7 +268,7 @@ impl Operation for ViewerOperation {
                         &bake_color_space,
                     );
 
-                    (pixel_block_rc, image_spec, data_window, display_window)
+                    (pixel_block_arc, image_spec, data_window, display_window)
                 }
             };
 
@@ -276,12 +293,12 @@ impl Operation for ViewerOperation {
         }
 
         *output = std::rc::Rc::new(stream_data);
-        NodeStatus::Valid
+        status
     }
 }
 
 impl AttrBlock for ViewerAttrs {
-    fn attr_hash(&self, _frame: FrameValue, state: &mut DefaultHasher) {
+    fn attr_hash(&self, _frame: FrameValue, state: &mut StableHasher) {
         self.enable.hash(state);
         if self.enable == 0 {
             return;
@@ -431,4 +448,51 @@ impl Validate for ViewerValidate {
         }
         node_compute_modes
     }
+
+    fn validate_attrs(
+        &self,
+        node_id: Identifier,
+        attr_block: &Box<dyn AttrBlock>,
+        _frames: &[FrameValue],
+        input_num_channels: &Vec<Option<i32>>,
+        issues: &mut Vec<ValidationIssue>,
+    ) -> Option<i32> {
+        let num_channels = input_num_channels.first().copied().flatten();
+        let enable = attr_block.get_attr_i32("enable") != 0;
+        if enable == false {
+            return num_channels;
+        }
+
+        let bake_option_num = attr_block.get_attr_i32("bake_option");
+        let bake_option = BakeOption::from(bake_option_num);
+        if bake_option == BakeOption::Uninitialized {
+            issues.push(ValidationIssue::invalid_enum_value(
+                node_id,
+                "bake_option",
+                bake_option_num,
+            ));
+        }
+        if bake_option == BakeOption::Nothing || bake_option == BakeOption::Uninitialized {
+            return num_channels;
+        }
+
+        let data_type_num = attr_block.get_attr_i32("bake_pixel_data_type");
+        if DataType::from(data_type_num) == DataType::Unknown {
+            issues.push(ValidationIssue::invalid_enum_value(
+                node_id,
+                "bake_pixel_data_type",
+                data_type_num,
+            ));
+        }
+
+        let bake_color_space = attr_block.get_attr_str("bake_color_space");
+        if color_space_exists(bake_color_space) == false {
+            issues.push(ValidationIssue::new(
+                node_id,
+                ValidationIssueKind::UnknownColorSpace,
+                format!("unknown color space: {:?}", bake_color_space),
+            ));
+        }
+        num_channels
+    }
 }
@@ -19,12 +19,12 @@
  *
  */
 
-use log::{debug, warn};
-use std::collections::hash_map::DefaultHasher;
+use log::debug;
 use std::hash;
 use std::hash::Hash;
 use std::rc::Rc;
 use std::string::String;
+use std::sync::Arc;
 
 use crate::attrblock::AttrBlock;
 use crate::cache::CacheImpl;
@@ -36,27 +36,36 @@ use crate::cxxbridge::ffi::ExrCompression;
 use crate::cxxbridge::ffi::ImageCompression;
 use crate::cxxbridge::ffi::ImageShared;
 use crate::cxxbridge::ffi::JpegChromaSubSampling;
-use crate::cxxbridge::ffi::NodeStatus;
+use crate::cxxbridge::ffi::NodeErrorKind;
 use crate::cxxbridge::ffi::NodeType;
+use crate::cxxbridge::ffi::ProxyScale;
+use crate::cxxbridge::ffi::ValidationIssue;
 use crate::data::FrameValue;
 use crate::data::HashValue;
 use crate::data::Identifier;
 use crate::data::NodeComputeMode;
+use crate::data::OperationCacheType;
 use crate::data::COLOR_SPACE_NAME_LINEAR;
 use crate::data::COLOR_SPACE_NAME_SRGB;
+use crate::deformutils;
+use crate::hashutils::StableHasher;
 use crate::imageio;
+use crate::imagelayer;
+use crate::imagelayer::ImageLayer;
+use crate::node::status::NodeStatusInfo;
 use crate::node::traits::Operation;
 use crate::node::traits::Validate;
 use crate::node::NodeImpl;
 use crate::ops;
 use crate::pathutils;
+use crate::pixelblock::pixelblock::PixelBlock;
 use crate::stream::StreamDataImpl;
 
 pub fn new(id: Identifier) -> NodeImpl {
     NodeImpl {
         node_type: NodeType::WriteImage,
         id,
-        status: NodeStatus::Uninitialized,
+        status: NodeStatusInfo::uninitialized(),
         compute: Box::new(WriteImageOperation::new()),
         validate: Box::new(WriteImageValidate::new()),
         attr_block: Box::new(WriteImageAttrs::new()),
@@ -126,6 +135,36 @@ impl WriteImageAttrs {
     }
 }
 
+/// Apply the deformers of 'stream_data' (such as Transform nodes) to
+/// the layers, the same as the image pixels are deformed when baked.
+fn deform_layers(stream_data: &StreamDataImpl) -> Vec<ImageLayer> {
+    let deformers = stream_data.deformers();
+    let display_window = stream_data.display_window();
+    stream_data
+        .layers()
+        .iter()
+        .map(|layer| {
+            let mut src_pixel_block = (**layer.pixel_block()).clone();
+            src_pixel_block.convert_into_data_type(DataType::Float32);
+            let mut pixel_block = PixelBlock::empty(DataType::Float32);
+            let mut data_window = layer.data_window();
+            deformutils::apply_deformers_to_pixels(
+                deformers,
+                display_window,
+                &src_pixel_block,
+                layer.data_window(),
+                &mut pixel_block,
+                &mut data_window,
+            );
+            ImageLayer::new(
+                layer.name().to_string(),
+                layer.channel_names().to_vec(),
+                data_window,
+                Arc::new(pixel_block),
+            )
+        })
+        .collect()
+}
 fn do_image_process(
     input: &Rc<StreamDataImpl>,
     file_path: &str,
@@ -166,6 +205,10 @@ fn do_image_process(
         COLOR_SPACE_NAME_LINEAR.to_string()
     };
 
+    // The deformers are removed from the stream when the image is
+    // baked.
+    let layers = deform_layers(copy);
+
     let bake_option = BakeOption::All;
     ops::bake::do_process(
         bake_option,
@@ -179,6 +222,14 @@ fn do_image_process(
         out_pixel_data_type,
     );
 
+    // Only EXR images can store the layers, as extra channels after
+    // the RGBA channels.
+    let is_exr = path_expanded.to_lowercase().ends_with(".exr");
+    let (pixel_block, channel_names) = match is_exr && !layers.is_empty() {
+        true => imagelayer::interleave_layers(&pixel_block, data_window, &layers),
+        false => (pixel_block, Vec::new()),
+    };
+
     // Write pixels
     let pixel_block_box = Box::new(pixel_block);
     let image = ImageShared {
@@ -204,22 +255,34 @@ fn do_image_process(
         _ => panic!("Invalid crop_on_write value: {:?}", crop_on_write),
     };
 
-    let ok = imageio::write_image(&image, &path_expanded, num_threads, do_crop, compress);
+    let ok = imageio::write_image_channels(
+        &image,
+        &channel_names,
+        &path_expanded,
+        num_threads,
+        do_crop,
+        compress,
+    );
     ok
 }
 
 impl Operation for WriteImageOperation {
+    fn cache_type(&self) -> OperationCacheType {
+        OperationCacheType::BoundByIO
+    }
+
     fn compute(
         &mut self,
         frame: FrameValue,
+        _proxy_scale: ProxyScale,
         _node_type_id: u8,
         attr_block: &Box<dyn AttrBlock>,
         _hash_value: HashValue,
         node_compute_mode: NodeComputeMode,
         inputs: &Vec<Rc<StreamDataImpl>>,
         output: &mut Rc<StreamDataImpl>,
-        _cache: &mut Box<CacheImpl>,
-    ) -> NodeStatus {
+        _cache: &Box<CacheImpl>,
+    ) -> NodeStatusInfo {
         debug!("WriteImageOperation.compute()");
         debug!(
             "WriteImageOperation NodeComputeMode={:#?}",
@@ -233,7 +296,7 @@ impl Operation for WriteImageOperation {
         if enable == false {
             let stream_data = StreamDataImpl::new();
             *output = std::rc::Rc::new(stream_data);
-            return NodeStatus::Valid;
+            return NodeStatusInfo::valid();
         }
 
         match inputs.len() {
@@ -241,13 +304,16 @@ impl Operation for WriteImageOperation {
                 // No input given, return an empty default stream.
                 let stream_data = StreamDataImpl::new();
                 *output = std::rc::Rc::new(stream_data);
-                NodeStatus::Warning
+                NodeStatusInfo::warning(
+                    NodeErrorKind::MissingInput,
+                    "no input connected".to_string(),
+                )
             }
             _ => {
                 let execute = attr_block.get_attr_i32("execute") != 0;
                 if execute == false {
                     *output = inputs[0].clone();
-                    NodeStatus::Valid
+                    NodeStatusInfo::valid()
                 } else {
                     let input = &inputs[0].clone();
                     let file_path = attr_block.get_attr_str("file_path");
@@ -281,13 +347,19 @@ impl Operation for WriteImageOperation {
                         crop_on_write,
                         compress,
                     );
-                    if ok == false {
-                        warn!("Failed to write image: status={}", ok);
-                    }
                     debug!("Success: {}", ok);
 
                     *output = inputs[0].clone();
-                    NodeStatus::Valid
+                    if ok == false {
+                        let frame_num = frame.round().trunc() as i32;
+                        let path_expanded =
+                            pathutils::expand_string(file_path.to_string(), frame_num);
+                        return NodeStatusInfo::warning(
+                            NodeErrorKind::FileWriteFailure,
+                            format!("failed to write image: {}", path_expanded),
+                        );
+                    }
+                    NodeStatusInfo::valid()
                 }
             }
         }
@@ -295,7 +367,7 @@ impl Operation for WriteImageOperation {
 }
 
 impl AttrBlock for WriteImageAttrs {
-    fn attr_hash(&self, frame: FrameValue, state: &mut DefaultHasher) {
+    fn attr_hash(&self, frame: FrameValue, state: &mut StableHasher) {
         self.enable.hash(state);
         if self.enable == 1 {
             let frame_num = frame.round().trunc() as i32;
@@ -407,4 +479,41 @@ impl Validate for WriteImageValidate {
         }
         node_compute_modes
     }
+
+    fn validate_attrs(
+        &self,
+        node_id: Identifier,
+        attr_block: &Box<dyn AttrBlock>,
+        _frames: &[FrameValue],
+        input_num_channels: &Vec<Option<i32>>,
+        issues: &mut Vec<ValidationIssue>,
+    ) -> Option<i32> {
+        let crop_on_write = attr_block.get_attr_i32("crop_on_write");
+        if CropOnWrite::from(crop_on_write) == CropOnWrite::Uninitialized {
+            issues.push(ValidationIssue::invalid_enum_value(
+                node_id,
+                "crop_on_write",
+                crop_on_write,
+            ));
+        }
+
+        let exr_compression = attr_block.get_attr_i32("exr_compression");
+        if ExrCompression::from(exr_compression) == ExrCompression::Uninitialized {
+            issues.push(ValidationIssue::invalid_enum_value(
+                node_id,
+                "exr_compression",
+                exr_compression,
+            ));
+        }
+
+        let jpeg_subsampling = attr_block.get_attr_i32("jpeg_subsampling");
+        if JpegChromaSubSampling::from(jpeg_subsampling) == JpegChromaSubSampling::Uninitialized {
+            issues.push(ValidationIssue::invalid_enum_value(
+                node_id,
+                "jpeg_subsampling",
+                jpeg_subsampling,
+            ));
+        }
+        input_num_channels.first().copied().flatten()
+    }
 }
@@ -19,9 +19,14 @@
  *
  */
 
+// STL
+#include <string>
+#include <vector>
+
 #include <opencompgraph/internal/oiio_utils.h>
 #include <OpenImageIO/typedesc.h>
 #include <OpenImageIO/imageio.h>
+#include <OpenImageIO/strutil.h>
 
 namespace open_comp_graph {
 namespace internal {
@@ -147,5 +152,133 @@ bool oiio_allocate_image(
     return true;
 }
 
+// Attributes that are not copied into the image metadata.
+static const char *skipped_metadata_names[] = {
+    // Stored in the ImageSpec.
+    "PixelAspectRatio",
+    "Orientation",
+    // Describes how the file was encoded.
+    "compression",
+    "Compression",
+    "planarconfig",
+    "openexr:chunkCount",
+    "openexr:dwaCompressionLevel",
+    "openexr:lineOrder",
+    "png:compressionLevel",
+    "jpeg:subsampling",
+    "tiff:Compression",
+    "tiff:PhotometricInterpretation",
+    "tiff:RowsPerStrip",
+};
+
+static bool skip_metadata_name(const std::string &name) {
+    // "oiio:" attributes are hints for OpenImageIO, such as
+    // "oiio:ColorSpace" and "oiio:BitsPerSample".
+    if (OIIO::Strutil::starts_with(name, "oiio:")) {
+        return true;
+    }
+    for (auto skipped_name : skipped_metadata_names) {
+        if (name == skipped_name) {
+            return true;
+        }
+    }
+    return false;
+}
+
+void oiio_read_metadata(
+        const OIIO::ImageSpec &spec,
+        rust::Vec<ImageMetadata> &metadata) {
+    for (auto &param : spec.extra_attribs) {
+        auto name = param.name().string();
+        if (skip_metadata_name(name)) {
+            continue;
+        }
+
+        auto type = param.type();
+        auto num_values = static_cast<int>(param.nvalues() * type.basevalues());
+        ImageMetadata item;
+        item.name = rust::String(name);
+        switch (type.basetype) {
+            case OIIO::TypeDesc::UINT8:
+            case OIIO::TypeDesc::INT8:
+            case OIIO::TypeDesc::UINT16:
+            case OIIO::TypeDesc::INT16:
+            case OIIO::TypeDesc::UINT32:
+            case OIIO::TypeDesc::INT32:
+            case OIIO::TypeDesc::UINT64:
+            case OIIO::TypeDesc::INT64:
+                item.data_type = ImageMetadataType::kInt;
+                for (auto i = 0; i < num_values; i++) {
+                    item.int_values.push_back(param.get_int_indexed(i));
+                }
+                break;
+            case OIIO::TypeDesc::HALF:
+            case OIIO::TypeDesc::FLOAT:
+            case OIIO::TypeDesc::DOUBLE:
+                item.data_type = ImageMetadataType::kFloat;
+                for (auto i = 0; i < num_values; i++) {
+                    item.float_values.push_back(param.get_float_indexed(i));
+                }
+                break;
+            case OIIO::TypeDesc::STRING:
+                item.data_type = ImageMetadataType::kString;
+                item.string_value = rust::String(param.get_ustring().string());
+                break;
+            default:
+                // Pointers and unknown types cannot be copied.
+                continue;
+        }
+        metadata.push_back(item);
+    }
+}
+
+void oiio_write_metadata(
+        const rust::Vec<ImageMetadata> &metadata,
+        OIIO::ImageSpec &spec) {
+    for (auto &item : metadata) {
+        auto name = std::string(item.name);
+        if (name.empty() || skip_metadata_name(name)) {
+            continue;
+        }
+
+        if (item.data_type == ImageMetadataType::kInt) {
+            auto num_values = static_cast<int>(item.int_values.size());
+            if (num_values == 0) {
+                continue;
+            }
+            // Time codes and key codes have special types, so the
+            // file formats can store them correctly.
+            if (OIIO::Strutil::ends_with(name, "TimeCode") && num_values == 2) {
+                unsigned int values[2] = {
+                    static_cast<unsigned int>(item.int_values[0]),
+                    static_cast<unsigned int>(item.int_values[1])};
+                spec.attribute(name, OIIO::TypeTimeCode, values);
+            } else if (OIIO::Strutil::ends_with(name, "KeyCode") && num_values == 7) {
+                spec.attribute(name, OIIO::TypeKeyCode, item.int_values.data());
+            } else if (num_values == 1) {
+                spec.attribute(name, item.int_values[0]);
+            } else {
+                auto type = OIIO::TypeDesc(OIIO::TypeDesc::INT, num_values);
+                spec.attribute(name, type, item.int_values.data());
+            }
+        } else if (item.data_type == ImageMetadataType::kFloat) {
+            auto num_values = static_cast<int>(item.float_values.size());
+            if (num_values == 0) {
+                continue;
+            }
+            if (num_values == 16) {
+                spec.attribute(name, OIIO::TypeMatrix44, item.float_values.data());
+            } else if (num_values == 1) {
+                spec.attribute(name, item.float_values[0]);
+            } else {
+                auto type = OIIO::TypeDesc(OIIO::TypeDesc::FLOAT, num_values);
+                spec.attribute(name, type, item.float_values.data());
+            }
+        } else if (item.data_type == ImageMetadataType::kString) {
+            spec.attribute(name, std::string(item.string_value));
+        }
+    }
+}
+
 } // namespace internal
 } // namespace open_comp_graph
@@ -21,8 +21,11 @@
 
 pub mod bake;
 pub mod colorgrade;
+pub mod imageblend;
 pub mod imagecrop;
 pub mod imagemerge;
+pub mod imageorient;
+pub mod imageproxy;
 pub mod imageresample;
 pub mod pixelremap;
 pub mod xformcolor;
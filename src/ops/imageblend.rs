@@ -0,0 +1,120 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+use log::debug;
+use std::time::Instant;
+
+use crate::cxxbridge::ffi::BBox2Di;
+use crate::cxxbridge::ffi::BlockSize;
+use crate::cxxbridge::ffi::DataType;
+use crate::cxxbridge::ffi::ImageShared;
+
+/// Add the pixels of 'image' (multiplied by 'weight') into the
+/// 'out_pixels' buffer, covering 'out_data_window'. Pixels outside
+/// the image's data window are black. An image without an alpha
+/// channel is treated as opaque.
+fn accumulate_pixels(
+    image: &ImageShared,
+    weight: f32,
+    out_data_window: BBox2Di,
+    out_num_channels: i32,
+    out_pixels: &mut [f32],
+) {
+    let pixels = image.pixel_block.as_slice_f32();
+    let num_channels = image.pixel_block.num_channels();
+    let common_channels = std::cmp::min(num_channels, out_num_channels) as usize;
+    let add_alpha = num_channels < 4 && out_num_channels >= 4;
+    let out_stride = out_num_channels as usize;
+
+    let mut out_index = 0;
+    for row in out_data_window.min_y..out_data_window.max_y {
+        for col in out_data_window.min_x..out_data_window.max_x {
+            let image_x = col - image.data_window.min_x;
+            let image_y = row - image.data_window.min_y;
+            let index = image.pixel_block.get_pixel_index(image_x, image_y);
+            if index >= 0 {
+                let i = index as usize;
+                for c in 0..common_channels {
+                    out_pixels[out_index + c] += pixels[i + c] * weight;
+                }
+                if add_alpha {
+                    out_pixels[out_index + 3] += weight;
+                }
+            }
+            out_index += out_stride;
+        }
+    }
+}
+
+/// Average many images together, with equal weighting.
+///
+/// The output data window covers all the input data windows, and the
+/// display window and image spec are taken from the first image.
+///
+/// Assumes:
+///
+///  - Images are already f32 pixel data types.
+pub fn blend_average(images: &[ImageShared], image_out: &mut ImageShared) -> bool {
+    let start = Instant::now();
+    debug!("blend_average: {} images", images.len());
+    let first_image = match images.first() {
+        Some(value) => value,
+        None => return false,
+    };
+    for image in images {
+        assert!(image.pixel_block.data_type() == DataType::Float32);
+    }
+
+    // Compute the output image dimensions and allocate needed memory.
+    let mut out_data_window = first_image.data_window;
+    let mut out_num_channels = first_image.pixel_block.num_channels();
+    for image in images.iter().skip(1) {
+        out_data_window = BBox2Di::combine(out_data_window, image.data_window);
+        out_num_channels = std::cmp::max(out_num_channels, image.pixel_block.num_channels());
+    }
+    let blocksize = BlockSize::new(
+        out_data_window.width(),
+        out_data_window.height(),
+        out_num_channels,
+    );
+    image_out
+        .pixel_block
+        .data_resize(blocksize, DataType::Float32);
+    image_out.data_window = out_data_window;
+    image_out.display_window = first_image.display_window;
+    image_out.spec = first_image.spec.clone();
+
+    debug!("Image OUT: Data Window {:?}", image_out.data_window);
+    debug!("Image OUT: Display Window {:?}", image_out.display_window);
+
+    let weight = 1.0 / (images.len() as f32);
+    let out_pixels = image_out.pixel_block.as_mut_slice_f32();
+    for value in out_pixels.iter_mut() {
+        *value = 0.0;
+    }
+    for image in images {
+        accumulate_pixels(image, weight, out_data_window, out_num_channels, out_pixels);
+    }
+
+    let duration = start.elapsed();
+    debug!("Total time: {:?}", duration);
+    true
+}
@@ -0,0 +1,316 @@
+/*
+ * Copyright (C) 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+// Flip, rotate or transpose an image, so the pixels have the Normal
+// orientation.
+//
+// The ImageOrientation describes how the pixels are stored in the
+// file, the same as the EXIF "Orientation" tag. For example an image
+// with the "Rotated90Clockwise" orientation is stored with the first
+// row on the right of the image, and is rotated 90 degrees
+// clockwise to become Normal.
+
+use log::debug;
+use std::time::Instant;
+
+use crate::cxxbridge::ffi::BBox2Di;
+use crate::cxxbridge::ffi::BlockSize;
+use crate::cxxbridge::ffi::ImageOrientation;
+use crate::cxxbridge::ffi::ImageShared;
+use crate::cxxbridge::ffi::ImageSpec;
+use crate::pixelblock::dataslice::DataSliceMut;
+use crate::pixelblock::pixelblock::PixelBlock;
+
+/// Does the orientation swap the width and height of the image?
+pub fn swaps_axes(orientation: ImageOrientation) -> bool {
+    matches!(
+        orientation,
+        ImageOrientation::Transposed
+            | ImageOrientation::Rotated90Clockwise
+            | ImageOrientation::Transverse
+            | ImageOrientation::Rotated90CounterClockwise
+    )
+}
+
+/// Does the orientation mirror the x and/or y axis (after any
+/// swap of the axes)?
+fn mirror_axes(orientation: ImageOrientation) -> (bool, bool) {
+    match orientation {
+        ImageOrientation::FlippedHorizontally => (true, false),
+        ImageOrientation::Rotated180 => (true, true),
+        ImageOrientation::FlippedVertically => (false, true),
+        ImageOrientation::Rotated90Clockwise => (true, false),
+        ImageOrientation::Transverse => (true, true),
+        ImageOrientation::Rotated90CounterClockwise => (false, true),
+        _ => (false, false),
+    }
+}
+
+/// The display window of an image after it is made Normal.
+pub fn orient_display_window(display_window: BBox2Di, orientation: ImageOrientation) -> BBox2Di {
+    match swaps_axes(orientation) {
+        true => BBox2Di::new(
+            display_window.min_y,
+            display_window.min_x,
+            display_window.max_y,
+            display_window.max_x,
+        ),
+        false => display_window,
+    }
+}
+
+/// The data window of an image after it is made Normal.
+///
+/// The data window is mirrored inside the (source) display window,
+/// so the data window stays in the same place relative to the image.
+pub fn orient_data_window(
+    data_window: BBox2Di,
+    display_window: BBox2Di,
+    orientation: ImageOrientation,
+) -> BBox2Di {
+    let new_display_window = orient_display_window(display_window, orientation);
+    let mut window = orient_display_window(data_window, orientation);
+    let (mirror_x, mirror_y) = mirror_axes(orientation);
+    if mirror_x {
+        let sum = new_display_window.min_x + new_display_window.max_x;
+        window = BBox2Di::new(
+            sum - window.max_x,
+            window.min_y,
+            sum - window.min_x,
+            window.max_y,
+        );
+    }
+    if mirror_y {
+        let sum = new_display_window.min_y + new_display_window.max_y;
+        window = BBox2Di::new(
+            window.min_x,
+            sum - window.max_y,
+            window.max_x,
+            sum - window.min_y,
+        );
+    }
+    window
+}
+
+/// Copy each pixel of 'src' into the Normal position in 'dst'.
+fn orient_pixels<T: Copy>(
+    src: &[T],
+    dst: &mut [T],
+    width: usize,
+    height: usize,
+    num_channels: usize,
+    orientation: ImageOrientation,
+) {
+    let swap = swaps_axes(orientation);
+    let (mirror_x, mirror_y) = mirror_axes(orientation);
+    let (dst_width, dst_height) = match swap {
+        true => (height, width),
+        false => (width, height),
+    };
+    for dst_y in 0..dst_height {
+        let y = match mirror_y {
+            true => dst_height - 1 - dst_y,
+            false => dst_y,
+        };
+        for dst_x in 0..dst_width {
+            let x = match mirror_x {
+                true => dst_width - 1 - dst_x,
+                false => dst_x,
+            };
+            let (src_x, src_y) = match swap {
+                true => (y, x),
+                false => (x, y),
+            };
+            let src_index = ((src_y * width) + src_x) * num_channels;
+            let dst_index = ((dst_y * dst_width) + dst_x) * num_channels;
+            dst[dst_index..dst_index + num_channels]
+                .copy_from_slice(&src[src_index..src_index + num_channels]);
+        }
+    }
+}
+
+/// Create a new pixel block with the pixels of 'pixel_block' in the
+/// Normal orientation.
+pub fn orient_pixel_block(pixel_block: &PixelBlock, orientation: ImageOrientation) -> PixelBlock {
+    let width = pixel_block.width();
+    let height = pixel_block.height();
+    let num_channels = pixel_block.num_channels();
+    let blocksize = match swaps_axes(orientation) {
+        true => BlockSize::new(height, width, num_channels),
+        false => BlockSize::new(width, height, num_channels),
+    };
+    let mut new_pixel_block = PixelBlock::new(blocksize, pixel_block.data_type());
+
+    let width = width as usize;
+    let height = height as usize;
+    let num_channels = num_channels as usize;
+    match new_pixel_block.as_mut_slice() {
+        DataSliceMut::Float32(dst) => orient_pixels(
+            pixel_block.as_slice_f32(),
+            dst,
+            width,
+            height,
+            num_channels,
+            orientation,
+        ),
+        DataSliceMut::Half16(dst) => orient_pixels(
+            pixel_block.as_slice_f16(),
+            dst,
+            width,
+            height,
+            num_channels,
+            orientation,
+        ),
+        DataSliceMut::UInt16(dst) => orient_pixels(
+            pixel_block.as_slice_u16(),
+            dst,
+            width,
+            height,
+            num_channels,
+            orientation,
+        ),
+        DataSliceMut::UInt8(dst) => orient_pixels(
+            pixel_block.as_slice_u8(),
+            dst,
+            width,
+            height,
+            num_channels,
+            orientation,
+        ),
+    }
+    new_pixel_block
+}
+
+/// Reset the orientation of 'image_spec' to Normal, after the pixels
+/// have been oriented.
+pub fn orient_image_spec(image_spec: &mut ImageSpec) {
+    if swaps_axes(image_spec.orientation()) {
+        // The pixel width becomes the pixel height.
+        let pixel_aspect = image_spec.pixel_aspect();
+        if pixel_aspect > 0.0 {
+            image_spec.set_pixel_aspect(1.0 / pixel_aspect);
+        }
+    }
+    image_spec.set_orientation(ImageOrientation::Normal);
+}
+
+/// Change the pixels and windows of 'image' to the Normal
+/// orientation, and reset the image spec orientation.
+///
+/// Returns false if the image spec has an invalid orientation.
+pub fn orient_image_in_place(image: &mut ImageShared) -> bool {
+    let start = Instant::now();
+    let orientation = image.spec.orientation();
+    debug!("Orientation: {:?}", orientation);
+    if orientation == ImageOrientation::Uninitialized {
+        return false;
+    }
+    if orientation == ImageOrientation::Normal {
+        return true;
+    }
+
+    let pixel_block = orient_pixel_block(&image.pixel_block, orientation);
+    *image.pixel_block = pixel_block;
+    image.data_window = orient_data_window(image.data_window, image.display_window, orientation);
+    image.display_window = orient_display_window(image.display_window, orientation);
+    orient_image_spec(&mut image.spec);
+
+    let duration = start.elapsed();
+    debug!("Orient time: {:?}", duration);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pixelblock::datablock::DataBlock;
+
+    /// A 3x2 image, with one channel holding the pixel index.
+    ///
+    ///   0 1 2
+    ///   3 4 5
+    fn test_image(orientation: ImageOrientation) -> ImageShared {
+        let values: Vec<f32> = (0..6).map(|v| v as f32).collect();
+        let pixel_block =
+            PixelBlock::from_datablock(BlockSize::new(3, 2, 1), DataBlock::from_slice_f32(&values));
+        let mut spec = ImageSpec::new();
+        spec.set_orientation(orientation);
+        spec.set_pixel_aspect(2.0);
+        ImageShared {
+            pixel_block: Box::new(pixel_block),
+            display_window: BBox2Di::new(0, 0, 10, 20),
+            data_window: BBox2Di::new(1, 2, 4, 4),
+            spec,
+        }
+    }
+
+    #[test]
+    fn test_orient_pixels() {
+        let expected: [(ImageOrientation, [f32; 6]); 8] = [
+            (ImageOrientation::Normal, [0., 1., 2., 3., 4., 5.]),
+            (
+                ImageOrientation::FlippedHorizontally,
+                [2., 1., 0., 5., 4., 3.],
+            ),
+            (ImageOrientation::Rotated180, [5., 4., 3., 2., 1., 0.]),
+            (
+                ImageOrientation::FlippedVertically,
+                [3., 4., 5., 0., 1., 2.],
+            ),
+            (ImageOrientation::Transposed, [0., 3., 1., 4., 2., 5.]),
+            (
+                ImageOrientation::Rotated90Clockwise,
+                [3., 0., 4., 1., 5., 2.],
+            ),
+            (ImageOrientation::Transverse, [5., 2., 4., 1., 3., 0.]),
+            (
+                ImageOrientation::Rotated90CounterClockwise,
+                [2., 5., 1., 4., 0., 3.],
+            ),
+        ];
+        for (orientation, pixels) in expected.iter() {
+            let mut image = test_image(*orientation);
+            assert!(orient_image_in_place(&mut image));
+            assert_eq!(image.pixel_block.as_slice_f32(), &pixels[..]);
+            assert_eq!(image.spec.orientation(), ImageOrientation::Normal);
+            assert_eq!(image.data_window.width(), image.pixel_block.width());
+            assert_eq!(image.data_window.height(), image.pixel_block.height());
+        }
+    }
+
+    #[test]
+    fn test_orient_windows() {
+        let mut image = test_image(ImageOrientation::Rotated90Clockwise);
+        assert!(orient_image_in_place(&mut image));
+        assert_eq!(image.display_window, BBox2Di::new(0, 0, 20, 10));
+        assert_eq!(image.data_window, BBox2Di::new(16, 1, 18, 4));
+        assert_eq!(image.spec.pixel_aspect(), 0.5);
+
+        let mut image = test_image(ImageOrientation::Rotated180);
+        assert!(orient_image_in_place(&mut image));
+        assert_eq!(image.display_window, BBox2Di::new(0, 0, 10, 20));
+        assert_eq!(image.data_window, BBox2Di::new(6, 16, 9, 18));
+        assert_eq!(image.spec.pixel_aspect(), 2.0);
+
+        let mut image = test_image(ImageOrientation::Uninitialized);
+        assert!(!orient_image_in_place(&mut image));
+    }
+}
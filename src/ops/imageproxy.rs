@@ -0,0 +1,259 @@
+/*
+ * Copyright (C) 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+// Reduce the resolution of an image for proxy (fast review) mode.
+//
+// The pixels are averaged in square blocks of 'factor' x 'factor'
+// pixels. The blocks are aligned to the origin (0, 0), not to the
+// data window, so images with different data windows stay aligned
+// to each other after reducing the resolution.
+
+use log::debug;
+use std::time::Instant;
+
+use crate::cxxbridge::ffi::BBox2Di;
+use crate::cxxbridge::ffi::BlockSize;
+use crate::cxxbridge::ffi::DataType;
+use crate::cxxbridge::ffi::ImageShared;
+use crate::cxxbridge::ffi::ProxyScale;
+use crate::pixelblock::pixelblock::PixelBlock;
+
+/// The number of full resolution pixels for each proxy pixel (along
+/// one axis).
+pub fn proxy_factor(proxy_scale: ProxyScale) -> i32 {
+    match proxy_scale {
+        ProxyScale::Half => 2,
+        ProxyScale::Quarter => 4,
+        ProxyScale::Eighth => 8,
+        _ => 1,
+    }
+}
+
+/// The lowest resolution of the two proxy scales.
+pub fn combine_proxy_scale(a: ProxyScale, b: ProxyScale) -> ProxyScale {
+    match proxy_factor(a) >= proxy_factor(b) {
+        true => a,
+        false => b,
+    }
+}
+
+/// Convert a full resolution pixel-space value to the proxy scale.
+pub fn proxy_value(value: f32, proxy_scale: ProxyScale) -> f32 {
+    value / (proxy_factor(proxy_scale) as f32)
+}
+
+/// Convert a full resolution window to the proxy scale.
+///
+/// The window is expanded to cover every proxy pixel that is
+/// (partly) inside the full resolution window.
+pub fn proxy_window(window: BBox2Di, proxy_scale: ProxyScale) -> BBox2Di {
+    let factor = proxy_factor(proxy_scale);
+    let floor = |value: i32| value.div_euclid(factor);
+    let ceil = |value: i32| -((-value).div_euclid(factor));
+    BBox2Di::new(
+        floor(window.min_x),
+        floor(window.min_y),
+        ceil(window.max_x),
+        ceil(window.max_y),
+    )
+}
+
+/// Average the pixels of a full resolution image, covering
+/// 'data_window', into the pixels of 'dst' covering
+/// 'dst_data_window' (the proxy window of 'data_window').
+fn downsample_pixels(
+    src: &[f32],
+    dst: &mut [f32],
+    data_window: BBox2Di,
+    dst_data_window: BBox2Di,
+    num_channels: usize,
+    factor: i32,
+) {
+    let width = data_window.width();
+    let height = data_window.height();
+    let dst_width = dst_data_window.width();
+    let dst_height = dst_data_window.height();
+    let mut sum = vec![0.0_f32; num_channels];
+    for dst_y in 0..dst_height {
+        // The rows of the source covered by this proxy pixel,
+        // relative to the data window.
+        let start_y = ((dst_data_window.min_y + dst_y) * factor - data_window.min_y).max(0);
+        let end_y = ((dst_data_window.min_y + dst_y + 1) * factor - data_window.min_y).min(height);
+        for dst_x in 0..dst_width {
+            let start_x = ((dst_data_window.min_x + dst_x) * factor - data_window.min_x).max(0);
+            let end_x =
+                ((dst_data_window.min_x + dst_x + 1) * factor - data_window.min_x).min(width);
+
+            sum.iter_mut().for_each(|v| *v = 0.0);
+            let mut count = 0;
+            for y in start_y..end_y {
+                for x in start_x..end_x {
+                    let src_index = (((y * width) + x) as usize) * num_channels;
+                    for (c, value) in sum.iter_mut().enumerate() {
+                        *value += src[src_index + c];
+                    }
+                    count += 1;
+                }
+            }
+
+            let dst_index = (((dst_y * dst_width) + dst_x) as usize) * num_channels;
+            if count > 0 {
+                let weight = 1.0 / (count as f32);
+                for (c, value) in sum.iter().enumerate() {
+                    dst[dst_index + c] = value * weight;
+                }
+            }
+        }
+    }
+}
+
+/// Create a new pixel block with the pixels of 'pixel_block'
+/// (covering 'data_window') at the proxy scale.
+///
+/// The new pixel block covers the proxy window of 'data_window', and
+/// has the same data type as 'pixel_block'.
+pub fn proxy_pixel_block(
+    pixel_block: &PixelBlock,
+    data_window: BBox2Di,
+    proxy_scale: ProxyScale,
+) -> PixelBlock {
+    let data_type = pixel_block.data_type();
+    let num_channels = pixel_block.num_channels();
+    let dst_data_window = proxy_window(data_window, proxy_scale);
+    let blocksize = BlockSize::new(
+        dst_data_window.width(),
+        dst_data_window.height(),
+        num_channels,
+    );
+    let mut new_pixel_block = PixelBlock::new(blocksize, DataType::Float32);
+
+    // Average the pixels in floating-point, to avoid overflowing
+    // the integer data types.
+    let mut src_pixel_block = pixel_block.clone();
+    src_pixel_block.convert_into_data_type(DataType::Float32);
+    downsample_pixels(
+        src_pixel_block.as_slice_f32(),
+        new_pixel_block.as_mut_slice_f32(),
+        data_window,
+        dst_data_window,
+        num_channels as usize,
+        proxy_factor(proxy_scale),
+    );
+    new_pixel_block.convert_into_data_type(data_type);
+    new_pixel_block
+}
+
+/// Reduce the pixels and windows of 'image' to the proxy scale.
+///
+/// Returns false if the proxy scale is invalid, otherwise true.
+pub fn proxy_image_in_place(image: &mut ImageShared, proxy_scale: ProxyScale) -> bool {
+    debug!("Proxy Scale: {:?}", proxy_scale);
+    if proxy_scale == ProxyScale::Uninitialized {
+        return false;
+    }
+    if proxy_scale == ProxyScale::Full {
+        return true;
+    }
+    let start = Instant::now();
+
+    // The pixel block must match the data window.
+    if (image.pixel_block.width() != image.data_window.width())
+        || (image.pixel_block.height() != image.data_window.height())
+    {
+        return false;
+    }
+
+    *image.pixel_block = proxy_pixel_block(&image.pixel_block, image.data_window, proxy_scale);
+    image.data_window = proxy_window(image.data_window, proxy_scale);
+    image.display_window = proxy_window(image.display_window, proxy_scale);
+
+    let duration = start.elapsed();
+    debug!("Proxy time: {:?}", duration);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cxxbridge::ffi::ImageSpec;
+    use crate::pixelblock::datablock::DataBlock;
+
+    /// A 4x2 image, with one channel holding the pixel index.
+    ///
+    ///   0 1 2 3
+    ///   4 5 6 7
+    fn test_image(data_window: BBox2Di) -> ImageShared {
+        let values: Vec<f32> = (0..8).map(|v| v as f32).collect();
+        let pixel_block =
+            PixelBlock::from_datablock(BlockSize::new(4, 2, 1), DataBlock::from_slice_f32(&values));
+        ImageShared {
+            pixel_block: Box::new(pixel_block),
+            display_window: BBox2Di::new(0, 0, 1920, 1080),
+            data_window,
+            spec: ImageSpec::new(),
+        }
+    }
+
+    #[test]
+    fn test_proxy_window() {
+        let window = BBox2Di::new(-3, 1, 1920, 1081);
+        assert_eq!(proxy_window(window, ProxyScale::Full), window);
+        assert_eq!(
+            proxy_window(window, ProxyScale::Half),
+            BBox2Di::new(-2, 0, 960, 541)
+        );
+        assert_eq!(
+            proxy_window(window, ProxyScale::Eighth),
+            BBox2Di::new(-1, 0, 240, 136)
+        );
+        assert_eq!(proxy_value(100.0, ProxyScale::Quarter), 25.0);
+        assert_eq!(
+            combine_proxy_scale(ProxyScale::Half, ProxyScale::Quarter),
+            ProxyScale::Quarter
+        );
+        assert_eq!(
+            combine_proxy_scale(ProxyScale::Half, ProxyScale::Full),
+            ProxyScale::Half
+        );
+    }
+
+    #[test]
+    fn test_proxy_pixels() {
+        let mut image = test_image(BBox2Di::new(0, 0, 4, 2));
+        assert!(proxy_image_in_place(&mut image, ProxyScale::Half));
+        assert_eq!(image.pixel_block.as_slice_f32(), &[2.5, 4.5]);
+        assert_eq!(image.data_window, BBox2Di::new(0, 0, 2, 1));
+        assert_eq!(image.display_window, BBox2Di::new(0, 0, 960, 540));
+
+        // The blocks of pixels are aligned to the origin, not the
+        // data window.
+        let mut image = test_image(BBox2Di::new(1, 0, 5, 2));
+        assert!(proxy_image_in_place(&mut image, ProxyScale::Half));
+        assert_eq!(image.pixel_block.as_slice_f32(), &[2.0, 3.5, 5.0]);
+        assert_eq!(image.data_window, BBox2Di::new(0, 0, 3, 1));
+        assert_eq!(image.data_window.width(), image.pixel_block.width());
+
+        let mut image = test_image(BBox2Di::new(0, 0, 4, 2));
+        assert!(proxy_image_in_place(&mut image, ProxyScale::Full));
+        assert_eq!(image.pixel_block.width(), 4);
+        assert!(!proxy_image_in_place(&mut image, ProxyScale::Uninitialized));
+    }
+}
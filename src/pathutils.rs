@@ -22,75 +22,135 @@
 use log::{debug, warn};
 use shellexpand;
 
-struct FramePaddingIndexRange {
+/// A frame number token in a file path, such as "####", "@@@@",
+/// "%04d" or "$F4".
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct FrameToken {
     start_index: usize,
     end_index: usize,
     padding_count: usize,
 }
 
-fn find_frame_padding_index_range(value: &String) -> FramePaddingIndexRange {
-    debug!("find_frame_padding_index: {}", value);
-    let mut start_index = 0;
-    let mut end_index = 0;
-    let mut last_char_valid = false;
-    for (i, byte) in value.bytes().enumerate() {
-        if byte == b'#' {
-            if last_char_valid == false {
-                // This is the first time we see a '#' character.
-                start_index = i;
-                end_index = i + 1;
-            } else {
-                // This might be the last time we see a '#' character.
-                end_index = i + 1;
+/// A file path split into a frame pattern and a frame number.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FramePattern {
+    /// The path with the frame number replaced by '#' characters.
+    pub pattern: String,
+    pub frame: i32,
+    /// The byte index of the '#' characters in the pattern.
+    pub frame_index: usize,
+    /// The number of digits in the frame number, not including the
+    /// negative sign.
+    pub padding_count: usize,
+}
+
+/// The number of ASCII digits at the start of 'bytes'.
+fn count_digits(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .take_while(|byte| byte.is_ascii_digit())
+        .count()
+}
+
+/// Parse the padding number in a token, such as the "4" in "%04d".
+fn parse_padding(bytes: &[u8]) -> usize {
+    std::str::from_utf8(bytes)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1)
+}
+
+/// Find Houdini-style "$F" and "$F4" tokens.
+///
+/// These are found before environment variables are expanded,
+/// otherwise they would be treated as environment variables.
+fn find_houdini_frame_tokens(value: &str) -> Vec<FrameToken> {
+    let bytes = value.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if (bytes[i] != b'$') || (bytes.get(i + 1) != Some(&b'F')) {
+            i += 1;
+            continue;
+        }
+        let digits_start = i + 2;
+        let digits_end = digits_start + count_digits(&bytes[digits_start..]);
+        let next_byte = bytes.get(digits_end).copied().unwrap_or(b'.');
+        if next_byte.is_ascii_alphanumeric() || (next_byte == b'_') {
+            // An environment variable starting with "F", such as
+            // "$FOO".
+            i = digits_end;
+            continue;
+        }
+        tokens.push(FrameToken {
+            start_index: i,
+            end_index: digits_end,
+            padding_count: parse_padding(&bytes[digits_start..digits_end]),
+        });
+        i = digits_end;
+    }
+    tokens
+}
+
+/// Find "####", "@@@@" and printf-style "%04d" tokens. Each '#' and
+/// '@' character is one digit of padding.
+fn find_frame_tokens(value: &str) -> Vec<FrameToken> {
+    debug!("find_frame_tokens: {}", value);
+    let bytes = value.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if (byte == b'#') || (byte == b'@') {
+            let count = bytes[i..].iter().take_while(|v| **v == byte).count();
+            tokens.push(FrameToken {
+                start_index: i,
+                end_index: i + count,
+                padding_count: count,
+            });
+            i += count;
+        } else if byte == b'%' {
+            let digits_start = i + 1;
+            let digits_end = digits_start + count_digits(&bytes[digits_start..]);
+            if bytes.get(digits_end) != Some(&b'd') {
+                i += 1;
+                continue;
             }
-            last_char_valid = true;
+            tokens.push(FrameToken {
+                start_index: i,
+                end_index: digits_end + 1,
+                padding_count: parse_padding(&bytes[digits_start..digits_end]),
+            });
+            i = digits_end + 1;
         } else {
-            if last_char_valid == true {
-                // The previous character was the last time we saw
-                // '#'.
-                break;
-            }
+            i += 1;
         }
     }
-    let padding_count = end_index - start_index;
-    FramePaddingIndexRange {
-        start_index,
-        end_index,
-        padding_count,
+    tokens
+}
+
+/// Format 'frame' with at least 'padding_count' digits. Negative
+/// frames have the sign in front of the padded digits, for example
+/// "-0005".
+pub fn format_frame(frame: i32, padding_count: usize) -> String {
+    let digits = format!("{:0width$}", frame.unsigned_abs(), width = padding_count);
+    match frame < 0 {
+        true => format!("-{}", digits),
+        false => digits,
     }
 }
 
-fn create_expanded_string(
-    value: &String,
-    index_range: FramePaddingIndexRange,
-    frame: i32,
-) -> String {
-    let mut expanded_string = value.clone();
-    debug!(
-        "index: start={} end={} pad={}",
-        index_range.start_index, index_range.end_index, index_range.padding_count
-    );
-
-    // FIXME: Create a way to use the padding_count to dynamically
-    // change the number formatted into the string. "format!" is a
-    // compile time creation. This is a pretty bad hack.
-    let frame_string = match index_range.padding_count {
-        0 => "".to_string(),
-        1 => format!("{:01}", frame),
-        2 => format!("{:02}", frame),
-        3 => format!("{:03}", frame),
-        4 => format!("{:04}", frame),
-        5 => format!("{:05}", frame),
-        6 => format!("{:06}", frame),
-        7 => format!("{:07}", frame),
-        8 => format!("{:08}", frame),
-        9 => format!("{:09}", frame),
-        _ => "".to_string(),
-    };
-    expanded_string.replace_range(
-        index_range.start_index..index_range.end_index,
-        &frame_string,
-    );
+fn replace_frame_tokens(value: &str, tokens: &[FrameToken], frame: i32) -> String {
+    let mut expanded_string = value.to_string();
+    // Replace from the end, so the earlier indices stay valid.
+    for token in tokens.iter().rev() {
+        debug!(
+            "index: start={} end={} pad={}",
+            token.start_index, token.end_index, token.padding_count
+        );
+        let frame_string = format_frame(frame, token.padding_count);
+        expanded_string.replace_range(token.start_index..token.end_index, &frame_string);
+    }
     expanded_string
 }
 
@@ -108,14 +168,140 @@ fn expand_file_path(value: &str) -> String {
     expanded_path.to_string()
 }
 
+/// Expand environment variables and frame number tokens in 'value'.
+///
+/// The frame tokens "####", "@@@@", "%04d", "%d", "$F4" and "$F" are
+/// supported, and a path may contain more than one token.
 pub fn expand_string(value: String, frame: i32) -> String {
     debug!("expand_string: {} frame={}", value, frame);
+    let houdini_tokens = find_houdini_frame_tokens(&value);
+    let value = replace_frame_tokens(&value, &houdini_tokens, frame);
     let expanded_path = expand_file_path(&value);
-    let index_range = find_frame_padding_index_range(&expanded_path);
-    if index_range.padding_count > 0 {
-        create_expanded_string(&expanded_path, index_range, frame)
+    let tokens = find_frame_tokens(&expanded_path);
+    if !tokens.is_empty() {
+        replace_frame_tokens(&expanded_path, &tokens, frame)
     } else {
         // No expansion needed.
         expanded_path
     }
 }
+
+/// Split a file path into a frame pattern and frame number, the
+/// inverse of 'expand_string'.
+///
+/// The frame number is the last run of digits in the file name,
+/// before the file extension. A '-' is a negative sign when it
+/// follows a '.' or '_' character. For example "image.-0005.exr"
+/// is split into "image.####.exr" and frame -5.
+pub fn split_frame_number(path: &str) -> Option<FramePattern> {
+    let bytes = path.as_bytes();
+    let name_start = match path.rfind(&['/', '\\'][..]) {
+        Some(index) => index + 1,
+        None => 0,
+    };
+    let stem_end = match path[name_start..].rfind('.') {
+        Some(0) | None => bytes.len(),
+        Some(index) => name_start + index,
+    };
+
+    let digits_end = (name_start..stem_end)
+        .rev()
+        .find(|i| bytes[*i].is_ascii_digit())?
+        + 1;
+    let digits_start = (name_start..digits_end)
+        .rev()
+        .take_while(|i| bytes[*i].is_ascii_digit())
+        .last()?;
+    let padding_count = digits_end - digits_start;
+
+    let mut start_index = digits_start;
+    if (digits_start > name_start) && (bytes[digits_start - 1] == b'-') {
+        let is_sign = match digits_start - 1 {
+            index if index == name_start => true,
+            index => (bytes[index - 1] == b'.') || (bytes[index - 1] == b'_'),
+        };
+        if is_sign {
+            start_index = digits_start - 1;
+        }
+    }
+    let frame: i32 = path[start_index..digits_end].parse().ok()?;
+
+    let mut pattern = path.to_string();
+    pattern.replace_range(start_index..digits_end, &"#".repeat(padding_count));
+    Some(FramePattern {
+        pattern,
+        frame,
+        frame_index: start_index,
+        padding_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_hash_padding() {
+        let path = "image.####.exr".to_string();
+        assert_eq!(expand_string(path.clone(), 1001), "image.1001.exr");
+        assert_eq!(expand_string(path.clone(), 7), "image.0007.exr");
+        assert_eq!(expand_string(path, -5), "image.-0005.exr");
+        assert_eq!(expand_string("image.#.exr".to_string(), 42), "image.42.exr");
+        assert_eq!(
+            expand_string("image.############.exr".to_string(), 1),
+            "image.000000000001.exr"
+        );
+    }
+
+    #[test]
+    fn test_expand_token_styles() {
+        assert_eq!(
+            expand_string("image.%04d.exr".to_string(), 12),
+            "image.0012.exr"
+        );
+        assert_eq!(
+            expand_string("image.%d.exr".to_string(), 12),
+            "image.12.exr"
+        );
+        assert_eq!(
+            expand_string("image.@@@.exr".to_string(), 12),
+            "image.012.exr"
+        );
+        assert_eq!(
+            expand_string("image.$F4.exr".to_string(), 12),
+            "image.0012.exr"
+        );
+        assert_eq!(
+            expand_string("image.$F.exr".to_string(), 12),
+            "image.12.exr"
+        );
+    }
+
+    #[test]
+    fn test_expand_multiple_tokens() {
+        assert_eq!(
+            expand_string("shot_##/image.%04d.exr".to_string(), 3),
+            "shot_03/image.0003.exr"
+        );
+    }
+
+    #[test]
+    fn test_split_frame_number() {
+        let value = split_frame_number("/path/plate_v002.1001.exr").unwrap();
+        assert_eq!(value.pattern, "/path/plate_v002.####.exr");
+        assert_eq!(value.frame, 1001);
+        assert_eq!(value.frame_index, 17);
+        assert_eq!(value.padding_count, 4);
+
+        let value = split_frame_number("image.-0005.exr").unwrap();
+        assert_eq!(value.pattern, "image.####.exr");
+        assert_eq!(value.frame, -5);
+
+        let value = split_frame_number("shot-0010.exr").unwrap();
+        assert_eq!(value.pattern, "shot-####.exr");
+        assert_eq!(value.frame, 10);
+
+        assert_eq!(split_frame_number("/path/1001/image.exr"), None);
+        assert_eq!(split_frame_number("image.mp4"), None);
+    }
+}
@@ -20,6 +20,7 @@
  */
 
 pub mod blocksize;
+pub mod compressed;
 pub mod datablock;
 pub mod dataslice;
 pub mod datatype;
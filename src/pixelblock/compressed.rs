@@ -0,0 +1,174 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Compressed pixel data, used to fit more images into the RAM cache.
+ */
+
+use log::{debug, error};
+
+use crate::cxxbridge::ffi::BlockSize;
+use crate::cxxbridge::ffi::CacheCompression;
+use crate::cxxbridge::ffi::DataType;
+use crate::pixelblock::datablock::DataBlock;
+use crate::pixelblock::pixelblock::PixelBlock;
+
+/// A PixelBlock with the pixel data compressed into bytes.
+///
+/// 'data_type' is the type of the original pixels, and the type
+/// restored by 'decompress'. 'stored_data_type' is the type of the
+/// compressed pixels, which is Half16 for lossy compression of
+/// Float32 pixels.
+#[derive(Debug, Clone)]
+pub struct CompressedPixelBlock {
+    blocksize: BlockSize,
+    data_type: DataType,
+    stored_data_type: DataType,
+    bytes: Vec<u8>,
+}
+
+impl CompressedPixelBlock {
+    /// Compress the pixel block, or return None if the compression
+    /// is disabled or would not reduce the size of the data.
+    pub fn compress(
+        pixel_block: &PixelBlock,
+        compression: CacheCompression,
+    ) -> Option<CompressedPixelBlock> {
+        let data_type = pixel_block.data_type();
+        let stored_data_type = match compression {
+            CacheCompression::Lossless => data_type,
+            CacheCompression::LossyHalf16 => match data_type {
+                DataType::Float32 => DataType::Half16,
+                _ => data_type,
+            },
+            _ => return None,
+        };
+
+        let bytes = match stored_data_type == data_type {
+            true => pixel_block.datablock().to_le_bytes(),
+            false => {
+                let mut datablock = pixel_block.datablock().clone();
+                datablock.convert_into_data_type(stored_data_type);
+                datablock.to_le_bytes()
+            }
+        };
+        let bytes = lz4_flex::compress_prepend_size(&bytes);
+        debug!(
+            "Compressed pixels from {} to {} bytes",
+            pixel_block.size_bytes(),
+            bytes.len()
+        );
+        if bytes.len() >= pixel_block.size_bytes() {
+            return None;
+        }
+
+        Some(CompressedPixelBlock {
+            blocksize: pixel_block.blocksize(),
+            data_type,
+            stored_data_type,
+            bytes,
+        })
+    }
+
+    pub fn decompress(&self) -> PixelBlock {
+        let bytes = match lz4_flex::decompress_size_prepended(&self.bytes) {
+            Ok(value) => value,
+            Err(e) => {
+                error!("Could not decompress pixels: {}", e);
+                return PixelBlock::new(self.blocksize, self.data_type);
+            }
+        };
+        let mut datablock = DataBlock::from_le_bytes(&bytes, self.stored_data_type);
+        datablock.convert_into_data_type(self.data_type);
+        PixelBlock::from_datablock(self.blocksize, datablock)
+    }
+
+    pub fn size_bytes(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn data_type(&self) -> DataType {
+        self.data_type
+    }
+
+    pub fn width(&self) -> i32 {
+        self.blocksize.width()
+    }
+
+    pub fn height(&self) -> i32 {
+        self.blocksize.height()
+    }
+
+    pub fn num_channels(&self) -> i32 {
+        self.blocksize.num_channels()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lossless_f32() {
+        let blocksize = BlockSize::new(16, 8, 4);
+        let mut pixelblock = PixelBlock::new(blocksize, DataType::Float32);
+        for (i, value) in pixelblock.as_mut_slice_f32().iter_mut().enumerate() {
+            *value = ((i % 7) as f32) * 0.1;
+        }
+        let compressed =
+            CompressedPixelBlock::compress(&pixelblock, CacheCompression::Lossless).unwrap();
+        assert!(compressed.size_bytes() < pixelblock.size_bytes());
+
+        let decompressed = compressed.decompress();
+        assert_eq!(decompressed.data_type(), DataType::Float32);
+        assert_eq!(decompressed.as_slice_f32(), pixelblock.as_slice_f32());
+    }
+
+    #[test]
+    fn lossy_f32() {
+        let blocksize = BlockSize::new(16, 8, 4);
+        let mut pixelblock = PixelBlock::new(blocksize, DataType::Float32);
+        for (i, value) in pixelblock.as_mut_slice_f32().iter_mut().enumerate() {
+            *value = ((i % 7) as f32) * 0.1;
+        }
+        let compressed =
+            CompressedPixelBlock::compress(&pixelblock, CacheCompression::LossyHalf16).unwrap();
+
+        let decompressed = compressed.decompress();
+        assert_eq!(decompressed.data_type(), DataType::Float32);
+        assert_eq!(decompressed.width(), 16);
+        assert_eq!(decompressed.height(), 8);
+        assert_eq!(decompressed.num_channels(), 4);
+        for (a, b) in decompressed
+            .as_slice_f32()
+            .iter()
+            .zip(pixelblock.as_slice_f32())
+        {
+            assert!((a - b).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn uncompressed() {
+        let blocksize = BlockSize::new(4, 4, 3);
+        let pixelblock = PixelBlock::new(blocksize, DataType::UInt8);
+        let compressed =
+            CompressedPixelBlock::compress(&pixelblock, CacheCompression::Uncompressed);
+        assert!(compressed.is_none());
+    }
+}
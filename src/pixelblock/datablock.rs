@@ -111,6 +111,11 @@ impl DataBlock {
         DataBlock::from_slice(data_slice)
     }
 
+    pub fn from_slice_f16(slice: &[f16]) -> DataBlock {
+        let data_slice = DataSlice::Half16(slice);
+        DataBlock::from_slice(data_slice)
+    }
+
     pub fn from_slice_u16(slice: &[u16]) -> DataBlock {
         let data_slice = DataSlice::UInt16(slice);
         DataBlock::from_slice(data_slice)
@@ -219,6 +224,58 @@ impl DataBlock {
         self.len() * size_bytes
     }
 
+    /// The data as raw (little-endian) bytes.
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::<u8>::with_capacity(self.size_bytes());
+        match &self.inner {
+            DataBlockEnum::Float32(data) => {
+                for value in data {
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            DataBlockEnum::UInt8(data) => bytes.extend_from_slice(&data[..]),
+            DataBlockEnum::Half16(data) => {
+                for value in data {
+                    bytes.extend_from_slice(&value.to_bits().to_le_bytes());
+                }
+            }
+            DataBlockEnum::UInt16(data) => {
+                for value in data {
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+        }
+        bytes
+    }
+
+    /// Construct from raw (little-endian) bytes, as created by
+    /// 'to_le_bytes'.
+    pub fn from_le_bytes(bytes: &[u8], data_type: DataType) -> DataBlock {
+        let inner = match data_type {
+            DataType::Float32 => DataBlockEnum::Float32(
+                bytes
+                    .chunks_exact(4)
+                    .map(|x| f32::from_le_bytes([x[0], x[1], x[2], x[3]]))
+                    .collect(),
+            ),
+            DataType::UInt8 => DataBlockEnum::UInt8(bytes.to_vec()),
+            DataType::Half16 => DataBlockEnum::Half16(
+                bytes
+                    .chunks_exact(2)
+                    .map(|x| f16::from_bits(u16::from_le_bytes([x[0], x[1]])))
+                    .collect(),
+            ),
+            DataType::UInt16 => DataBlockEnum::UInt16(
+                bytes
+                    .chunks_exact(2)
+                    .map(|x| u16::from_le_bytes([x[0], x[1]]))
+                    .collect(),
+            ),
+            _ => panic!("Unsupported DataType: {:#?}", data_type),
+        };
+        DataBlock { inner }
+    }
+
     pub fn data_resize(&mut self, new_length: usize, data_type: DataType) {
         debug!(
             "data_resize: new_length={:#?} data_type={:#?}",
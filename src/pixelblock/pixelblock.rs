@@ -265,6 +265,14 @@ impl PixelBlock {
         self.datablock.size_bytes()
     }
 
+    pub fn blocksize(&self) -> BlockSize {
+        self.blocksize
+    }
+
+    pub fn datablock(&self) -> &DataBlock {
+        &self.datablock
+    }
+
     pub fn get_pixel_index(&self, x: i32, y: i32) -> isize {
         self.blocksize.get_index(x, y)
     }
@@ -0,0 +1,147 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+#include <algorithm>
+#include <opencompgraph/prefetch.h>
+
+namespace open_comp_graph {
+
+Prefetcher::Prefetcher(std::shared_ptr<Cache> cache, size_t num_threads) noexcept
+        : cache{std::move(cache)},
+          active_count{0},
+          playhead{0.0},
+          direction{1},
+          stop{false},
+          cache_full{false} {
+    num_threads = std::max<size_t>(num_threads, 1);
+    for (size_t i = 0; i < num_threads; ++i) {
+        this->threads.emplace_back(&Prefetcher::run_worker, this);
+    }
+}
+
+Prefetcher::~Prefetcher() {
+    {
+        std::lock_guard<std::mutex> lock(this->mutex);
+        this->stop = true;
+        this->queue.clear();
+    }
+    this->queue_condition.notify_all();
+    for (auto &thread : this->threads) {
+        thread.join();
+    }
+}
+
+void Prefetcher::prefetch(const Graph &graph,
+                          const Node &node,
+                          double playhead,
+                          int32_t direction,
+                          size_t frame_count) noexcept {
+    direction = (direction < 0) ? -1 : 1;
+    std::vector<double> frames;
+    frames.reserve(frame_count);
+    for (size_t i = 1; i <= frame_count; ++i) {
+        frames.push_back(playhead + static_cast<double>(direction * static_cast<int32_t>(i)));
+    }
+    auto requests = graph.prefetch_requests(node, frames, this->cache);
+    {
+        std::lock_guard<std::mutex> lock(this->mutex);
+        this->playhead = playhead;
+        this->direction = direction;
+        this->cache_full = false;
+        this->queue.clear();
+        for (auto &request : requests) {
+            this->queue.push_back(request);
+        }
+    }
+    this->queue_condition.notify_all();
+}
+
+void Prefetcher::set_playhead(double playhead, int32_t direction) noexcept {
+    std::lock_guard<std::mutex> lock(this->mutex);
+    this->playhead = playhead;
+    this->direction = (direction < 0) ? -1 : 1;
+    auto passed = [this](const PrefetchRequest &request) {
+        return ((request.frame - this->playhead) * this->direction) <= 0.0;
+    };
+    this->queue.erase(
+        std::remove_if(this->queue.begin(), this->queue.end(), passed),
+        this->queue.end());
+    this->idle_condition.notify_all();
+}
+
+void Prefetcher::cancel() noexcept {
+    std::lock_guard<std::mutex> lock(this->mutex);
+    this->queue.clear();
+    this->idle_condition.notify_all();
+}
+
+void Prefetcher::wait() noexcept {
+    std::unique_lock<std::mutex> lock(this->mutex);
+    this->idle_condition.wait(lock, [this] {
+        return this->queue.empty() && (this->active_count == 0);
+    });
+}
+
+size_t Prefetcher::pending_count() const noexcept {
+    std::lock_guard<std::mutex> lock(this->mutex);
+    return this->queue.size() + this->active_count;
+}
+
+bool Prefetcher::budget_reached() const noexcept {
+    std::lock_guard<std::mutex> lock(this->mutex);
+    return this->cache_full;
+}
+
+void Prefetcher::run_worker() noexcept {
+    while (true) {
+        PrefetchRequest request;
+        {
+            std::unique_lock<std::mutex> lock(this->mutex);
+            this->queue_condition.wait(lock, [this] {
+                return this->stop || !this->queue.empty();
+            });
+            if (this->stop) {
+                return;
+            }
+            request = this->queue.front();
+            this->queue.pop_front();
+            this->active_count += 1;
+        }
+
+        // Reading the image does not need the cache lock.
+        auto image = internal::read_prefetch_image(request);
+        auto &cache_box = this->cache->get_box();
+        bool inserted = cache_box->insert_prefetch_image(std::move(image));
+
+        {
+            std::lock_guard<std::mutex> lock(this->mutex);
+            this->active_count -= 1;
+            if (!inserted) {
+                // The cache is full, stop reading more images.
+                this->cache_full = true;
+                this->queue.clear();
+            }
+        }
+        this->idle_condition.notify_all();
+    }
+}
+
+} // namespace open_comp_graph
@@ -0,0 +1,71 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Images read ahead of time (on worker threads), to be stored in the
+ * cache before they are needed.
+ */
+
+use log::debug;
+use std::path::Path;
+
+use crate::cache::CachedImage;
+use crate::cxxbridge::ffi::PrefetchRequest;
+use crate::data::HashValue;
+use crate::node::read_image;
+
+/// An image read ahead of time, waiting to be inserted into the
+/// cache. The image is None if the file could not be read.
+#[derive(Debug)]
+pub struct PrefetchImage {
+    key: HashValue,
+    image: Option<CachedImage>,
+}
+
+impl PrefetchImage {
+    pub fn key(&self) -> u64 {
+        self.key
+    }
+
+    pub fn size_bytes(&self) -> usize {
+        match &self.image {
+            Some(image) => image.size_bytes(),
+            None => 0,
+        }
+    }
+
+    pub fn into_parts(self) -> (HashValue, Option<CachedImage>) {
+        (self.key, self.image)
+    }
+}
+
+/// Read the image for the request. This does not need the graph or
+/// the cache, so many requests can be read at the same time.
+pub fn read_prefetch_image(request: &PrefetchRequest) -> Box<PrefetchImage> {
+    debug!(
+        "Prefetch: key={} frame={} path={}",
+        request.key, request.frame, request.file_path
+    );
+    let path = Path::new(&request.file_path);
+    let num_threads = 0;
+    let image = read_image::read_cached_image(path, request.proxy_scale, num_threads).ok();
+    Box::new(PrefetchImage {
+        key: request.key,
+        image,
+    })
+}
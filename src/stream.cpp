@@ -73,6 +73,10 @@ internal::ImageSpec StreamData::clone_image_spec() const noexcept {
     return this->inner.inner->clone_image_spec();
 };
 
+ProxyScale StreamData::proxy_scale() const noexcept {
+    return this->inner.inner->proxy_scale();
+};
+
 size_t StreamData::deformers_len() const noexcept {
     return this->inner.inner->deformers_len();
 };
@@ -114,4 +118,24 @@ DataType StreamData::pixel_data_type() const noexcept {
     return this->inner.inner->pixel_data_type();
 };
 
+size_t StreamData::layers_len() const noexcept {
+    return this->inner.inner->layers_len();
+};
+
+rust::Str StreamData::layer_name(size_t index) const noexcept {
+    return this->inner.inner->layer_name(index);
+};
+
+rust::Vec<rust::String> StreamData::layer_channel_names(size_t index) const noexcept {
+    return this->inner.inner->layer_channel_names(index);
+};
+
+BBox2Di StreamData::layer_data_window(size_t index) const noexcept {
+    return this->inner.inner->layer_data_window(index);
+};
+
+rust::Slice<const uint8_t> StreamData::layer_pixel_buffer(size_t index) const noexcept {
+    return this->inner.inner->layer_pixel_buffer(index);
+};
+
 } // namespace open_comp_graph
@@ -21,6 +21,7 @@
 
 use log::debug;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::colorop::color_ops_hash;
 use crate::colorop::ColorOp;
@@ -30,10 +31,12 @@ use crate::cxxbridge::ffi::BBox2Di;
 use crate::cxxbridge::ffi::DataType;
 use crate::cxxbridge::ffi::ImageSpec;
 use crate::cxxbridge::ffi::Matrix4;
+use crate::cxxbridge::ffi::ProxyScale;
 use crate::cxxbridge::ffi::StreamDataState;
 use crate::data::HashValue;
 use crate::deformer::Deformer;
 use crate::deformutils;
+use crate::imagelayer::ImageLayer;
 use crate::pixelblock::dataslice::DataSlice;
 use crate::pixelblock::pixelblock::PixelBlock;
 use crate::pixelblock::utils::transmute_slice_f16_to_u8;
@@ -48,9 +51,26 @@ pub struct StreamDataImpl {
     data_window: BBox2Di,
     color_matrix: Matrix4,
     image_spec: ImageSpec,
-    pixel_block: Rc<PixelBlock>,
+    pixel_block: Arc<PixelBlock>,
     deformers: Vec<Box<dyn Deformer>>,
     color_ops: Vec<Box<dyn ColorOp>>,
+    // Extra named channels (such as "depth" or "motion") passed
+    // through with the RGBA pixels.
+    layers: Vec<ImageLayer>,
+    // The resolution of the pixels (and windows), relative to the
+    // full resolution image.
+    proxy_scale: ProxyScale,
+}
+
+/// The pixels of a pixel block, as bytes.
+fn pixel_block_buffer(pixel_block: &PixelBlock) -> &[u8] {
+    let slice = pixel_block.as_slice();
+    match &slice {
+        DataSlice::Float32(data) => transmute_slice_f32_to_u8(data),
+        DataSlice::UInt8(data) => data,
+        DataSlice::Half16(data) => transmute_slice_f16_to_u8(data),
+        DataSlice::UInt16(data) => transmute_slice_u16_to_u8(data),
+    }
 }
 
 impl StreamDataImpl {
@@ -58,7 +78,7 @@ impl StreamDataImpl {
         let state = StreamDataState::Invalid;
         let hash = 0;
 
-        let pixel_block = Rc::new(PixelBlock::new_color_bars());
+        let pixel_block = Arc::new(PixelBlock::new_color_bars());
         let bbox_max_width = pixel_block.width();
         let bbox_max_height = pixel_block.height();
         let display_window = BBox2Di::new(0, 0, bbox_max_width, bbox_max_height);
@@ -67,6 +87,8 @@ impl StreamDataImpl {
         let image_spec = ImageSpec::new();
         let deformers = Vec::new();
         let color_ops = Vec::new();
+        let layers = Vec::new();
+        let proxy_scale = ProxyScale::Full;
 
         StreamDataImpl {
             state,
@@ -78,6 +100,8 @@ impl StreamDataImpl {
             pixel_block,
             deformers,
             color_ops,
+            layers,
+            proxy_scale,
         }
     }
 
@@ -94,7 +118,8 @@ impl StreamDataImpl {
     }
 
     pub fn size_bytes(&self) -> usize {
-        self.pixel_block.size_bytes()
+        let layers_bytes: usize = self.layers.iter().map(|l| l.size_bytes()).sum();
+        self.pixel_block.size_bytes() + layers_bytes
     }
 
     pub fn hash(&self) -> HashValue {
@@ -137,6 +162,14 @@ impl StreamDataImpl {
         self.image_spec = value;
     }
 
+    pub fn proxy_scale(&self) -> ProxyScale {
+        self.proxy_scale
+    }
+
+    pub fn set_proxy_scale(&mut self, value: ProxyScale) {
+        self.proxy_scale = value;
+    }
+
     pub fn apply_deformers(
         &self,
         buffer: &mut [f32],
@@ -193,34 +226,44 @@ impl StreamDataImpl {
         self.color_ops.clear();
     }
 
+    pub fn layers(&self) -> &Vec<ImageLayer> {
+        &self.layers
+    }
+
+    pub fn layers_len(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn layer(&self, index: usize) -> Option<&ImageLayer> {
+        self.layers.get(index)
+    }
+
+    pub fn set_layers(&mut self, value: Vec<ImageLayer>) {
+        self.layers = value;
+    }
+
     pub fn clone_pixel_block(&self) -> PixelBlock {
         (*self.pixel_block).clone()
     }
 
-    pub fn pixel_block(&self) -> Rc<PixelBlock> {
+    pub fn pixel_block(&self) -> Arc<PixelBlock> {
         self.pixel_block.clone()
     }
 
     pub fn pixel_block_as_mut(&mut self) -> &mut PixelBlock {
-        Rc::make_mut(&mut self.pixel_block)
+        Arc::make_mut(&mut self.pixel_block)
     }
 
-    pub fn set_pixel_block(&mut self, pixel_block: Rc<PixelBlock>) {
+    pub fn set_pixel_block(&mut self, pixel_block: Arc<PixelBlock>) {
         // when the "old" Rc goes out of scope it will be cleaned up
         // if there are no more references to the underlying
         // allocation.
-        let _old_data = Rc::clone(&self.pixel_block);
+        let _old_data = Arc::clone(&self.pixel_block);
         self.pixel_block = pixel_block.clone();
     }
 
     pub fn pixel_buffer(&self) -> &[u8] {
-        let slice = self.pixel_block.as_slice();
-        match &slice {
-            DataSlice::Float32(data) => transmute_slice_f32_to_u8(data),
-            DataSlice::UInt8(data) => data,
-            DataSlice::Half16(data) => transmute_slice_f16_to_u8(data),
-            DataSlice::UInt16(data) => transmute_slice_u16_to_u8(data),
-        }
+        pixel_block_buffer(&self.pixel_block)
     }
 
     pub fn pixel_width(&self) -> i32 {
@@ -307,6 +350,10 @@ impl StreamDataImplRc {
         self.inner.clone_image_spec()
     }
 
+    pub fn proxy_scale(&self) -> ProxyScale {
+        self.inner.proxy_scale()
+    }
+
     pub fn apply_deformers(
         &self,
         buffer: &mut [f32],
@@ -341,6 +388,41 @@ impl StreamDataImplRc {
         self.inner.color_ops_len()
     }
 
+    pub fn layers_len(&self) -> usize {
+        self.inner.layers_len()
+    }
+
+    /// The name of the layer at 'index', or an empty string if there
+    /// is no layer.
+    pub fn layer_name(&self, index: usize) -> &str {
+        match self.inner.layer(index) {
+            Some(layer) => layer.name(),
+            None => "",
+        }
+    }
+
+    pub fn layer_channel_names(&self, index: usize) -> Vec<String> {
+        match self.inner.layer(index) {
+            Some(layer) => layer.channel_names().clone(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn layer_data_window(&self, index: usize) -> BBox2Di {
+        match self.inner.layer(index) {
+            Some(layer) => layer.data_window(),
+            None => BBox2Di::new(0, 0, 0, 0),
+        }
+    }
+
+    /// The Float32 pixels of the layer at 'index', as bytes.
+    pub fn layer_pixel_buffer(&self, index: usize) -> &[u8] {
+        match self.inner.layer(index) {
+            Some(layer) => pixel_block_buffer(layer.pixel_block()),
+            None => &[],
+        }
+    }
+
     // pub fn pixel_block(&self) -> Rc<PixelBlock> {
     //     self.inner.pixel_block()
     // }
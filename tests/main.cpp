@@ -26,21 +26,43 @@
 #include "test_graph/test_graph_no_ops.h"
 #include "test_graph/test_graph_re_eval_modified_graph.h"
 #include "test_graph/test_graph_re_connect_graph.h"
+#include "test_graph/test_graph_node_status.h"
+#include "test_graph/test_graph_validate.h"
+#include "test_graph/test_graph_observer.h"
+#include "test_graph/test_graph_proxy.h"
+#include "test_frameset/test_frameset.h"
+#include "test_imagesequence/test_imagesequence.h"
 #include "test_stream/test_stream_empty_write_geom.h"
 #include "test_cache/test_cache_read_image_seq.h"
 #include "test_cache/test_cache_init.h"
+#include "test_cache/test_cache_disk.h"
+#include "test_cache/test_cache_invalidate.h"
+#include "test_cache/test_cache_pin.h"
+#include "test_cache/test_cache_stats.h"
+#include "test_cache/test_cache_cached_frames.h"
+#include "test_cache/test_cache_compress.h"
+#include "test_cache/test_cache_prefetch.h"
+#include "test_cache/test_cache_shared.h"
 #include "test_node_null/test_node_null.h"
 #include "test_node_imageio/test_node_imageio.h"
 #include "test_node_imageio/test_node_imageio_jpeg.h"
 #include "test_node_imageio/test_node_imageio_j2k.h"
 #include "test_node_imageio/test_node_imageio_png.h"
 #include "test_node_imageio/test_node_imageio_tiff.h"
+#include "test_node_imageio/test_node_imageio_frame_range.h"
+#include "test_node_imageio/test_node_imageio_alpha.h"
+#include "test_node_imageio/test_node_imageio_rust.h"
+#include "test_node_imageio/test_node_imageio_exr_rust.h"
+#include "test_node_imageio/test_node_imageio_layers.h"
+#include "test_node_imageio/test_node_imageio_orientation.h"
 #include "test_node_lens/test_node_lens.h"
 #include "test_node_transform/test_node_transform.h"
 #include "test_node_resample/test_node_resample.h"
 #include "test_node_grade/test_node_grade.h"
 #include "test_node_grade/test_node_grade_concat.h"
 #include "test_node_merge/test_node_merge.h"
+#include "test_node_frame_blend/test_node_frame_blend.h"
+#include "test_node_metadata/test_node_metadata.h"
 #include "test_node_crop/test_node_crop.h"
 #include "test_node_viewer/test_node_viewer.h"
 // #include "test_node_viewer/test_node_viewer_disk_cache.h"
@@ -54,6 +76,13 @@ int main() {
     const bool debug_print = true;
 
     test_cache_init(debug_print);
+    test_cache_invalidate(debug_print);
+    test_cache_pin(debug_print);
+    test_cache_stats(debug_print);
+    test_cache_cached_frames(debug_print);
+    test_cache_compress(debug_print);
+    test_frameset(debug_print);
+    test_imagesequence(debug_print);
 
     // Run single frame tests.
     {
@@ -75,6 +104,10 @@ int main() {
             test_graph_no_ops(debug_print, cache);
             test_graph_re_eval_modified_graph(debug_print, cache);
             test_graph_re_connect_graph(debug_print, cache);
+            test_graph_node_status(debug_print, cache);
+            test_graph_validate(debug_print);
+            test_graph_observer(debug_print);
+            test_graph_proxy(debug_print, cache);
 
             test_node_lens(debug_print, cache);
             test_node_transform(debug_print, cache);
@@ -83,11 +116,19 @@ int main() {
             test_node_imageio_j2k(debug_print, cache);
             test_node_imageio_png(debug_print, cache);
             test_node_imageio_tiff(debug_print, cache);
+            test_node_imageio_frame_range(debug_print, cache);
+            test_node_imageio_alpha(debug_print, cache);
+            test_node_imageio_rust(debug_print, cache);
+            test_node_imageio_exr_rust(debug_print, cache);
+            test_node_imageio_layers(debug_print, cache);
+            test_node_imageio_orientation(debug_print, cache);
             test_node_null(debug_print, cache);
             test_node_resample(debug_print, cache);
             test_node_grade(debug_print, cache);
             test_node_grade_concat(debug_print, cache);
             test_node_merge(debug_print, cache);
+            test_node_frame_blend(debug_print, cache);
+            test_node_metadata(debug_print, cache);
             test_node_crop(debug_print, cache);
             test_node_viewer(debug_print, cache);
             // test_node_viewer_disk_cache(debug_print, cache);
@@ -108,6 +149,9 @@ int main() {
         bench.start();
 
         test_cache_read_image_seq(debug_print);
+        test_cache_disk(debug_print);
+        test_cache_shared(debug_print);
+        test_cache_prefetch(debug_print);
 
         bench.stop();
         bench.print("Mult-Frame Tests:");
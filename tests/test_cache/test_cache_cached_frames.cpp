@@ -0,0 +1,85 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Query which frames of a node are cached, without executing the
+ * graph.
+ */
+
+#include <iostream>
+#include <vector>
+
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+
+int test_cache_cached_frames(const bool debug_print) {
+    if (debug_print) {
+        std::cout << "======================= test_cache_cached_frames()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+
+    auto graph = ocg::Graph();
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    graph.set_node_attr_str(
+        read_node, "file_path",
+        "tests/data/ocg-testdata/images/color_bars/3840x2160_png_compress3/color_bars.####.png");
+
+    const size_t kBytesToGigabytes = 1073741824;  // int(pow(2, 30))
+    auto cache = std::make_shared<ocg::Cache>();
+    cache->set_capacity_bytes(1 * kBytesToGigabytes);
+
+    // Compute only the even frames.
+    std::vector<int32_t> all_frames = {951, 952, 953, 954};
+    std::vector<int32_t> even_frames = {952, 954};
+    graph.execute(read_node, even_frames, cache);
+
+    auto stats_before = cache->stats();
+    auto cached_frames = graph.cached_frames(read_node, all_frames, cache);
+    auto stats_after = cache->stats();
+    if (cached_frames.size() != all_frames.size()) {
+        std::cout << "ERROR: Expected a result for each frame.\n";
+        return 1;
+    }
+    for (auto cached_frame : cached_frames) {
+        std::cout << "Frame: " << cached_frame.frame
+                  << " cached=" << cached_frame.cached << '\n';
+        auto frame = static_cast<int32_t>(cached_frame.frame);
+        auto is_even = (frame % 2) == 0;
+        if (cached_frame.cached != is_even) {
+            std::cout << "ERROR: Frame " << frame
+                      << " has the wrong cached state.\n";
+            return 1;
+        }
+    }
+
+    // Querying must not change the cache.
+    if ((stats_before.hits != stats_after.hits)
+        || (stats_before.misses != stats_after.misses)
+        || (stats_before.count != stats_after.count)) {
+        std::cout << "ERROR: Querying cached frames changed the cache.\n";
+        return 1;
+    }
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test Cache Cached Frames:");
+    }
+    return 0;
+}
@@ -0,0 +1,89 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Store images compressed in the cache, and read them back.
+ */
+
+#include <iostream>
+#include <vector>
+
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+
+size_t cache_read_image(ocg::Graph &graph,
+                        ocg::Node &read_node,
+                        ocg::CacheCompression compression) {
+    const size_t kBytesToGigabytes = 1073741824;  // int(pow(2, 30))
+    auto cache = std::make_shared<ocg::Cache>();
+    cache->set_capacity_bytes(1 * kBytesToGigabytes);
+    cache->set_compression(compression);
+
+    // The second execute reads the (compressed) image from the cache.
+    std::vector<int32_t> frames = {1};
+    graph.execute(read_node, frames, cache);
+    graph.execute(read_node, frames, cache);
+    std::cout << "Cache: \n"
+              << cache->data_debug_string() << '\n';
+
+    auto stats = cache->stats();
+    if (stats.hits != 1) {
+        std::cout << "ERROR: The image was not read from the cache.\n";
+        return 0;
+    }
+    return stats.used_bytes;
+}
+
+int test_cache_compress(const bool debug_print) {
+    if (debug_print) {
+        std::cout << "============================ test_cache_compress()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+
+    auto graph = ocg::Graph();
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    graph.set_node_attr_str(
+        read_node, "file_path",
+        "./tests/data/openexr-images/TestImages/BrightRings.exr");
+
+    auto uncompressed_bytes = cache_read_image(
+        graph, read_node, ocg::CacheCompression::kUncompressed);
+    auto lossless_bytes = cache_read_image(
+        graph, read_node, ocg::CacheCompression::kLossless);
+    auto lossy_bytes = cache_read_image(
+        graph, read_node, ocg::CacheCompression::kLossyHalf16);
+    std::cout << "Cache Bytes: uncompressed=" << uncompressed_bytes
+              << " lossless=" << lossless_bytes
+              << " lossy=" << lossy_bytes << '\n';
+    if ((lossless_bytes == 0) || (lossless_bytes >= uncompressed_bytes)) {
+        std::cout << "ERROR: Lossless compression did not reduce the size.\n";
+        return 1;
+    }
+    if ((lossy_bytes == 0) || (lossy_bytes >= uncompressed_bytes)) {
+        std::cout << "ERROR: Lossy compression did not reduce the size.\n";
+        return 1;
+    }
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test Cache Compress:");
+    }
+    return 0;
+}
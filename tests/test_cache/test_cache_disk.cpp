@@ -0,0 +1,111 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Read an image sequence with a tiny RAM cache, so that images are
+ * evicted to the disk cache, then read them back again.
+ */
+
+#include <iostream>
+#include <fstream>
+#include <iomanip>
+#include <sstream>
+#include <vector>
+
+#include <opencompgraph.h>
+#include "../generate_frame_range.h"
+
+namespace ocg = open_comp_graph;
+
+
+int test_cache_disk(const bool debug_print) {
+    if (debug_print) {
+        std::cout << "============================== test_cache_disk()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+    auto graph = ocg::Graph();
+
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    graph.set_node_attr_str(
+        read_node, "file_path",
+        "tests/data/ocg-testdata/images/color_bars/3840x2160_jpg/color_bars.####.jpg");
+    graph.set_node_attr_i32(read_node, "use_cache", 1);
+
+    // Only enough RAM for a couple of frames; everything else spills
+    // to the disk cache.
+    const size_t kBytesToMegabytes = 1048576;  // int(pow(2, 20))
+    const size_t kBytesToGigabytes = 1073741824;  // int(pow(2, 30))
+    auto cache = std::make_shared<ocg::Cache>();
+    cache->set_capacity_bytes(64 * kBytesToMegabytes);
+    auto ok = cache->enable_disk_cache(
+        "./tests/data/out/disk_cache/",
+        1 * kBytesToGigabytes,
+        ocg::DiskCacheImageType::kExrLossyHalf16);
+    if (!ok || !cache->disk_cache_enabled()) {
+        std::cout << "ERROR: Could not enable disk cache.\n";
+        return 1;
+    }
+
+    auto frames = generate_frame_range(951, 960);
+
+    // First pass; reads from the source images and fills the caches.
+    graph.execute(read_node, frames, cache);
+    std::cout << "Cache (first pass): \n"
+              << cache->data_debug_string() << '\n';
+    std::cout << "Disk Cache: count: "
+              << cache->disk_cache_count() << '\n';
+    std::cout << "Disk Cache: used_bytes: "
+              << cache->disk_cache_used_bytes() << '\n';
+
+    // Second pass; the evicted frames are read back from disk.
+    graph.execute(read_node, frames, cache);
+    std::cout << "Cache (second pass): \n"
+              << cache->data_debug_string() << '\n';
+
+    // A disk cache file that cannot be read (such as a file
+    // truncated by a crashed session) is a cache miss, and the
+    // source image is read again.
+    cache->set_capacity_bytes(1);
+    cache->set_capacity_bytes(64 * kBytesToMegabytes);
+    auto frame = frames[0];
+    auto hash = graph.node_hash(read_node, frame);
+    std::stringstream file_path;
+    file_path << "./tests/data/out/disk_cache/ocg_"
+              << std::hex << std::setw(16) << std::setfill('0') << hash
+              << ".exr";
+    {
+        std::ofstream truncated(file_path.str(), std::ios::trunc);
+    }
+    std::vector<int32_t> truncated_frames = {frame};
+    graph.execute(read_node, truncated_frames, cache);
+    auto stream_data = graph.output_stream();
+    if ((graph.node_status(read_node) != ocg::NodeStatus::kValid)
+        || (stream_data.pixel_width() == 0)
+        || (stream_data.pixel_height() == 0)) {
+        std::cout << "ERROR: An unreadable disk cache file was used.\n";
+        return 1;
+    }
+
+    cache->disable_disk_cache();
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test Cache Disk:");
+    }
+    return 0;
+}
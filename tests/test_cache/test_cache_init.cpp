@@ -63,6 +63,11 @@ int test_cache_init(const bool debug_print) {
               << cache->used_bytes() << '\n';
     std::cout << "Cache: count: "
               << cache->count() << '\n';
+    cache->set_eviction_policy(ocg::CacheEvictionPolicy::kComputeCost);
+    if (cache->eviction_policy() != ocg::CacheEvictionPolicy::kComputeCost) {
+        std::cout << "ERROR: Cache eviction policy was not set.\n";
+        return 1;
+    }
     std::cout << "Cache: \n"
               << cache->data_debug_string() << '\n';
 
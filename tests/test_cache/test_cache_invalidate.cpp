@@ -0,0 +1,95 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Invalidate cached images when the file on disk changes.
+ */
+
+#include <iostream>
+#include <fstream>
+
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+
+int test_cache_invalidate(const bool debug_print) {
+    if (debug_print) {
+        std::cout << "======================== test_cache_invalidate()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+
+    // Copy an image, so we can change it on disk.
+    const char* src_file_path =
+        "./tests/data/openexr-images/TestImages/BrightRings.exr";
+    const char* file_path =
+        "./tests/data/out/test_cache_invalidate_image.exr";
+    {
+        std::ifstream src(src_file_path, std::ios::binary);
+        std::ofstream dst(file_path, std::ios::binary);
+        dst << src.rdbuf();
+    }
+
+    auto graph = ocg::Graph();
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    graph.set_node_attr_str(read_node, "file_path", file_path);
+    graph.set_node_attr_i32(read_node, "use_cache", 1);
+    auto hash_mode = static_cast<int32_t>(ocg::ReadImageHashMode::kModifiedTime);
+    graph.set_node_attr_i32(read_node, "hash_mode", hash_mode);
+
+    const size_t kBytesToGigabytes = 1073741824;  // int(pow(2, 30))
+    auto cache = std::make_shared<ocg::Cache>();
+    cache->set_capacity_bytes(1 * kBytesToGigabytes);
+
+    auto frame = 1;
+    std::vector<int32_t> frames = {frame};
+    auto hash_before = graph.node_hash(read_node, frame);
+    graph.execute(read_node, frames, cache);
+    std::cout << "Cache count (after read): " << cache->count() << '\n';
+
+    // Overwrite the file with a different image; the hash changes
+    // because the file size and modification time change.
+    {
+        std::ifstream src(
+            "./tests/data/openexr-images/TestImages/AllHalfValues.exr",
+            std::ios::binary);
+        std::ofstream dst(file_path, std::ios::binary);
+        dst << src.rdbuf();
+    }
+    auto hash_after = graph.node_hash(read_node, frame);
+    std::cout << "Hash changed: " << (hash_before != hash_after) << '\n';
+    if (hash_before == hash_after) {
+        std::cout << "ERROR: Hash did not change after the file changed.\n";
+        return 1;
+    }
+
+    // Remove all images read from the output directory.
+    auto removed = cache->invalidate_path("./tests/data/out/");
+    std::cout << "Cache entries invalidated: " << removed << '\n';
+    std::cout << "Cache count (after invalidate): " << cache->count() << '\n';
+    if (cache->count() != 0) {
+        std::cout << "ERROR: Cache entries were not invalidated.\n";
+        return 1;
+    }
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test Cache Invalidate:");
+    }
+    return 0;
+}
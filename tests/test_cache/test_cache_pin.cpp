@@ -0,0 +1,112 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Pin cached images, so they are not evicted.
+ */
+
+#include <iostream>
+#include <vector>
+
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+
+int test_cache_pin(const bool debug_print) {
+    if (debug_print) {
+        std::cout << "=============================== test_cache_pin()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+
+    auto graph = ocg::Graph();
+    auto read1_node = graph.create_node(ocg::NodeType::kReadImage, "read1");
+    auto read2_node = graph.create_node(ocg::NodeType::kReadImage, "read2");
+    graph.set_node_attr_str(
+        read1_node, "file_path",
+        "./tests/data/openexr-images/TestImages/BrightRings.exr");
+    graph.set_node_attr_str(
+        read2_node, "file_path",
+        "./tests/data/openexr-images/TestImages/AllHalfValues.exr");
+
+    const size_t kBytesToGigabytes = 1073741824;  // int(pow(2, 30))
+    auto cache = std::make_shared<ocg::Cache>();
+    cache->set_capacity_bytes(1 * kBytesToGigabytes);
+
+    auto frame = 1;
+    std::vector<int32_t> frames = {frame};
+
+    // Keys may be pinned before the images are computed.
+    auto read1_hash = graph.node_hash(read1_node, frame);
+    cache->pin(read1_hash);
+
+    graph.execute(read1_node, frames, cache);
+    graph.execute(read2_node, frames, cache);
+    std::cout << "Cache (before eviction): \n"
+              << cache->data_debug_string() << '\n';
+
+    // Shrink the cache so everything must be evicted; only the
+    // pinned image is kept.
+    cache->set_capacity_bytes(1);
+    std::cout << "Cache (after eviction): \n"
+              << cache->data_debug_string() << '\n';
+    if ((cache->count() != 1) || (cache->pinned_count() != 1)) {
+        std::cout << "ERROR: The pinned image was evicted.\n";
+        return 1;
+    }
+    std::cout << "Cache: pinned_bytes: "
+              << cache->pinned_bytes() << '\n';
+
+    // Once unpinned, the image can be evicted.
+    cache->unpin(read1_hash);
+    cache->set_capacity_bytes(1);
+    std::cout << "Cache (after unpin): \n"
+              << cache->data_debug_string() << '\n';
+    if (cache->count() != 0) {
+        std::cout << "ERROR: The unpinned image was not evicted.\n";
+        return 1;
+    }
+
+    // The layers of an image are pinned together with the image.
+    auto read3_node = graph.create_node(ocg::NodeType::kReadImage, "read3");
+    graph.set_node_attr_str(
+        read3_node, "file_path",
+        "./tests/data/openexr-images/Beachball/singlepart.0001.exr");
+    graph.set_node_attr_str(read3_node, "layers", "*");
+    auto read3_hash = graph.node_hash(read3_node, frame);
+    cache->pin(read3_hash);
+    cache->set_capacity_bytes(1 * kBytesToGigabytes);
+    graph.execute(read3_node, frames, cache);
+    auto read3_count = cache->count();
+    cache->set_capacity_bytes(1);
+    std::cout << "Cache (layers pinned): \n"
+              << cache->data_debug_string() << '\n';
+    if ((read3_count < 2)
+        || (cache->count() != read3_count)
+        || (cache->pinned_count() != read3_count)) {
+        std::cout << "ERROR: The layers of the pinned image were evicted.\n";
+        return 1;
+    }
+    cache->unpin_all();
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test Cache Pin:");
+    }
+    return 0;
+}
@@ -0,0 +1,99 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Read an image sequence into the cache ahead of the playhead.
+ */
+
+#include <iostream>
+#include <vector>
+
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+
+int test_cache_prefetch(const bool debug_print) {
+    if (debug_print) {
+        std::cout << "============================ test_cache_prefetch()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+
+    auto graph = ocg::Graph();
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    auto grade_node = graph.create_node(ocg::NodeType::kGrade, "grade");
+    graph.set_node_attr_str(
+        read_node, "file_path",
+        "tests/data/ocg-testdata/images/color_bars/3840x2160_png_compress3/color_bars.####.png");
+    graph.connect(read_node, grade_node, 0);
+
+    const size_t kBytesToGigabytes = 1073741824;  // int(pow(2, 30))
+    auto cache = std::make_shared<ocg::Cache>();
+    cache->set_capacity_bytes(2 * kBytesToGigabytes);
+
+    // Prefetch the images upstream of the grade node, after the
+    // playhead.
+    auto num_threads = 2;
+    ocg::Prefetcher prefetcher(cache, num_threads);
+    auto playhead = 950;
+    auto frame_count = 5;
+    prefetcher.prefetch(graph, grade_node, playhead, 1, frame_count);
+    prefetcher.wait();
+    std::cout << "Cache (after prefetch): \n"
+              << cache->data_debug_string() << '\n';
+
+    std::vector<int32_t> frames = {951, 952, 953, 954, 955};
+    auto cached_frames = graph.cached_frames(read_node, frames, cache);
+    for (auto cached_frame : cached_frames) {
+        if (!cached_frame.cached) {
+            std::cout << "ERROR: Frame " << cached_frame.frame
+                      << " was not prefetched.\n";
+            return 1;
+        }
+    }
+
+    // Executing the prefetched frames uses the cache.
+    auto stats_before = cache->stats();
+    graph.execute(grade_node, frames, cache);
+    auto stats_after = cache->stats();
+    if (stats_after.hits != (stats_before.hits + frames.size())) {
+        std::cout << "ERROR: Prefetched frames were not used.\n";
+        return 1;
+    }
+
+    // Prefetching stops when the cache is full, rather than evicting
+    // images.
+    auto small_cache = std::make_shared<ocg::Cache>();
+    small_cache->set_capacity_bytes(cache->used_bytes() / 2);
+    ocg::Prefetcher small_prefetcher(small_cache, num_threads);
+    small_prefetcher.prefetch(graph, read_node, playhead, 1, frame_count);
+    small_prefetcher.wait();
+    std::cout << "Small Cache (after prefetch): \n"
+              << small_cache->data_debug_string() << '\n';
+    if (!small_prefetcher.budget_reached()
+        || (small_cache->stats().evictions != 0)) {
+        std::cout << "ERROR: Prefetch did not stop at the cache capacity.\n";
+        return 1;
+    }
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test Cache Prefetch:");
+    }
+    return 0;
+}
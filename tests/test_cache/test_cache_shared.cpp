@@ -0,0 +1,83 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Share one cache between graphs executing on different threads.
+ */
+
+#include <iostream>
+#include <vector>
+#include <thread>
+
+#include <opencompgraph.h>
+#include "../generate_frame_range.h"
+
+namespace ocg = open_comp_graph;
+
+
+int test_cache_shared(const bool debug_print) {
+    if (debug_print) {
+        std::cout << "============================ test_cache_shared()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+
+    auto cache = ocg::shared_cache();
+    if (cache != ocg::shared_cache()) {
+        std::cout << "ERROR: Shared cache is not shared.\n";
+        return 1;
+    }
+    std::cout << "Shared Cache: capacity_bytes: "
+              << cache->capacity_bytes() << '\n';
+
+    // Each "viewer" has it's own graph, reading the same images.
+    auto execute_viewer = [](int viewer_num) {
+        auto graph = ocg::Graph();
+        auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+        graph.set_node_attr_str(
+            read_node, "file_path",
+            "tests/data/ocg-testdata/images/color_bars/3840x2160_jpg/color_bars.####.jpg");
+        auto frames = generate_frame_range(951, 960);
+        auto shared = ocg::shared_cache();
+        auto status = graph.execute(read_node, frames, shared);
+        std::cout << "Viewer " << viewer_num << " executed: "
+                  << (status == ocg::ExecuteStatus::kSuccess) << '\n';
+    };
+
+    std::vector<std::thread> threads;
+    for (auto i = 0; i < 4; ++i) {
+        threads.push_back(std::thread(execute_viewer, i));
+    }
+    for (auto &thread : threads) {
+        thread.join();
+    }
+
+    // Each frame is only stored once, no matter how many graphs read
+    // it.
+    std::cout << "Shared Cache: \n"
+              << cache->data_debug_string() << '\n';
+    if (cache->count() > 10) {
+        std::cout << "ERROR: Images were stored more than once.\n";
+        return 1;
+    }
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test Cache Shared:");
+    }
+    return 0;
+}
@@ -0,0 +1,106 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Query the cache statistics and inspect the cached images.
+ */
+
+#include <iostream>
+#include <vector>
+
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+
+int test_cache_stats(const bool debug_print) {
+    if (debug_print) {
+        std::cout << "=============================== test_cache_stats()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+
+    const size_t kBytesToGigabytes = 1073741824;  // int(pow(2, 30))
+    auto cache = std::make_shared<ocg::Cache>();
+    cache->set_capacity_bytes(1 * kBytesToGigabytes);
+
+    // A new cache has no statistics.
+    auto stats = cache->stats();
+    if ((stats.hits != 0) || (stats.misses != 0) || (stats.count != 0)) {
+        std::cout << "ERROR: A new cache has non-zero statistics.\n";
+        return 1;
+    }
+    std::cout << "Cache (empty): \n"
+              << cache->data_debug_string() << '\n';
+
+    auto graph = ocg::Graph();
+    auto read1_node = graph.create_node(ocg::NodeType::kReadImage, "read1");
+    auto read2_node = graph.create_node(ocg::NodeType::kReadImage, "read2");
+    graph.set_node_attr_str(
+        read1_node, "file_path",
+        "./tests/data/openexr-images/TestImages/BrightRings.exr");
+    graph.set_node_attr_str(
+        read2_node, "file_path",
+        "./tests/data/openexr-images/TestImages/AllHalfValues.exr");
+
+    std::vector<int32_t> frames = {1};
+    graph.execute(read1_node, frames, cache);
+    graph.execute(read2_node, frames, cache);
+    graph.execute(read1_node, frames, cache);
+
+    stats = cache->stats();
+    std::cout << "Cache: hits=" << stats.hits
+              << " misses=" << stats.misses
+              << " inserts=" << stats.inserts
+              << " evictions=" << stats.evictions
+              << " used_bytes=" << stats.used_bytes
+              << " capacity_bytes=" << stats.capacity_bytes
+              << " count=" << stats.count
+              << " evicted_compute_seconds=" << stats.evicted_compute_seconds
+              << '\n';
+    if (stats.count != 2) {
+        std::cout << "ERROR: Expected 2 cached images.\n";
+        return 1;
+    }
+    if (stats.eviction_policy != cache->eviction_policy()) {
+        std::cout << "ERROR: Statistics eviction policy does not match.\n";
+        return 1;
+    }
+
+    size_t total_bytes = 0;
+    auto entries = cache->entries();
+    for (auto entry : entries) {
+        std::cout << "Cache Entry: key=" << entry.key
+                  << " size_bytes=" << entry.size_bytes
+                  << " width=" << entry.width
+                  << " height=" << entry.height
+                  << " num_channels=" << entry.num_channels
+                  << " last_access_order=" << entry.last_access_order
+                  << " pinned=" << entry.pinned << '\n';
+        total_bytes += entry.size_bytes;
+    }
+    if (total_bytes != stats.used_bytes) {
+        std::cout << "ERROR: Cache entry sizes do not match used bytes.\n";
+        return 1;
+    }
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test Cache Stats:");
+    }
+    return 0;
+}
@@ -0,0 +1,89 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Parse frame set specifications, then iterate, merge and chunk them.
+ */
+
+#include <iostream>
+#include <cassert>
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+int test_frameset(const bool debug_print) {
+    if (debug_print) {
+        std::cout << "=============================== test_frameset()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+
+    auto frame_set = ocg::FrameSet("1001-1100");
+    assert(frame_set.len() == 100);
+    assert(frame_set.first_frame() == 1001);
+    assert(frame_set.last_frame() == 1100);
+
+    auto step_set = ocg::FrameSet("1-100x2");
+    assert(step_set.len() == 50);
+    assert(step_set.contains(99));
+    assert(step_set.contains(100) == false);
+
+    auto list_set = ocg::FrameSet("1,5,10-20");
+    auto frames = list_set.frames();
+    assert(frames.size() == 13);
+    assert(frames[0] == 1);
+    assert(frames[1] == 5);
+    assert(frames[2] == 10);
+
+    auto negative_set = ocg::FrameSet("-10--5");
+    assert(negative_set.len() == 6);
+    assert(negative_set.first_frame() == -10);
+
+    // An invalid spec leaves the frame set unchanged.
+    assert(list_set.parse("1-x") == false);
+    assert(list_set.len() == 13);
+
+    list_set.merge(negative_set);
+    assert(list_set.len() == 19);
+    assert(list_set.first_frame() == -10);
+
+    auto chunks = frame_set.chunks(30);
+    assert(chunks.size() == 4);
+    assert(chunks[0].first_frame() == 1001);
+    assert(chunks[3].len() == 10);
+    assert(chunks[3].last_frame() == 1100);
+
+    if (debug_print) {
+        std::cout << "frame_set=" << frame_set.to_spec_string() << '\n';
+        std::cout << "list_set=" << list_set.to_spec_string() << '\n';
+        std::cout << list_set.data_debug_string() << '\n';
+    }
+
+    // Execute a graph using a frame set.
+    auto graph = ocg::Graph();
+    auto null_node = graph.create_node(ocg::NodeType::kNull, "null");
+    auto cache = std::make_shared<ocg::Cache>();
+    auto status = graph.execute(null_node, ocg::FrameSet("1-3"), cache);
+    assert(status == ocg::ExecuteStatus::kSuccess);
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test FrameSet:");
+    }
+
+    return 0;
+}
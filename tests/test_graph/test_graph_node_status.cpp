@@ -0,0 +1,92 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Check the node status message and error kind explain why a node
+ * has a warning.
+ */
+
+#include <iostream>
+#include <fstream>
+#include <cassert>
+#include <opencompgraph.h>
+#include "../generate_frame_range.h"
+
+namespace ocg = open_comp_graph;
+
+int test_graph_node_status(const bool debug_print,
+                           std::shared_ptr<ocg::Cache> cache) {
+    if (debug_print) {
+        std::cout << "=============================== test_graph_node_status()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+
+    auto frames = generate_frame_range(1001, 1001);
+    auto graph = ocg::Graph();
+
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    auto null_node = graph.create_node(ocg::NodeType::kNull, "null");
+    graph.set_node_attr_str(read_node, "file_path", "tests/data/does_not_exist.####.exr");
+    graph.connect(read_node, null_node, 0);
+
+    auto exec_status = graph.execute(null_node, frames, cache);
+    assert(exec_status == ocg::ExecuteStatus::kSuccess);
+
+    auto read_status = graph.node_status(read_node);
+    auto read_error_kind = graph.node_error_kind(read_node);
+    auto read_message = graph.node_status_message(read_node);
+    assert(read_status == ocg::NodeStatus::kWarning);
+    assert(read_error_kind == ocg::NodeErrorKind::kFileNotFound);
+    assert(read_message.find("does_not_exist.1001.exr") != std::string::npos);
+
+    auto null_status = graph.node_status(null_node);
+    auto null_message = graph.node_status_message(null_node);
+    assert(null_status == ocg::NodeStatus::kValid);
+    assert(null_message.empty());
+
+    // A file that exists but is not an image is an error, not an
+    // empty frame.
+    const char* bad_file_path = "./tests/data/out/test_graph_node_status_bad.exr";
+    {
+        std::ofstream bad_file(bad_file_path, std::ios::trunc);
+        bad_file << "not an image";
+    }
+    auto bad_read_node = graph.create_node(ocg::NodeType::kReadImage, "bad_read");
+    graph.set_node_attr_str(bad_read_node, "file_path", bad_file_path);
+    auto bad_exec_status = graph.execute(bad_read_node, frames, cache);
+    assert(bad_exec_status == ocg::ExecuteStatus::kError);
+
+    auto bad_read_status = graph.node_status(bad_read_node);
+    auto bad_read_error_kind = graph.node_error_kind(bad_read_node);
+    auto bad_read_message = graph.node_status_message(bad_read_node);
+    assert(bad_read_status == ocg::NodeStatus::kError);
+    assert(bad_read_error_kind == ocg::NodeErrorKind::kComputeFailure);
+    assert(bad_read_message.find("test_graph_node_status_bad.exr") != std::string::npos);
+
+    if (debug_print) {
+        std::cout << "read_node message: " << read_message << '\n';
+        std::cout << "bad_read_node message: " << bad_read_message << '\n';
+        std::cout << "Graph as string:\n"
+                  << graph.data_debug_string();
+
+        bench.stop();
+        bench.print("Test Graph Node Status:");
+    }
+
+    return 0;
+}
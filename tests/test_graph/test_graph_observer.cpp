@@ -0,0 +1,87 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ *
+ * Observe edits made to a graph.
+ */
+
+#include <iostream>
+#include <cassert>
+#include <memory>
+#include <string>
+#include <vector>
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+class RecordingObserver : public ocg::GraphObserver {
+public:
+    void notify(const ocg::GraphEvent &event) override {
+        m_kinds.push_back(event.kind);
+        m_attr_names.push_back(std::string(event.attr_name));
+    }
+
+    std::vector<ocg::GraphEventKind> m_kinds;
+    std::vector<std::string> m_attr_names;
+};
+
+int test_graph_observer(const bool debug_print) {
+    if (debug_print) {
+        std::cout << "=============================== test_graph_observer()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+
+    auto graph = ocg::Graph();
+    auto observer = std::make_shared<RecordingObserver>();
+    auto observer_id = graph.add_observer(observer);
+
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    auto null_node = graph.create_node(ocg::NodeType::kNull, "null");
+    graph.set_node_attr_str(read_node, "file_path", "tests/data/image.####.exr");
+    graph.set_node_attr_i32(read_node, "not_an_attr", 1);  // Not observed.
+    graph.connect(read_node, null_node, 0);
+    graph.disconnect_input(null_node, 0);
+    graph.delete_node(read_node);
+
+    if (debug_print) {
+        std::cout << "Event count: " << observer->m_kinds.size() << '\n';
+    }
+    assert(observer->m_kinds.size() == 6);
+    assert(observer->m_kinds[0] == ocg::GraphEventKind::kNodeAdded);
+    assert(observer->m_kinds[1] == ocg::GraphEventKind::kNodeAdded);
+    assert(observer->m_kinds[2] == ocg::GraphEventKind::kAttrChanged);
+    assert(observer->m_attr_names[2] == "file_path");
+    assert(observer->m_kinds[3] == ocg::GraphEventKind::kConnected);
+    assert(observer->m_kinds[4] == ocg::GraphEventKind::kDisconnected);
+    assert(observer->m_kinds[5] == ocg::GraphEventKind::kNodeRemoved);
+
+    // No more events are recorded once the observer is removed.
+    assert(graph.remove_observer(observer_id) == true);
+    assert(graph.remove_observer(observer_id) == false);
+    graph.set_node_attr_str(null_node, "file_path", "");
+    graph.connect(read_node, null_node, 0);
+    assert(observer->m_kinds.size() == 6);
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test Graph Observer:");
+    }
+
+    return 0;
+}
@@ -0,0 +1,140 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Execute a graph at full resolution and at proxy scales.
+ */
+
+#include <iostream>
+#include <vector>
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+static bool check_window(const char *name, ocg::BBox2Di window,
+                         int32_t min_x, int32_t min_y,
+                         int32_t max_x, int32_t max_y) {
+    if ((window.min_x != min_x) || (window.min_y != min_y)
+        || (window.max_x != max_x) || (window.max_y != max_y)) {
+        std::cout << "ERROR: " << name << " window is "
+                  << window.min_x << "," << window.min_y << " to "
+                  << window.max_x << "," << window.max_y
+                  << ", expected "
+                  << min_x << "," << min_y << " to "
+                  << max_x << "," << max_y << '\n';
+        return false;
+    }
+    return true;
+}
+
+int test_graph_proxy(const bool debug_print,
+                     std::shared_ptr<ocg::Cache> cache) {
+    if (debug_print) {
+        std::cout << "============================ test_graph_proxy()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+    std::vector<int32_t> frames = {1};
+
+    auto graph = ocg::Graph();
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    auto crop_node = graph.create_node(ocg::NodeType::kCropImage, "crop");
+    graph.set_node_attr_str(read_node, "file_path",
+                            "tests/data/oiio-images/tahoe-gps.jpg");
+    graph.set_node_attr_i32(crop_node, "window_min_x", 0);
+    graph.set_node_attr_i32(crop_node, "window_min_y", 0);
+    graph.set_node_attr_i32(crop_node, "window_max_x", 200);
+    graph.set_node_attr_i32(crop_node, "window_max_y", 100);
+    graph.set_node_attr_i32(crop_node, "reformat", 1);
+    graph.connect(read_node, crop_node, 0);
+
+    // Missing frames are black.
+    auto missing_node = graph.create_node(ocg::NodeType::kReadImage, "missing");
+    graph.set_node_attr_str(missing_node, "file_path",
+                            "tests/data/does_not_exist.####.exr");
+    graph.set_node_attr_i32(
+        missing_node, "missing_frame_mode",
+        static_cast<int32_t>(ocg::ReadImageMissingFrameMode::kBlack));
+
+    // Full resolution.
+    graph.execute(read_node, frames, cache);
+    auto full_display_window = graph.output_stream().display_window();
+    auto full_read_hash = graph.node_hash(read_node, 1);
+    graph.execute(crop_node, frames, cache);
+    auto stream_data = graph.output_stream();
+    auto ok = check_window("full crop", stream_data.display_window(),
+                           0, 0, 200, 100);
+    if (stream_data.proxy_scale() != ocg::ProxyScale::kFull) {
+        std::cout << "ERROR: Stream is not full resolution.\n";
+        ok = false;
+    }
+    graph.execute(missing_node, frames, cache);
+    auto full_missing_window = graph.output_stream().display_window();
+
+    // Half resolution, the pixel-space crop window is scaled to
+    // match.
+    graph.set_proxy_scale(ocg::ProxyScale::kHalf);
+    if (graph.node_hash(read_node, 1) == full_read_hash) {
+        std::cout << "ERROR: Proxy scale does not change the hash.\n";
+        ok = false;
+    }
+    graph.execute(read_node, frames, cache);
+    ok &= check_window("half read", graph.output_stream().display_window(),
+                       full_display_window.min_x / 2,
+                       full_display_window.min_y / 2,
+                       (full_display_window.max_x + 1) / 2,
+                       (full_display_window.max_y + 1) / 2);
+    graph.execute(crop_node, frames, cache);
+    stream_data = graph.output_stream();
+    ok &= check_window("half crop", stream_data.display_window(),
+                       0, 0, 100, 50);
+    if (stream_data.proxy_scale() != ocg::ProxyScale::kHalf) {
+        std::cout << "ERROR: Stream is not half resolution.\n";
+        ok = false;
+    }
+
+    // Black frames for missing files are also at the proxy scale.
+    graph.execute(missing_node, frames, cache);
+    stream_data = graph.output_stream();
+    ok &= check_window("half missing", stream_data.display_window(),
+                       0, 0,
+                       (full_missing_window.max_x + 1) / 2,
+                       (full_missing_window.max_y + 1) / 2);
+    if ((stream_data.pixel_width() != (full_missing_window.max_x + 1) / 2)
+        || (stream_data.proxy_scale() != ocg::ProxyScale::kHalf)) {
+        std::cout << "ERROR: Missing frame is not half resolution.\n";
+        ok = false;
+    }
+
+    // The lowest resolution of the graph and ReadImage node is used.
+    graph.set_node_attr_i32(read_node, "proxy_scale",
+                            static_cast<int32_t>(ocg::ProxyScale::kQuarter));
+    graph.execute(crop_node, frames, cache);
+    stream_data = graph.output_stream();
+    ok &= check_window("quarter crop", stream_data.display_window(),
+                       0, 0, 50, 25);
+    if (stream_data.proxy_scale() != ocg::ProxyScale::kQuarter) {
+        std::cout << "ERROR: Stream is not quarter resolution.\n";
+        ok = false;
+    }
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test Graph Proxy:");
+    }
+    return ok ? 0 : 1;
+}
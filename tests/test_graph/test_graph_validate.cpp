@@ -0,0 +1,91 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Validate a graph with known problems, without executing it.
+ */
+
+#include <iostream>
+#include <cassert>
+#include <opencompgraph.h>
+#include "../generate_frame_range.h"
+
+namespace ocg = open_comp_graph;
+
+int test_graph_validate(const bool debug_print) {
+    if (debug_print) {
+        std::cout << "=============================== test_graph_validate()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+
+    auto frames = generate_frame_range(1, 3);
+    auto graph = ocg::Graph();
+
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    auto merge_node = graph.create_node(ocg::NodeType::kMergeImage, "merge");
+    auto viewer_node = graph.create_node(ocg::NodeType::kViewer, "viewer");
+
+    graph.set_node_attr_str(read_node, "file_path", "tests/data/does_not_exist.####.exr");
+    graph.set_node_attr_i32(merge_node, "mode", 99);
+    graph.set_node_attr_i32(viewer_node, "bake_option", 1);  // 1 = kColorSpace
+    graph.set_node_attr_str(viewer_node, "bake_color_space", "not_a_color_space");
+
+    // Only input A is connected to the merge node.
+    graph.connect(read_node, merge_node, 0);
+    graph.connect(merge_node, viewer_node, 0);
+
+    auto issues = graph.validate(viewer_node, frames);
+    auto count_file_not_found = 0;
+    auto count_missing_input = 0;
+    auto count_invalid_enum = 0;
+    auto count_unknown_color_space = 0;
+    for (auto &issue : issues) {
+        if (debug_print) {
+            std::cout << "node_id=" << issue.node_id
+                      << " frame=" << issue.frame
+                      << " message=" << std::string(issue.message) << '\n';
+        }
+        switch (issue.kind) {
+            case ocg::ValidationIssueKind::kFileNotFound:
+                count_file_not_found += 1;
+                break;
+            case ocg::ValidationIssueKind::kMissingInput:
+                count_missing_input += 1;
+                break;
+            case ocg::ValidationIssueKind::kInvalidEnumValue:
+                count_invalid_enum += 1;
+                break;
+            case ocg::ValidationIssueKind::kUnknownColorSpace:
+                count_unknown_color_space += 1;
+                break;
+            default:
+                break;
+        }
+    }
+    assert(count_file_not_found == 3);
+    assert(count_missing_input == 1);
+    assert(count_invalid_enum == 1);
+    assert(count_unknown_color_space == 1);
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test Graph Validate:");
+    }
+
+    return 0;
+}
@@ -0,0 +1,78 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Find the image sequences in a directory.
+ */
+
+#include <iostream>
+#include <string>
+#include <vector>
+#include <cassert>
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+int test_imagesequence(const bool debug_print) {
+    if (debug_print) {
+        std::cout << "=============================== test_imagesequence()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+
+    auto sequences = ocg::find_image_sequences(
+        "tests/data/ocg-testdata/images/color_bars/3840x2160_png_compress3");
+    assert(sequences.size() == 1);
+
+    auto sequence = sequences[0];
+    if (debug_print) {
+        std::cout << "sequence: " << std::string(sequence.file_path)
+                  << " first_frame=" << sequence.first_frame
+                  << " last_frame=" << sequence.last_frame
+                  << " padding=" << sequence.padding
+                  << " frame_count=" << sequence.frame_count
+                  << " missing_frames_spec=" << std::string(sequence.missing_frames_spec)
+                  << '\n';
+    }
+    assert(sequence.first_frame == 951);
+    assert(sequence.last_frame == 1001);
+    assert(sequence.padding == 4);
+    assert(sequence.frame_count == 51);
+    assert(sequence.missing_frames_spec.empty());
+
+    // The sequence file path can be read by a ReadImage node.
+    auto graph = ocg::Graph();
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    graph.set_node_attr_str(read_node, "file_path", sequence.file_path);
+    graph.set_node_attr_i32(read_node, "use_frame_range", 1);
+    graph.set_node_attr_i32(read_node, "first_frame", sequence.first_frame);
+    graph.set_node_attr_i32(read_node, "last_frame", sequence.last_frame);
+    auto frames = std::vector<double>{951.0, 1001.0};
+    auto issues = graph.validate(read_node, frames);
+    assert(issues.size() == 0);
+
+    // Directories that do not exist have no sequences.
+    auto no_sequences = ocg::find_image_sequences("tests/data/does_not_exist");
+    assert(no_sequences.size() == 0);
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test Image Sequence:");
+    }
+
+    return 0;
+}
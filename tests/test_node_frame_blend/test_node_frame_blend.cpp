@@ -0,0 +1,140 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ */
+
+#include <iostream>
+#include <cassert>
+#include <opencompgraph.h>
+#include "../generate_frame_range.h"
+
+namespace ocg = open_comp_graph;
+
+int test_node_frame_blend(const bool debug_print,
+                          std::shared_ptr<ocg::Cache> cache) {
+    if (debug_print) {
+        std::cout << "========================== test_node_frame_blend()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+
+    auto frames = generate_frame_range(2, 4);
+    auto graph = ocg::Graph();
+
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    auto blend_node = graph.create_node(ocg::NodeType::kFrameBlend, "frame_blend");
+    auto ghost_node = graph.create_node(ocg::NodeType::kFrameBlend, "frame_ghost");
+    auto write_blend_node = graph.create_node(ocg::NodeType::kWriteImage, "write_blend");
+    auto write_ghost_node = graph.create_node(ocg::NodeType::kWriteImage, "write_ghost");
+
+    graph.set_node_attr_str(
+        read_node, "file_path",
+        "tests/data/openexr-images/Beachball/multipart.####.exr");
+
+    // Motion blur; 5 samples within a shutter of one frame.
+    graph.set_node_attr_i32(blend_node, "samples", 5);
+    graph.set_node_attr_f32(blend_node, "shutter_open", -0.5f);
+    graph.set_node_attr_f32(blend_node, "shutter_close", 0.5f);
+    graph.set_node_attr_str(
+        write_blend_node, "file_path",
+        "./tests/data/out/test_node_frame_blend_blur_out.####.exr");
+    graph.connect(read_node, blend_node, 0);
+    graph.connect(blend_node, write_blend_node, 0);
+
+    // Ghosting; average the previous, current and next frames.
+    graph.set_node_attr_i32(ghost_node, "samples", 3);
+    graph.set_node_attr_f32(ghost_node, "shutter_open", -1.0f);
+    graph.set_node_attr_f32(ghost_node, "shutter_close", 1.0f);
+    graph.set_node_attr_str(
+        write_ghost_node, "file_path",
+        "./tests/data/out/test_node_frame_blend_ghost_out.####.exr");
+    graph.connect(read_node, ghost_node, 0);
+    graph.connect(ghost_node, write_ghost_node, 0);
+
+    if (debug_print) {
+        std::cout << "Graph as string (before):\n"
+                  << graph.data_debug_string();
+    }
+    auto status_blend = graph.execute(write_blend_node, frames, cache);
+    auto status_ghost = graph.execute(write_ghost_node, frames, cache);
+    assert(status_blend == ocg::ExecuteStatus::kSuccess);
+    assert(status_ghost == ocg::ExecuteStatus::kSuccess);
+    assert(graph.node_status(blend_node) == ocg::NodeStatus::kValid);
+    assert(graph.node_status(ghost_node) == ocg::NodeStatus::kValid);
+
+    // A single sample in the middle of the shutter is the input at
+    // the same frame, which must still be computed.
+    auto single_node = graph.create_node(ocg::NodeType::kFrameBlend, "frame_single");
+    graph.set_node_attr_i32(single_node, "samples", 1);
+    graph.connect(read_node, single_node, 0);
+    std::vector<int32_t> single_frames = {2};
+    auto status_read = graph.execute(read_node, single_frames, cache);
+    assert(status_read == ocg::ExecuteStatus::kSuccess);
+    auto read_display_window = graph.output_stream().display_window();
+    auto read_pixel_width = graph.output_stream().pixel_width();
+    auto status_single = graph.execute(single_node, single_frames, cache);
+    assert(status_single == ocg::ExecuteStatus::kSuccess);
+    assert(graph.node_status(single_node) == ocg::NodeStatus::kValid);
+    auto single_stream = graph.output_stream();
+    assert(single_stream.display_window().max_x == read_display_window.max_x);
+    assert(single_stream.display_window().max_y == read_display_window.max_y);
+    assert(single_stream.pixel_width() == read_pixel_width);
+
+    // Executing again uses the cached blend.
+    status_single = graph.execute(single_node, single_frames, cache);
+    assert(status_single == ocg::ExecuteStatus::kSuccess);
+    assert(graph.output_stream().pixel_width() == read_pixel_width);
+
+    // Executing a blend of many samples again is a cache hit, and
+    // the input is not evaluated at every sample again.
+    const size_t kBytesToGigabytes = 1073741824;  // int(pow(2, 30))
+    auto blur_cache = std::make_shared<ocg::Cache>();
+    blur_cache->set_capacity_bytes(1 * kBytesToGigabytes);
+    std::vector<int32_t> blur_frames = {3};
+    auto status_blur = graph.execute(blend_node, blur_frames, blur_cache);
+    assert(status_blur == ocg::ExecuteStatus::kSuccess);
+    auto blur_cached_frames = graph.cached_frames(blend_node, blur_frames, blur_cache);
+    assert(blur_cached_frames.size() == 1);
+    assert(blur_cached_frames[0].cached);
+    auto stats_first = blur_cache->stats();
+    status_blur = graph.execute(blend_node, blur_frames, blur_cache);
+    assert(status_blur == ocg::ExecuteStatus::kSuccess);
+    assert(graph.node_status(blend_node) == ocg::NodeStatus::kValid);
+    auto stats_second = blur_cache->stats();
+    if (debug_print) {
+        std::cout << "Blur cache (first): hits=" << stats_first.hits
+                  << " misses=" << stats_first.misses
+                  << " inserts=" << stats_first.inserts << '\n'
+                  << "Blur cache (second): hits=" << stats_second.hits
+                  << " misses=" << stats_second.misses
+                  << " inserts=" << stats_second.inserts << '\n';
+    }
+    assert(stats_second.misses == stats_first.misses);
+    assert(stats_second.inserts == stats_first.inserts);
+    assert((stats_second.hits - stats_first.hits) < 5);
+
+    if (debug_print) {
+        std::cout << "Graph as string (after):\n"
+                  << graph.data_debug_string();
+
+        bench.stop();
+        bench.print("Test Node Frame Blend:");
+    }
+
+    return 0;
+}
@@ -0,0 +1,109 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Read an image with a color space override, and premultiply or
+ * unpremultiply the alpha.
+ */
+
+#include <iostream>
+#include <string>
+#include <vector>
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+int test_node_imageio_alpha(const bool debug_print,
+                            std::shared_ptr<ocg::Cache> cache) {
+    if (debug_print) {
+        std::cout << "=============== test_node_imageio_alpha()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+    std::vector<int32_t> frames = {1};
+
+    auto graph = ocg::Graph();
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    graph.set_node_attr_str(
+        read_node, "file_path",
+        "tests/data/oiio-images/oiio-logo-with-alpha.png");
+    auto default_hash = graph.node_hash(read_node, 1);
+
+    // Override the color space of the file.
+    graph.set_node_attr_str(read_node, "color_space", "Linear");
+    graph.execute(read_node, frames, cache);
+    auto stream_data = graph.output_stream();
+    if (std::string(stream_data.clone_image_spec().color_space) != "Linear") {
+        std::cout << "ERROR: Color space is not overridden.\n";
+        return 1;
+    }
+    if (graph.node_hash(read_node, 1) == default_hash) {
+        std::cout << "ERROR: Color space does not change the hash.\n";
+        return 1;
+    }
+    graph.set_node_attr_str(read_node, "color_space", "");
+    if (graph.node_hash(read_node, 1) != default_hash) {
+        std::cout << "ERROR: Default attributes change the hash.\n";
+        return 1;
+    }
+
+    // The pixels as stored in the file.
+    graph.execute(read_node, frames, cache);
+    stream_data = graph.output_stream();
+    auto file_unassociated_alpha = stream_data.clone_image_spec().unassociated_alpha;
+    auto file_buffer = stream_data.pixel_buffer();
+    std::vector<uint8_t> file_pixels(file_buffer.begin(), file_buffer.end());
+
+    // The pixels only change when the alpha of the file is not
+    // already in the requested state.
+    auto modes = std::vector<ocg::ReadImageAlphaMode>{
+        ocg::ReadImageAlphaMode::kUnpremultiply,
+        ocg::ReadImageAlphaMode::kPremultiply};
+    for (auto alpha_mode : modes) {
+        auto unassociated_alpha =
+            alpha_mode == ocg::ReadImageAlphaMode::kUnpremultiply;
+        graph.set_node_attr_i32(
+            read_node, "alpha_mode", static_cast<int32_t>(alpha_mode));
+        graph.execute(read_node, frames, cache);
+        stream_data = graph.output_stream();
+        if (stream_data.clone_image_spec().unassociated_alpha != unassociated_alpha) {
+            std::cout << "ERROR: Alpha mode is not applied: "
+                      << static_cast<int32_t>(alpha_mode) << '\n';
+            return 1;
+        }
+        auto buffer = stream_data.pixel_buffer();
+        std::vector<uint8_t> pixels(buffer.begin(), buffer.end());
+        auto unchanged = pixels == file_pixels;
+        auto expect_unchanged = unassociated_alpha == file_unassociated_alpha;
+        if (unchanged != expect_unchanged) {
+            std::cout << "ERROR: Alpha mode changed pixels incorrectly: "
+                      << static_cast<int32_t>(alpha_mode) << '\n';
+            return 1;
+        }
+    }
+
+    if (stream_data.pixel_num_channels() != 4) {
+        std::cout << "ERROR: Image does not have an alpha channel.\n";
+        return 1;
+    }
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test ImageIO Alpha:");
+    }
+    return 0;
+}
@@ -0,0 +1,173 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Read and write OpenEXR images with the pure-Rust image IO backend,
+ * and compare the results with the OpenImageIO backend.
+ */
+
+#include <iostream>
+#include <string>
+#include <vector>
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+namespace {
+
+struct ReadResult {
+    ocg::BBox2Di display_window;
+    ocg::BBox2Di data_window;
+    ocg::DataType data_type;
+    int32_t num_channels;
+    std::vector<uint8_t> pixels;
+};
+
+bool read_exr(ocg::ImageIOBackend backend,
+              const std::string &file_path,
+              std::shared_ptr<ocg::Cache> &cache,
+              ReadResult &result) {
+    ocg::set_image_io_backend(backend);
+    auto graph = ocg::Graph();
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    graph.set_node_attr_str(read_node, "file_path", file_path);
+    std::vector<int32_t> frames = {1};
+    graph.execute(read_node, frames, cache);
+    if (graph.node_status(read_node) != ocg::NodeStatus::kValid) {
+        std::cout << "ERROR: Reading " << file_path << " failed: "
+                  << graph.node_status_message(read_node) << '\n';
+        return false;
+    }
+
+    auto stream_data = graph.output_stream();
+    result.display_window = stream_data.display_window();
+    result.data_window = stream_data.data_window();
+    result.data_type = stream_data.pixel_data_type();
+    result.num_channels = stream_data.pixel_num_channels();
+    auto pixel_buffer = stream_data.pixel_buffer();
+    result.pixels.assign(pixel_buffer.begin(), pixel_buffer.end());
+    return true;
+}
+
+bool same_window(const ocg::BBox2Di &a, const ocg::BBox2Di &b) {
+    return (a.min_x == b.min_x) && (a.min_y == b.min_y)
+        && (a.max_x == b.max_x) && (a.max_y == b.max_y);
+}
+
+} // namespace
+
+int test_node_imageio_exr_rust(const bool debug_print,
+                               std::shared_ptr<ocg::Cache> cache) {
+    if (debug_print) {
+        std::cout << "====================== test_node_imageio_exr_rust()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+    auto old_backend = ocg::image_io_backend();
+
+    // The Rust backend must give the same pixels and windows as
+    // OpenImageIO.
+    std::vector<std::string> file_paths = {
+        "./tests/data/openexr-images/ScanLines/Desk.exr",
+        "./tests/data/openexr-images/DisplayWindow/t08.exr",
+    };
+    for (auto file_path : file_paths) {
+        ReadResult oiio_result;
+        ReadResult rust_result;
+        if (!read_exr(ocg::ImageIOBackend::kOpenImageIO, file_path, cache, oiio_result)
+            || !read_exr(ocg::ImageIOBackend::kRust, file_path, cache, rust_result)) {
+            ocg::set_image_io_backend(old_backend);
+            return 1;
+        }
+        if (!same_window(oiio_result.display_window, rust_result.display_window)
+            || !same_window(oiio_result.data_window, rust_result.data_window)) {
+            std::cout << "ERROR: Image windows differ: " << file_path << '\n';
+            ocg::set_image_io_backend(old_backend);
+            return 1;
+        }
+        if ((oiio_result.data_type != rust_result.data_type)
+            || (oiio_result.num_channels != rust_result.num_channels)
+            || (oiio_result.pixels != rust_result.pixels)) {
+            std::cout << "ERROR: Image pixels differ: " << file_path << '\n';
+            ocg::set_image_io_backend(old_backend);
+            return 1;
+        }
+    }
+
+    // Write with each EXR compression mode.
+    ocg::set_image_io_backend(ocg::ImageIOBackend::kRust);
+    std::vector<int32_t> frames = {1};
+    std::vector<ocg::ExrCompression> compressions = {
+        ocg::ExrCompression::kNone,
+        ocg::ExrCompression::kRle,
+        ocg::ExrCompression::kZip,
+        ocg::ExrCompression::kZipScanline,
+        ocg::ExrCompression::kPiz,
+        ocg::ExrCompression::kPxr24,
+        ocg::ExrCompression::kB44,
+        ocg::ExrCompression::kB44a,
+        ocg::ExrCompression::kDwaa,
+        ocg::ExrCompression::kDwab,
+    };
+    auto graph = ocg::Graph();
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    graph.set_node_attr_str(
+        read_node, "file_path",
+        "./tests/data/openexr-images/DisplayWindow/t08.exr");
+    for (auto compression : compressions) {
+        auto compression_num = static_cast<int32_t>(compression);
+        auto file_path = std::string("./tests/data/out/test_node_imageio_exr_rust_out_")
+            + std::to_string(compression_num) + ".exr";
+        auto write_node = graph.create_node(ocg::NodeType::kWriteImage, "write");
+        graph.set_node_attr_str(write_node, "file_path", file_path);
+        graph.set_node_attr_i32(write_node, "exr_compression", compression_num);
+        graph.connect(read_node, write_node, 0);
+        graph.execute(write_node, frames, cache);
+        if (graph.node_status(write_node) != ocg::NodeStatus::kValid) {
+            std::cout << "ERROR: Writing " << file_path << " failed: "
+                      << graph.node_status_message(write_node) << '\n';
+            ocg::set_image_io_backend(old_backend);
+            return 1;
+        }
+        graph.delete_node(write_node);
+
+        // The written windows must match the source image.
+        ReadResult source_result;
+        ReadResult written_result;
+        if (!read_exr(ocg::ImageIOBackend::kRust,
+                      "./tests/data/openexr-images/DisplayWindow/t08.exr",
+                      cache, source_result)
+            || !read_exr(ocg::ImageIOBackend::kRust, file_path, cache, written_result)) {
+            ocg::set_image_io_backend(old_backend);
+            return 1;
+        }
+        if (!same_window(source_result.display_window, written_result.display_window)
+            || !same_window(source_result.data_window, written_result.data_window)) {
+            std::cout << "ERROR: Written image windows differ: " << file_path << '\n';
+            ocg::set_image_io_backend(old_backend);
+            return 1;
+        }
+    }
+
+    ocg::set_image_io_backend(old_backend);
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test ImageIO EXR Rust:");
+    }
+    return 0;
+}
@@ -0,0 +1,115 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Read an image sequence with a frame range, and missing frames.
+ */
+
+#include <iostream>
+#include <vector>
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+int test_node_imageio_frame_range(const bool debug_print,
+                                  std::shared_ptr<ocg::Cache> cache) {
+    if (debug_print) {
+        std::cout << "=============== test_node_imageio_frame_range()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+
+    auto graph = ocg::Graph();
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    graph.set_node_attr_str(
+        read_node, "file_path",
+        "tests/data/ocg-testdata/images/color_bars/3840x2160_png_compress3/color_bars.####.png");
+
+    // The sequence has frames 951 to 1001.
+    graph.set_node_attr_i32(read_node, "use_frame_range", 1);
+    graph.set_node_attr_i32(read_node, "first_frame", 951);
+    graph.set_node_attr_i32(read_node, "last_frame", 1001);
+
+    // Held frames read the same file, so have the same hash.
+    graph.set_node_attr_i32(
+        read_node, "after_mode",
+        static_cast<int32_t>(ocg::ReadImageFrameRangeMode::kHold));
+    if (graph.node_hash(read_node, 1010) != graph.node_hash(read_node, 1001)) {
+        std::cout << "ERROR: Frame 1010 is not held at frame 1001.\n";
+        return 1;
+    }
+
+    graph.set_node_attr_i32(
+        read_node, "after_mode",
+        static_cast<int32_t>(ocg::ReadImageFrameRangeMode::kLoop));
+    if (graph.node_hash(read_node, 1002) != graph.node_hash(read_node, 951)) {
+        std::cout << "ERROR: Frame 1002 does not loop to frame 951.\n";
+        return 1;
+    }
+
+    graph.set_node_attr_i32(
+        read_node, "after_mode",
+        static_cast<int32_t>(ocg::ReadImageFrameRangeMode::kBounce));
+    if (graph.node_hash(read_node, 1002) != graph.node_hash(read_node, 1000)) {
+        std::cout << "ERROR: Frame 1002 does not bounce to frame 1000.\n";
+        return 1;
+    }
+
+    graph.set_node_attr_i32(
+        read_node, "before_mode",
+        static_cast<int32_t>(ocg::ReadImageFrameRangeMode::kBlack));
+    std::vector<int32_t> black_frames = {900};
+    graph.execute(read_node, black_frames, cache);
+    if (graph.node_status(read_node) != ocg::NodeStatus::kValid) {
+        std::cout << "ERROR: Black frame is not valid: "
+                  << graph.node_status_message(read_node) << '\n';
+        return 1;
+    }
+
+    // Missing frames, without a frame range.
+    graph.set_node_attr_i32(read_node, "use_frame_range", 0);
+    std::vector<int32_t> missing_frames = {1050};
+
+    graph.set_node_attr_i32(
+        read_node, "missing_frame_mode",
+        static_cast<int32_t>(ocg::ReadImageMissingFrameMode::kError));
+    graph.execute(read_node, missing_frames, cache);
+    if (graph.node_status(read_node) != ocg::NodeStatus::kError) {
+        std::cout << "ERROR: Missing frame is not an error.\n";
+        return 1;
+    }
+
+    graph.set_node_attr_i32(
+        read_node, "missing_frame_mode",
+        static_cast<int32_t>(ocg::ReadImageMissingFrameMode::kNearest));
+    if (graph.node_hash(read_node, 1050) != graph.node_hash(read_node, 1001)) {
+        std::cout << "ERROR: Frame 1050 does not use the nearest frame 1001.\n";
+        return 1;
+    }
+    graph.execute(read_node, missing_frames, cache);
+    if (graph.node_status(read_node) != ocg::NodeStatus::kValid) {
+        std::cout << "ERROR: Nearest frame is not valid: "
+                  << graph.node_status_message(read_node) << '\n';
+        return 1;
+    }
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test ImageIO Frame Range:");
+    }
+    return 0;
+}
@@ -0,0 +1,185 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Read image layers (extra named channels) and pass them through
+ * to an EXR file.
+ */
+
+#include <algorithm>
+#include <iostream>
+#include <string>
+#include <vector>
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+int test_node_imageio_layers(const bool debug_print,
+                             std::shared_ptr<ocg::Cache> cache) {
+    if (debug_print) {
+        std::cout << "====================== test_node_imageio_layers()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+    std::vector<int32_t> frames = {1};
+
+    auto graph = ocg::Graph();
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    auto grade_node = graph.create_node(ocg::NodeType::kGrade, "grade");
+    auto write_node = graph.create_node(ocg::NodeType::kWriteImage, "write");
+    auto read_out_node = graph.create_node(ocg::NodeType::kReadImage, "read_out");
+    graph.set_node_attr_str(
+        read_node, "file_path",
+        "./tests/data/openexr-images/Beachball/singlepart.0001.exr");
+    graph.set_node_attr_str(read_node, "layers", "*");
+    graph.set_node_attr_f32(grade_node, "multiply_r", 0.5f);
+    graph.set_node_attr_str(
+        write_node, "file_path",
+        "./tests/data/out/test_node_imageio_layers_out.exr");
+    graph.set_node_attr_str(
+        read_out_node, "file_path",
+        "./tests/data/out/test_node_imageio_layers_out.exr");
+    graph.set_node_attr_str(read_out_node, "layers", "*");
+    graph.connect(read_node, grade_node, 0);
+    graph.connect(grade_node, write_node, 0);
+
+    // Layers are passed through the grade node, only the RGBA
+    // channels are changed.
+    graph.execute(write_node, frames, cache);
+    auto stream_data = graph.output_stream();
+    auto layers_len = stream_data.layers_len();
+    if (debug_print) {
+        for (size_t i = 0; i < layers_len; ++i) {
+            std::cout << "layer: " << std::string(stream_data.layer_name(i));
+            for (auto &channel_name : stream_data.layer_channel_names(i)) {
+                std::cout << " " << std::string(channel_name);
+            }
+            std::cout << '\n';
+        }
+    }
+    if (layers_len == 0) {
+        std::cout << "ERROR: No layers were read.\n";
+        return 1;
+    }
+    if (stream_data.pixel_num_channels() > 4) {
+        std::cout << "ERROR: Layer channels were read as RGBA channels.\n";
+        return 1;
+    }
+    std::vector<std::string> layer_names;
+    for (size_t i = 0; i < layers_len; ++i) {
+        layer_names.push_back(std::string(stream_data.layer_name(i)));
+        auto channels_len = stream_data.layer_channel_names(i).size();
+        auto data_window = stream_data.layer_data_window(i);
+        auto pixel_count = static_cast<size_t>(
+            (data_window.max_x - data_window.min_x)
+            * (data_window.max_y - data_window.min_y));
+        auto buffer_len = stream_data.layer_pixel_buffer(i).size();
+        if (buffer_len != (pixel_count * channels_len * sizeof(float))) {
+            std::cout << "ERROR: Layer pixels do not match the data window: "
+                      << layer_names[i] << '\n';
+            return 1;
+        }
+    }
+
+    // The written file has the same layers.
+    graph.execute(read_out_node, frames, cache);
+    auto stream_data_out = graph.output_stream();
+    if (stream_data_out.layers_len() != layers_len) {
+        std::cout << "ERROR: Written image has " << stream_data_out.layers_len()
+                  << " layers, expected " << layers_len << ".\n";
+        return 1;
+    }
+    for (size_t i = 0; i < layers_len; ++i) {
+        auto name = std::string(stream_data_out.layer_name(i));
+        auto found = std::find(layer_names.begin(), layer_names.end(), name);
+        if (found == layer_names.end()) {
+            std::cout << "ERROR: Unexpected layer: " << name << '\n';
+            return 1;
+        }
+    }
+
+    // Only the selected layers are read.
+    graph.set_node_attr_str(read_out_node, "layers", layer_names[0]);
+    graph.execute(read_out_node, frames, cache);
+    auto stream_data_one = graph.output_stream();
+    if ((stream_data_one.layers_len() != 1)
+        || (std::string(stream_data_one.layer_name(0)) != layer_names[0])) {
+        std::cout << "ERROR: Layer was not selected: " << layer_names[0] << '\n';
+        return 1;
+    }
+
+    // Layers are resampled and transformed with the image, so the
+    // written layers match the resolution of the image.
+    auto resample_node = graph.create_node(ocg::NodeType::kResampleImage, "resample");
+    auto transform_node = graph.create_node(ocg::NodeType::kTransform, "transform");
+    auto write_resample_node = graph.create_node(ocg::NodeType::kWriteImage, "write_resample");
+    auto read_resample_node = graph.create_node(ocg::NodeType::kReadImage, "read_resample");
+    graph.set_node_attr_i32(resample_node, "factor", -1);
+    graph.set_node_attr_f32(transform_node, "translate_x", 10.0f);
+    graph.set_node_attr_str(
+        write_resample_node, "file_path",
+        "./tests/data/out/test_node_imageio_layers_resample_out.exr");
+    graph.set_node_attr_str(
+        read_resample_node, "file_path",
+        "./tests/data/out/test_node_imageio_layers_resample_out.exr");
+    graph.set_node_attr_str(read_resample_node, "layers", "*");
+    graph.connect(read_node, resample_node, 0);
+    graph.connect(resample_node, transform_node, 0);
+    graph.connect(transform_node, write_resample_node, 0);
+
+    graph.execute(write_resample_node, frames, cache);
+    auto stream_data_resample = graph.output_stream();
+    auto resample_data_window = stream_data_resample.data_window();
+    for (size_t i = 0; i < stream_data_resample.layers_len(); ++i) {
+        auto data_window = stream_data_resample.layer_data_window(i);
+        if ((data_window.min_x != resample_data_window.min_x)
+            || (data_window.min_y != resample_data_window.min_y)
+            || (data_window.max_x != resample_data_window.max_x)
+            || (data_window.max_y != resample_data_window.max_y)) {
+            std::cout << "ERROR: Layer was not resampled: "
+                      << std::string(stream_data_resample.layer_name(i)) << '\n';
+            return 1;
+        }
+    }
+
+    graph.execute(read_resample_node, frames, cache);
+    auto stream_data_resample_out = graph.output_stream();
+    auto resample_out_data_window = stream_data_resample_out.data_window();
+    if (stream_data_resample_out.layers_len() != layers_len) {
+        std::cout << "ERROR: Resampled image has "
+                  << stream_data_resample_out.layers_len()
+                  << " layers, expected " << layers_len << ".\n";
+        return 1;
+    }
+    for (size_t i = 0; i < layers_len; ++i) {
+        auto data_window = stream_data_resample_out.layer_data_window(i);
+        if ((data_window.min_x != resample_out_data_window.min_x)
+            || (data_window.min_y != resample_out_data_window.min_y)
+            || (data_window.max_x != resample_out_data_window.max_x)
+            || (data_window.max_y != resample_out_data_window.max_y)) {
+            std::cout << "ERROR: Written layer does not match the image: "
+                      << std::string(stream_data_resample_out.layer_name(i)) << '\n';
+            return 1;
+        }
+    }
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test ImageIO Layers:");
+    }
+    return 0;
+}
@@ -0,0 +1,87 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Read an image with and without applying the image orientation.
+ */
+
+#include <iostream>
+#include <utility>  // swap
+#include <vector>
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+int test_node_imageio_orientation(const bool debug_print,
+                                  std::shared_ptr<ocg::Cache> cache) {
+    if (debug_print) {
+        std::cout << "================= test_node_imageio_orientation()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+    std::vector<int32_t> frames = {1};
+
+    auto graph = ocg::Graph();
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    graph.set_node_attr_str(
+        read_node, "file_path",
+        "./tests/data/oiio-images/tahoe-gps.jpg");
+
+    // The pixels as they are stored in the file.
+    graph.set_node_attr_i32(read_node, "apply_orientation", 0);
+    graph.execute(read_node, frames, cache);
+    auto stream_data_file = graph.output_stream();
+    auto file_orientation = stream_data_file.clone_image_spec().orientation;
+    auto file_display_window = stream_data_file.display_window();
+    if (debug_print) {
+        std::cout << "file orientation: "
+                  << static_cast<int32_t>(file_orientation) << '\n';
+    }
+
+    // The pixels in the Normal orientation.
+    graph.set_node_attr_i32(read_node, "apply_orientation", 1);
+    graph.execute(read_node, frames, cache);
+    auto stream_data = graph.output_stream();
+    if (stream_data.clone_image_spec().orientation != ocg::ImageOrientation::kNormal) {
+        std::cout << "ERROR: Image orientation was not applied.\n";
+        return 1;
+    }
+
+    auto swaps_axes =
+        (file_orientation == ocg::ImageOrientation::kTransposed)
+        || (file_orientation == ocg::ImageOrientation::kRotated90Clockwise)
+        || (file_orientation == ocg::ImageOrientation::kTransverse)
+        || (file_orientation == ocg::ImageOrientation::kRotated90CounterClockwise);
+    auto display_window = stream_data.display_window();
+    auto width = display_window.max_x - display_window.min_x;
+    auto height = display_window.max_y - display_window.min_y;
+    auto file_width = file_display_window.max_x - file_display_window.min_x;
+    auto file_height = file_display_window.max_y - file_display_window.min_y;
+    if (swaps_axes) {
+        std::swap(file_width, file_height);
+    }
+    if ((width != file_width) || (height != file_height)) {
+        std::cout << "ERROR: Display window does not match the orientation.\n";
+        return 1;
+    }
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test ImageIO Orientation:");
+    }
+    return 0;
+}
@@ -0,0 +1,94 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Read and write images with the pure-Rust image IO backend.
+ */
+
+#include <iostream>
+#include <vector>
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+int test_node_imageio_rust(const bool debug_print,
+                           std::shared_ptr<ocg::Cache> cache) {
+    if (debug_print) {
+        std::cout << "====================== test_node_imageio_rust()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+    std::vector<int32_t> frames = {1};
+
+    auto old_backend = ocg::image_io_backend();
+    ocg::set_image_io_backend(ocg::ImageIOBackend::kRust);
+
+    auto graph = ocg::Graph();
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    auto write_png_node = graph.create_node(ocg::NodeType::kWriteImage, "write_png");
+    auto write_tiff_node = graph.create_node(ocg::NodeType::kWriteImage, "write_tiff");
+    auto read_png_node = graph.create_node(ocg::NodeType::kReadImage, "read_png");
+
+    graph.set_node_attr_str(
+        read_node, "file_path",
+        "./tests/data/ocg-testdata/images/checker/checker_8bit_rgba_8x8.png");
+    graph.set_node_attr_str(
+        write_png_node, "file_path",
+        "./tests/data/out/test_node_imageio_rust_out.png");
+    graph.set_node_attr_str(
+        write_tiff_node, "file_path",
+        "./tests/data/out/test_node_imageio_rust_out.tif");
+    graph.set_node_attr_str(
+        read_png_node, "file_path",
+        "./tests/data/out/test_node_imageio_rust_out.png");
+    graph.connect(read_node, write_png_node, 0);
+    graph.connect(read_node, write_tiff_node, 0);
+
+    graph.execute(write_png_node, frames, cache);
+    if (graph.node_status(write_png_node) != ocg::NodeStatus::kValid) {
+        std::cout << "ERROR: Writing PNG failed: "
+                  << graph.node_status_message(write_png_node) << '\n';
+        ocg::set_image_io_backend(old_backend);
+        return 1;
+    }
+    graph.execute(write_tiff_node, frames, cache);
+    if (graph.node_status(write_tiff_node) != ocg::NodeStatus::kValid) {
+        std::cout << "ERROR: Writing TIFF failed: "
+                  << graph.node_status_message(write_tiff_node) << '\n';
+        ocg::set_image_io_backend(old_backend);
+        return 1;
+    }
+
+    // Read the written image back.
+    graph.execute(read_png_node, frames, cache);
+    auto stream_data = graph.output_stream();
+    if ((stream_data.pixel_width() != 8) || (stream_data.pixel_height() != 8)) {
+        std::cout << "ERROR: Written image has the wrong size: "
+                  << stream_data.pixel_width() << "x"
+                  << stream_data.pixel_height() << '\n';
+        ocg::set_image_io_backend(old_backend);
+        return 1;
+    }
+
+    ocg::set_image_io_backend(old_backend);
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test ImageIO Rust:");
+    }
+    return 0;
+}
@@ -0,0 +1,131 @@
+/*
+ * Copyright (C) 2020, 2021 David Cattermole.
+ *
+ * This file is part of OpenCompGraph.
+ *
+ * OpenCompGraph is free software: you can redistribute it and/or modify it
+ * under the terms of the GNU Lesser General Public License as
+ * published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * OpenCompGraph is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with OpenCompGraph.  If not, see <https://www.gnu.org/licenses/>.
+ * ====================================================================
+ *
+ * Edit image metadata and pass it through to an EXR file.
+ */
+
+#include <iostream>
+#include <string>
+#include <vector>
+#include <opencompgraph.h>
+
+namespace ocg = open_comp_graph;
+
+static const ocg::internal::ImageMetadata *find_metadata(
+        const ocg::internal::ImageSpec &spec,
+        const std::string &name) {
+    for (auto &item : spec.metadata) {
+        if (std::string(item.name) == name) {
+            return &item;
+        }
+    }
+    return nullptr;
+}
+
+int test_node_metadata(const bool debug_print,
+                       std::shared_ptr<ocg::Cache> cache) {
+    if (debug_print) {
+        std::cout << "============================= test_node_metadata()" << '\n';
+    }
+    auto bench = ocg::internal::BenchmarkTime();
+    std::vector<int32_t> frames = {1};
+
+    auto graph = ocg::Graph();
+    auto read_node = graph.create_node(ocg::NodeType::kReadImage, "read");
+    auto shot_node = graph.create_node(ocg::NodeType::kMetadata, "shot");
+    auto size_node = graph.create_node(ocg::NodeType::kMetadata, "size");
+    auto grade_node = graph.create_node(ocg::NodeType::kGrade, "grade");
+    auto write_node = graph.create_node(ocg::NodeType::kWriteImage, "write");
+    auto read_out_node = graph.create_node(ocg::NodeType::kReadImage, "read_out");
+    graph.set_node_attr_str(
+        read_node, "file_path",
+        "./tests/data/openexr-images/ScanLines/Desk.exr");
+    graph.set_node_attr_str(shot_node, "name", "studio:shot");
+    graph.set_node_attr_i32(
+        shot_node, "value_type",
+        static_cast<int32_t>(ocg::ImageMetadataType::kString));
+    graph.set_node_attr_str(shot_node, "value", "sh010");
+    graph.set_node_attr_str(size_node, "name", "studio:size");
+    graph.set_node_attr_i32(
+        size_node, "value_type",
+        static_cast<int32_t>(ocg::ImageMetadataType::kInt));
+    graph.set_node_attr_str(size_node, "value", "1920 1080");
+    graph.set_node_attr_f32(grade_node, "multiply_r", 0.5f);
+    graph.set_node_attr_str(
+        write_node, "file_path",
+        "./tests/data/out/test_node_metadata_out.exr");
+    graph.set_node_attr_str(
+        read_out_node, "file_path",
+        "./tests/data/out/test_node_metadata_out.exr");
+    graph.connect(read_node, shot_node, 0);
+    graph.connect(shot_node, size_node, 0);
+    graph.connect(size_node, grade_node, 0);
+    graph.connect(grade_node, write_node, 0);
+
+    // The metadata is passed through the grade node and written to
+    // the file.
+    graph.execute(write_node, frames, cache);
+    auto spec = graph.output_stream().clone_image_spec();
+    if (debug_print) {
+        for (auto &item : spec.metadata) {
+            std::cout << "metadata: " << std::string(item.name) << '\n';
+        }
+    }
+    graph.execute(read_out_node, frames, cache);
+    auto spec_out = graph.output_stream().clone_image_spec();
+    auto shot = find_metadata(spec_out, "studio:shot");
+    if ((shot == nullptr) || (std::string(shot->string_value) != "sh010")) {
+        std::cout << "ERROR: Metadata \"studio:shot\" was not written.\n";
+        return 1;
+    }
+    auto size = find_metadata(spec_out, "studio:size");
+    if ((size == nullptr)
+        || (size->int_values.size() != 2)
+        || (size->int_values[0] != 1920)
+        || (size->int_values[1] != 1080)) {
+        std::cout << "ERROR: Metadata \"studio:size\" was not written.\n";
+        return 1;
+    }
+
+    // Remove a single value.
+    graph.set_node_attr_i32(
+        shot_node, "mode", static_cast<int32_t>(ocg::MetadataMode::kRemove));
+    graph.execute(size_node, frames, cache);
+    auto spec_removed = graph.output_stream().clone_image_spec();
+    if ((find_metadata(spec_removed, "studio:shot") != nullptr)
+        || (find_metadata(spec_removed, "studio:size") == nullptr)) {
+        std::cout << "ERROR: Metadata \"studio:shot\" was not removed.\n";
+        return 1;
+    }
+
+    // Remove all values.
+    graph.set_node_attr_i32(
+        size_node, "mode", static_cast<int32_t>(ocg::MetadataMode::kRemoveAll));
+    graph.execute(size_node, frames, cache);
+    if (graph.output_stream().clone_image_spec().metadata.size() != 0) {
+        std::cout << "ERROR: Metadata was not removed.\n";
+        return 1;
+    }
+
+    if (debug_print) {
+        bench.stop();
+        bench.print("Test Node Metadata:");
+    }
+    return 0;
+}